@@ -0,0 +1,213 @@
+//! Float types the crate is generic over.
+//!
+//! # Determinism
+//!
+//! Sampling only uses basic IEEE 754 arithmetic and the functions of [`Float`]. Every one of
+//! them is correctly rounded except `powf`, `sin` and `cos`, which `std` takes from the
+//! platform's math library, so the fractals, easing curves and domain rotations using them can
+//! differ in the last bits between platforms.
+//!
+//! The `deterministic` feature takes every float function from `libm` instead, which is
+//! written in Rust, so a seed produces bit-identical values on every platform with IEEE 754
+//! floats. Targets computing with x87 extended precision, like `i586`, aren't covered.
+//!
+//! [`Fixed`] doesn't use the FPU at all, so tasks sampled with it match on every platform,
+//! x87 included, with or without the feature.
+mod fixed;
+
+use core::fmt::{Debug, Display};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+pub use fixed::Fixed;
+
+/// Floating point type the noise sources and tasks are generic over.
+///
+/// Implemented for `f32`, `f64` and [`Fixed`], so a custom task written against `T: Float`
+/// works with all of them:
+///
+/// ```
+/// use ferro_noise::float::Float;
+///
+/// fn average<T: Float>(a: T, b: T) -> T {
+///     (a + b) * T::HALF
+/// }
+///
+/// assert_eq!(average(1.0_f32, 2.0), 1.5);
+/// assert_eq!(average(1.0_f64, 2.0), 1.5);
+/// ```
+pub trait Float:
+    Copy
+    + Debug
+    + Default
+    + Display
+    + PartialEq
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Sum
+    + 'static
+{
+    const ZERO: Self;
+    const HALF: Self;
+    const ONE: Self;
+    const TWO: Self;
+    const EPSILON: Self;
+    const MIN: Self;
+    const MAX: Self;
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+
+    /// Converts from `f64`, rounding to the nearest value of this type.
+    fn from_f64(value: f64) -> Self;
+    fn from_i64(value: i64) -> Self;
+    fn from_usize(value: usize) -> Self;
+    fn to_f64(self) -> f64;
+    /// Converts with `as`, so the fractional part is dropped and values out of range saturate.
+    fn to_i64(self) -> i64;
+    /// Converts with `as`, so the fractional part is dropped and values out of range saturate.
+    fn to_usize(self) -> usize;
+
+    fn abs(self) -> Self;
+    fn floor(self) -> Self;
+    fn round(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    /// Sine of an angle in radians.
+    fn sin(self) -> Self;
+    /// Cosine of an angle in radians.
+    fn cos(self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+}
+
+/// Without `std` or with `deterministic` the float functions come from `libm`, `powf`, `sin`
+/// and `cos` may then differ from `std` in the last bit.
+macro_rules! float_impl {
+    ($type: ident) => {
+        impl Float for $type {
+            const ZERO: Self = 0.0;
+            const HALF: Self = 0.5;
+            const ONE: Self = 1.0;
+            const TWO: Self = 2.0;
+            const EPSILON: Self = $type::EPSILON;
+            const MIN: Self = $type::MIN;
+            const MAX: Self = $type::MAX;
+            const INFINITY: Self = $type::INFINITY;
+            const NEG_INFINITY: Self = $type::NEG_INFINITY;
+
+            fn from_f64(value: f64) -> Self {
+                value as $type
+            }
+
+            fn from_i64(value: i64) -> Self {
+                value as $type
+            }
+
+            fn from_usize(value: usize) -> Self {
+                value as $type
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn to_i64(self) -> i64 {
+                self as i64
+            }
+
+            fn to_usize(self) -> usize {
+                self as usize
+            }
+
+            #[cfg(all(feature = "std", not(feature = "deterministic")))]
+            fn abs(self) -> Self {
+                $type::abs(self)
+            }
+
+            #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+            fn abs(self) -> Self {
+                libm::Libm::<$type>::fabs(self)
+            }
+
+            #[cfg(all(feature = "std", not(feature = "deterministic")))]
+            fn floor(self) -> Self {
+                $type::floor(self)
+            }
+
+            #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+            fn floor(self) -> Self {
+                libm::Libm::<$type>::floor(self)
+            }
+
+            #[cfg(all(feature = "std", not(feature = "deterministic")))]
+            fn round(self) -> Self {
+                $type::round(self)
+            }
+
+            #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+            fn round(self) -> Self {
+                libm::Libm::<$type>::round(self)
+            }
+
+            #[cfg(all(feature = "std", not(feature = "deterministic")))]
+            fn sqrt(self) -> Self {
+                $type::sqrt(self)
+            }
+
+            #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+            fn sqrt(self) -> Self {
+                libm::Libm::<$type>::sqrt(self)
+            }
+
+            #[cfg(all(feature = "std", not(feature = "deterministic")))]
+            fn powf(self, n: Self) -> Self {
+                $type::powf(self, n)
+            }
+
+            #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+            fn powf(self, n: Self) -> Self {
+                libm::Libm::<$type>::pow(self, n)
+            }
+
+            #[cfg(all(feature = "std", not(feature = "deterministic")))]
+            fn sin(self) -> Self {
+                $type::sin(self)
+            }
+
+            #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+            fn sin(self) -> Self {
+                libm::Libm::<$type>::sin(self)
+            }
+
+            #[cfg(all(feature = "std", not(feature = "deterministic")))]
+            fn cos(self) -> Self {
+                $type::cos(self)
+            }
+
+            #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+            fn cos(self) -> Self {
+                libm::Libm::<$type>::cos(self)
+            }
+
+            fn min(self, other: Self) -> Self {
+                $type::min(self, other)
+            }
+
+            fn max(self, other: Self) -> Self {
+                $type::max(self, other)
+            }
+        }
+    };
+}
+
+float_impl!(f32);
+float_impl!(f64);
@@ -0,0 +1,516 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use super::Float;
+
+/// Signed Q16.16 fixed point number, computed with integer arithmetic only.
+///
+/// Sampling a task tree over `Fixed` gives identical results on every platform whatever its
+/// FPU does, e.g. for terrain in lockstep multiplayer simulations. The range is
+/// `[-32768, 32768)` with a resolution of `2^-16`, results out of range saturate:
+///
+/// ```
+/// use ferro_noise::float::{Fixed, Float};
+///
+/// let a = Fixed::from_f64(1.5);
+/// assert_eq!(a * Fixed::TWO, Fixed::from_f64(3.0));
+/// assert_eq!(a.to_bits(), 0x0001_8000);
+/// assert_eq!(Fixed::MAX + a, Fixed::MAX);
+/// ```
+///
+/// There is no NaN, dividing by zero saturates and roots or fractional powers of negative
+/// numbers are zero.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Hash)]
+pub struct Fixed(i32);
+
+/// `2^(2^-i)` for `i` in `1..=32` as Q32.32, used by `exp2`.
+const EXP2_STEPS: [u64; 32] = [
+    0x16a09e668,
+    0x1306fe0a3,
+    0x1172b83c8,
+    0x10b5586d0,
+    0x1059b0d31,
+    0x102c9a3e7,
+    0x10163daa0,
+    0x100b1afa6,
+    0x10058c86e,
+    0x1002c605e,
+    0x100162f39,
+    0x1000b175f,
+    0x100058ba0,
+    0x10002c5cc,
+    0x1000162e5,
+    0x10000b172,
+    0x1000058b9,
+    0x100002c5d,
+    0x10000162e,
+    0x100000b17,
+    0x10000058c,
+    0x1000002c6,
+    0x100000163,
+    0x1000000b1,
+    0x100000059,
+    0x10000002c,
+    0x100000016,
+    0x10000000b,
+    0x100000006,
+    0x100000003,
+    0x100000001,
+    0x100000001,
+];
+
+/// π as Q32.32.
+const PI: i64 = 13_493_037_705;
+
+impl Fixed {
+    /// Number of fractional bits.
+    pub const FRAC_BITS: u32 = 16;
+
+    const FRAC_MASK: i32 = (1 << Self::FRAC_BITS) - 1;
+
+    /// Creates a number from its raw Q16.16 representation.
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw Q16.16 representation.
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    /// Creates a number from raw bits wider than 32 bits, saturating out of range.
+    fn saturate(bits: i64) -> Self {
+        Self(bits.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+
+    /// Base 2 logarithm of a positive number as Q32.32.
+    fn log2(self) -> i64 {
+        debug_assert!(self.0 > 0);
+        let msb = 31 - self.0.leading_zeros() as i64;
+        // Mantissa in [1, 2) as Q32.32
+        let mut m = ((self.0 as u64) << 32) >> msb;
+        let mut frac = 0_i64;
+        for bit in (0..32).rev() {
+            m = ((m as u128 * m as u128) >> 32) as u64;
+            if m >= 2 << 32 {
+                m >>= 1;
+                frac |= 1 << bit;
+            }
+        }
+        ((msb - Self::FRAC_BITS as i64) << 32) + frac
+    }
+
+    /// Two to the power of a Q32.32 number.
+    fn exp2(y: i64) -> Self {
+        if y >= 15 << 32 {
+            return Self::MAX;
+        }
+        if y < -17 << 32 {
+            return Self::ZERO;
+        }
+
+        let int = y >> 32;
+        let frac = y & 0xffff_ffff;
+        let mut m = 1_u64 << 32;
+        for (i, step) in EXP2_STEPS.iter().enumerate() {
+            if frac & (1 << (31 - i)) != 0 {
+                m = ((m as u128 * *step as u128) >> 32) as u64;
+            }
+        }
+
+        // m * 2^int as Q16.16 with rounding
+        let shift = 16 - int;
+        Self::saturate(((m + (1 << (shift - 1))) >> shift) as i64)
+    }
+
+    /// Sine of a Q32.32 angle, with a Taylor series after reducing the angle to `[-π/2, π/2]`.
+    fn sin_q32(angle: i64) -> Self {
+        let mut x = angle % (2 * PI);
+        if x > PI {
+            x -= 2 * PI;
+        } else if x < -PI {
+            x += 2 * PI;
+        }
+        if x > PI / 2 {
+            x = PI - x;
+        } else if x < -PI / 2 {
+            x = -PI - x;
+        }
+
+        // The first term left out is below 2^-24 for |x| <= π/2
+        let x2 = ((x as i128 * x as i128) >> 32) as i64;
+        let mut term = x;
+        let mut sum = x;
+        for k in 1..=5 {
+            term = -(((term as i128 * x2 as i128) >> 32) as i64) / (2 * k * (2 * k + 1));
+            sum += term;
+        }
+        Self::saturate((sum + (1 << 15)) >> 16)
+    }
+
+    /// Power with an integer exponent by squaring.
+    fn powi(self, mut n: i64) -> Self {
+        let invert = n < 0;
+        n = n.abs();
+        let mut base = self;
+        let mut result = Self::ONE;
+        while n > 0 {
+            if n & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            n >>= 1;
+        }
+        if invert {
+            Self::ONE / result
+        } else {
+            result
+        }
+    }
+}
+
+impl Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.to_f64(), f)
+    }
+}
+
+impl Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let product = self.0 as i64 * rhs.0 as i64;
+        Self::saturate((product + (1 << (Self::FRAC_BITS - 1))) >> Self::FRAC_BITS)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        match (rhs.0, self.0.cmp(&0)) {
+            (0, Ordering::Less) => Self::MIN,
+            (0, Ordering::Equal) => Self::ZERO,
+            (0, Ordering::Greater) => Self::MAX,
+            _ => Self::saturate(((self.0 as i64) << Self::FRAC_BITS) / rhs.0 as i64),
+        }
+    }
+}
+
+impl Rem for Fixed {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        if rhs.0 == 0 {
+            Self::ZERO
+        } else {
+            Self::saturate(self.0 as i64 % rhs.0 as i64)
+        }
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.saturating_neg())
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Fixed {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Fixed {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Sum for Fixed {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Float for Fixed {
+    const ZERO: Self = Self(0);
+    const HALF: Self = Self(1 << (Self::FRAC_BITS - 1));
+    const ONE: Self = Self(1 << Self::FRAC_BITS);
+    const TWO: Self = Self(2 << Self::FRAC_BITS);
+    const EPSILON: Self = Self(1);
+    const MIN: Self = Self(i32::MIN);
+    const MAX: Self = Self(i32::MAX);
+    const INFINITY: Self = Self::MAX;
+    const NEG_INFINITY: Self = Self::MIN;
+
+    /// Decodes the bits of `value`, so the conversion doesn't depend on the FPU either.
+    /// Rounds half away from zero, NaN is zero.
+    fn from_f64(value: f64) -> Self {
+        let bits = value.to_bits();
+        let negative = bits >> 63 != 0;
+        let exponent = ((bits >> 52) & 0x7ff) as i64;
+        let mantissa = bits & ((1 << 52) - 1);
+
+        let magnitude = match exponent {
+            0x7ff if mantissa != 0 => 0,
+            0x7ff => i64::MAX,
+            // Subnormals are far below the resolution
+            0 => 0,
+            _ => {
+                // value = mantissa * 2^(exponent - 1075), shifted by the fractional bits
+                let mantissa = mantissa | (1 << 52);
+                let shift = exponent - 1075 + Self::FRAC_BITS as i64;
+                if shift > 10 {
+                    i64::MAX
+                } else if shift >= 0 {
+                    (mantissa << shift) as i64
+                } else if shift >= -54 {
+                    ((mantissa + (1 << (-shift - 1))) >> -shift) as i64
+                } else {
+                    0
+                }
+            }
+        };
+
+        Self::saturate(if negative { -magnitude } else { magnitude })
+    }
+
+    fn from_i64(value: i64) -> Self {
+        Self::saturate(value.saturating_mul(1 << Self::FRAC_BITS))
+    }
+
+    fn from_usize(value: usize) -> Self {
+        Self::from_i64(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+
+    fn to_f64(self) -> f64 {
+        self.0 as f64 / (1 << Self::FRAC_BITS) as f64
+    }
+
+    fn to_i64(self) -> i64 {
+        (self.0 / (1 << Self::FRAC_BITS)) as i64
+    }
+
+    fn to_usize(self) -> usize {
+        self.to_i64().max(0) as usize
+    }
+
+    fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    fn floor(self) -> Self {
+        Self(self.0 & !Self::FRAC_MASK)
+    }
+
+    fn round(self) -> Self {
+        let half = 1 << (Self::FRAC_BITS - 1);
+        let magnitude = (self.0 as i64).abs() + half;
+        let magnitude = magnitude & !(Self::FRAC_MASK as i64);
+        Self::saturate(if self.0 < 0 { -magnitude } else { magnitude })
+    }
+
+    /// Rounded down, zero for negative numbers.
+    fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+
+        // sqrt(bits * 2^16) is the result in Q16.16
+        let value = (self.0 as u64) << Self::FRAC_BITS;
+        let mut root = 0_u64;
+        let mut bit = 1_u64 << 62;
+        while bit > value {
+            bit >>= 2;
+        }
+        let mut rest = value;
+        while bit != 0 {
+            if rest >= root + bit {
+                rest -= root + bit;
+                root = (root >> 1) + bit;
+            } else {
+                root >>= 1;
+            }
+            bit >>= 2;
+        }
+        Self(root as i32)
+    }
+
+    /// Exact up to rounding for integer exponents, otherwise computed as `2^(n * log2(x))`.
+    fn powf(self, n: Self) -> Self {
+        if n.0 & Self::FRAC_MASK == 0 {
+            return self.powi(n.to_i64());
+        }
+        match self.0.cmp(&0) {
+            Ordering::Less => Self::ZERO,
+            Ordering::Equal if n.0 < 0 => Self::MAX,
+            Ordering::Equal => Self::ZERO,
+            Ordering::Greater => {
+                let y = (self.log2() as i128 * n.0 as i128) >> Self::FRAC_BITS;
+                Self::exp2(y.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+            }
+        }
+    }
+
+    fn sin(self) -> Self {
+        Self::sin_q32((self.0 as i64) << Self::FRAC_BITS)
+    }
+
+    fn cos(self) -> Self {
+        Self::sin_q32(((self.0 as i64) << Self::FRAC_BITS) + PI / 2)
+    }
+
+    fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fixed;
+    use crate::float::Float;
+
+    fn fixed(value: f64) -> Fixed {
+        Fixed::from_f64(value)
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(fixed(1.0), Fixed::ONE);
+        assert_eq!(fixed(-2.5).to_bits(), -0x0002_8000);
+        assert_eq!(fixed(0.1).to_bits(), 0x199a);
+        assert_eq!(fixed(1e-10), Fixed::ZERO);
+        assert_eq!(fixed(1e10), Fixed::MAX);
+        assert_eq!(fixed(f64::NEG_INFINITY), Fixed::MIN);
+        assert_eq!(fixed(f64::NAN), Fixed::ZERO);
+        assert_eq!(fixed(-2.5).to_f64(), -2.5);
+        assert_eq!(Fixed::from_i64(-3), fixed(-3.0));
+        assert_eq!(Fixed::from_usize(usize::MAX), Fixed::MAX);
+        assert_eq!(fixed(-2.75).to_i64(), -2);
+        assert_eq!(fixed(-2.75).to_usize(), 0);
+        assert_eq!(fixed(7.5).to_usize(), 7);
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(fixed(1.5) + fixed(2.25), fixed(3.75));
+        assert_eq!(fixed(1.5) - fixed(2.25), fixed(-0.75));
+        assert_eq!(fixed(1.5) * fixed(-2.25), fixed(-3.375));
+        assert_eq!(fixed(-3.375) / fixed(1.5), fixed(-2.25));
+        assert_eq!(fixed(7.5) % fixed(2.0), fixed(1.5));
+        assert_eq!(-Fixed::MIN, Fixed::MAX);
+        assert_eq!(Fixed::MIN - Fixed::ONE, Fixed::MIN);
+        assert_eq!(fixed(1000.0) * fixed(1000.0), Fixed::MAX);
+        assert_eq!(Fixed::ONE / Fixed::ZERO, Fixed::MAX);
+        assert_eq!(-Fixed::ONE / Fixed::ZERO, Fixed::MIN);
+        assert_eq!(Fixed::ZERO / Fixed::ZERO, Fixed::ZERO);
+        assert_eq!(
+            [fixed(0.5), fixed(0.25), fixed(-1.0)]
+                .into_iter()
+                .sum::<Fixed>(),
+            fixed(-0.25)
+        );
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(fixed(-2.25).abs(), fixed(2.25));
+        assert_eq!(fixed(-2.25).floor(), fixed(-3.0));
+        assert_eq!(fixed(2.75).floor(), fixed(2.0));
+        assert_eq!(fixed(-2.5).round(), fixed(-3.0));
+        assert_eq!(fixed(2.25).round(), fixed(2.0));
+        assert_eq!(fixed(6.25).sqrt(), fixed(2.5));
+        assert_eq!(fixed(2.0).sqrt().to_bits(), 0x0001_6a09);
+        assert_eq!(fixed(-4.0).sqrt(), Fixed::ZERO);
+        assert_eq!(fixed(-1.5).min(fixed(0.5)), fixed(-1.5));
+        assert_eq!(fixed(-1.5).max(fixed(0.5)), fixed(0.5));
+    }
+
+    #[test]
+    fn powers() {
+        assert_eq!(fixed(1.5).powf(fixed(3.0)), fixed(3.375));
+        assert_eq!(fixed(-2.0).powf(fixed(3.0)), fixed(-8.0));
+        assert_eq!(fixed(2.0).powf(fixed(-2.0)), fixed(0.25));
+        assert_eq!(fixed(0.0).powf(fixed(0.0)), Fixed::ONE);
+        assert_eq!(fixed(0.0).powf(fixed(0.5)), Fixed::ZERO);
+        assert_eq!(fixed(-2.0).powf(fixed(0.5)), Fixed::ZERO);
+        assert_eq!(fixed(100.0).powf(fixed(10.5)), Fixed::MAX);
+
+        for (x, n) in [
+            (2.0, 0.5),
+            (0.3, 1.7),
+            (5.5, -0.9),
+            (0.01, 0.25),
+            (1.9, 3.3),
+        ] {
+            let expected = x.powf(n);
+            let result = fixed(x).powf(fixed(n)).to_f64();
+            // Limited by the rounding of x and n to 16 fractional bits
+            let tolerance = 4.0 * (1.0 + expected * n.abs()) / 65536.0;
+            assert!(
+                (result - expected).abs() <= tolerance,
+                "{x}^{n}: {result} != {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn trigonometry() {
+        assert_eq!(Fixed::ZERO.sin(), Fixed::ZERO);
+        assert_eq!(Fixed::ZERO.cos(), Fixed::ONE);
+        assert_eq!(fixed(core::f64::consts::FRAC_PI_2).sin(), Fixed::ONE);
+
+        for x in [-100.0, -3.5, -1.0, 0.3, 1.5, 2.0, 3.1, 4.7, 1000.0] {
+            // Limited by the rounding of x to 16 fractional bits
+            let tolerance = 2.0 / 65536.0;
+            let sin = fixed(x).sin().to_f64();
+            let cos = fixed(x).cos().to_f64();
+            let x = fixed(x).to_f64();
+            assert!((sin - x.sin()).abs() <= tolerance, "sin {x}: {sin}");
+            assert!((cos - x.cos()).abs() <= tolerance, "cos {x}: {cos}");
+        }
+    }
+}
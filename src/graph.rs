@@ -0,0 +1,238 @@
+//! Description of a task graph that can be rendered as Graphviz DOT or Mermaid text.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt::Write;
+
+/// A task, labelled with its type and its main settings.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Node {
+    pub name: String,
+    pub kind: String,
+    pub params: Vec<(String, String)>,
+}
+
+/// A task sampled by another task, optionally labelled with the field it is used in.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub label: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl Node {
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![self.name.clone(), self.kind.clone()];
+        lines.extend(self.params.iter().map(|(k, v)| format!("{} = {}", k, v)));
+        lines
+    }
+}
+
+fn dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(value: &str) -> String {
+    value
+        .replace('"', "#quot;")
+        .replace('|', "#124;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+impl Graph {
+    /// Sorts nodes and edges so the output doesn't depend on the order tasks were found in.
+    pub fn sort(&mut self) {
+        self.nodes.sort();
+        self.edges.sort();
+        self.edges.dedup();
+    }
+
+    /// Renders the graph in the Graphviz DOT language, edges pointing from a task to the tasks
+    /// sampling it.
+    pub fn to_dot(&self) -> String {
+        let mut result = String::from("digraph {\n    node [shape=box];\n");
+        for node in self.nodes.iter() {
+            let label: Vec<String> = node.lines().iter().map(|x| dot_escape(x)).collect();
+            let _ = writeln!(
+                result,
+                "    \"{}\" [label=\"{}\"];",
+                dot_escape(&node.name),
+                label.join("\\n")
+            );
+        }
+        for edge in self.edges.iter() {
+            let _ = write!(
+                result,
+                "    \"{}\" -> \"{}\"",
+                dot_escape(&edge.from),
+                dot_escape(&edge.to)
+            );
+            match &edge.label {
+                Some(label) => {
+                    let _ = writeln!(result, " [label=\"{}\"];", dot_escape(label));
+                }
+                None => result.push_str(";\n"),
+            }
+        }
+        result.push_str("}\n");
+        result
+    }
+
+    /// Renders the graph as a Mermaid flowchart, edges pointing from a task to the tasks
+    /// sampling it.
+    pub fn to_mermaid(&self) -> String {
+        // Every name gets the index of its node as id, names only found in edges come after
+        let mut ids: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut unknown = vec![];
+        for (i, node) in self.nodes.iter().enumerate() {
+            ids.entry(&node.name).or_insert(i);
+        }
+        for name in self.edges.iter().flat_map(|x| [&x.from, &x.to]) {
+            if !ids.contains_key(name.as_str()) {
+                ids.insert(name, self.nodes.len() + unknown.len());
+                unknown.push(name);
+            }
+        }
+        let id = |name: &str| ids[name];
+
+        let mut result = String::from("flowchart LR\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let label: Vec<String> = node.lines().iter().map(|x| mermaid_escape(x)).collect();
+            let _ = writeln!(result, "    n{}[\"{}\"]", i, label.join("<br/>"));
+        }
+        for (i, name) in unknown.iter().enumerate() {
+            let _ = writeln!(
+                result,
+                "    n{}[\"{}\"]",
+                self.nodes.len() + i,
+                mermaid_escape(name)
+            );
+        }
+        for edge in self.edges.iter() {
+            match &edge.label {
+                Some(label) => {
+                    let _ = writeln!(
+                        result,
+                        "    n{} -->|{}| n{}",
+                        id(&edge.from),
+                        mermaid_escape(label),
+                        id(&edge.to)
+                    );
+                }
+                None => {
+                    let _ = writeln!(result, "    n{} --> n{}", id(&edge.from), id(&edge.to));
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Edge, Graph, Node};
+
+    fn graph() -> Graph {
+        Graph {
+            nodes: vec![
+                Node {
+                    name: "height".to_owned(),
+                    kind: "scale".to_owned(),
+                    params: vec![("scale".to_owned(), "2".to_owned())],
+                },
+                Node {
+                    name: "base \"a\"".to_owned(),
+                    kind: "fractal".to_owned(),
+                    params: vec![],
+                },
+            ],
+            edges: vec![Edge {
+                from: "base \"a\"".to_owned(),
+                to: "height".to_owned(),
+                label: Some("source".to_owned()),
+            }],
+        }
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut graph = graph();
+        graph.sort();
+        assert_eq!(
+            graph.to_dot(),
+            concat!(
+                "digraph {\n",
+                "    node [shape=box];\n",
+                "    \"base \\\"a\\\"\" [label=\"base \\\"a\\\"\\nfractal\"];\n",
+                "    \"height\" [label=\"height\\nscale\\nscale = 2\"];\n",
+                "    \"base \\\"a\\\"\" -> \"height\" [label=\"source\"];\n",
+                "}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn to_mermaid() {
+        let mut graph = graph();
+        graph.sort();
+        graph.edges[0].label = None;
+        assert_eq!(
+            graph.to_mermaid(),
+            concat!(
+                "flowchart LR\n",
+                "    n0[\"base #quot;a#quot;<br/>fractal\"]\n",
+                "    n1[\"height<br/>scale<br/>scale = 2\"]\n",
+                "    n0 --> n1\n",
+            )
+        );
+    }
+    #[test]
+    fn to_mermaid_unknown_names() {
+        let graph = Graph {
+            nodes: vec![Node {
+                name: "1".to_owned(),
+                kind: "constant".to_owned(),
+                params: vec![],
+            }],
+            edges: vec![
+                Edge {
+                    from: "0".to_owned(),
+                    to: "1".to_owned(),
+                    label: None,
+                },
+                Edge {
+                    from: "a b".to_owned(),
+                    to: "1".to_owned(),
+                    label: None,
+                },
+                Edge {
+                    from: "a_b".to_owned(),
+                    to: "1".to_owned(),
+                    label: None,
+                },
+            ],
+        };
+        assert_eq!(
+            graph.to_mermaid(),
+            concat!(
+                "flowchart LR\n",
+                "    n0[\"1<br/>constant\"]\n",
+                "    n1[\"0\"]\n",
+                "    n2[\"a b\"]\n",
+                "    n3[\"a_b\"]\n",
+                "    n1 --> n0\n",
+                "    n2 --> n0\n",
+                "    n3 --> n0\n",
+            )
+        );
+    }
+}
@@ -1,10 +1,20 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature must be enabled for float functions");
+
+pub mod float;
+pub mod graph;
 pub mod math;
 #[cfg(feature = "serde")]
 pub mod ser;
 pub mod source;
-mod task;
+pub mod task;
 
 pub mod prelude {
+    pub use super::float::Float;
     pub use super::source::f32::{Gradient, Noise, Perlin};
     pub use super::task::f32::*;
 }
@@ -1,14 +1,60 @@
 mod algorithm;
 mod easing;
 
+pub use algorithm::*;
 pub use easing::*;
 
+/// Non-generic versions of the functions, e.g. to be used as a `Blender`.
+macro_rules! math_aliases {
+    ($type: ty) => {
+        pub fn clamp(v: $type, min: $type, max: $type) -> $type {
+            super::clamp(v, min, max)
+        }
+
+        pub fn cubic_curve(t: $type) -> $type {
+            super::cubic_curve(t)
+        }
+
+        pub fn ease_in_out(t: $type, exp: $type) -> $type {
+            super::ease_in_out(t, exp)
+        }
+
+        pub fn lerp(a: $type, b: $type, f: $type) -> $type {
+            super::lerp(a, b, f)
+        }
+
+        pub fn linear_curve(t: $type) -> $type {
+            super::linear_curve(t)
+        }
+
+        pub fn max(a: $type, b: $type) -> $type {
+            super::max(a, b)
+        }
+
+        pub fn min(a: $type, b: $type) -> $type {
+            super::min(a, b)
+        }
+
+        pub fn nearly_eq(a: $type, b: $type) -> bool {
+            super::nearly_eq(a, b)
+        }
+
+        pub fn quintic_curve(t: $type) -> $type {
+            super::quintic_curve(t)
+        }
+    };
+}
+
 pub mod f32 {
-    pub use super::algorithm::f32::*;
-    pub use super::easing::f32::*;
+    math_aliases!(f32);
 }
 
 pub mod f64 {
-    pub use super::algorithm::f64::*;
-	pub use super::easing::f64::*;
+    math_aliases!(f64);
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+
+    math_aliases!(Fixed);
 }
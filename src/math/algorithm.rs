@@ -1,157 +1,105 @@
-macro_rules! clamp {
-    ($type: ty) => {
-        /// Clamps a value between a minimum and maximum value.
-        ///
-        /// # Arguments
-        ///
-        /// * `v`: The value to clamp
-        /// * `min`: The minimum value
-        /// * `max`: The maximum value
-        ///
-        /// # Examples
-        ///
-        /// ```
-        /// extern crate ferro_noise;
-        /// use ferro_noise::math::f32::{clamp};
-        ///
-        /// let result = clamp(6.0, 1.0, 5.0);
-        /// assert_eq!(result, 5.0);
-        /// ```
-        pub fn clamp(v: $type, min: $type, max: $type) -> $type {
-            v.min(max).max(min)
-        }
-    };
-}
-
-macro_rules! cubic {
-    ($type: ty) => {
-        /// Evaluates a cubic curve at a given time `t`, where `t` is typically in the range [0, 1].
-        ///
-        /// # Examples
-        ///
-        /// ```
-        /// extern crate ferro_noise;
-        /// use ferro_noise::math::f32::cubic_curve;
-        ///
-        /// let result = cubic_curve(2.0);
-        /// assert_eq!(result, -4.0);
-        /// ```
-        ///
-        /// # Notes
-        ///
-        /// This cubic curve function uses the formula `3t^2 − 2t^3`, where `t` is typically in the
-        /// range [0, 1]. The function returns `0` at `t = 1.5` and `-4` at `t = 2`.
-        pub fn cubic_curve(t: $type) -> $type {
-            // https://en.wikipedia.org/wiki/Cubic_Hermite_spline
-            // 3t^2 − 2t^3
-            (t * t) * (3.0 - (2.0 * t))
-        }
-    };
-}
+use crate::float::Float;
 
-macro_rules! lerp {
-    ($type: ty) => {
-        /// Linearly interpolate between two values by a given alpha value.
-        ///
-        /// # Arguments
-        ///
-        /// * `a`: The starting value to interpolate from
-        /// * `b`: The ending value to interpolate to
-        /// * `f`: The alpha value, typically in the range [0, 1]
-        ///
-        /// # Examples
-        ///
-        /// ```
-        /// extern crate ferro_noise;
-        /// use ferro_noise::math::f32::lerp;
-        ///
-        /// let result = lerp(0.0, 1.0, 0.4);
-        /// assert_eq!(result, 0.4);
-        /// ```
-        pub fn lerp(a: $type, b: $type, f: $type) -> $type {
-            a * (1.0 - f) + f * b
-        }
-    };
+/// Clamps a value between a minimum and maximum value.
+///
+/// # Arguments
+///
+/// * `v`: The value to clamp
+/// * `min`: The minimum value
+/// * `max`: The maximum value
+///
+/// # Examples
+///
+/// ```
+/// extern crate ferro_noise;
+/// use ferro_noise::math::clamp;
+///
+/// let result = clamp(6.0_f32, 1.0, 5.0);
+/// assert_eq!(result, 5.0);
+/// ```
+pub fn clamp<T: Float>(v: T, min: T, max: T) -> T {
+    v.min(max).max(min)
 }
 
-macro_rules! linear {
-    ($type: ty) => {
-        pub fn linear_curve(t: $type) -> $type {
-            t
-        }
-    };
+/// Evaluates a cubic curve at a given time `t`, where `t` is typically in the range [0, 1].
+///
+/// # Examples
+///
+/// ```
+/// extern crate ferro_noise;
+/// use ferro_noise::math::cubic_curve;
+///
+/// let result = cubic_curve(2.0_f32);
+/// assert_eq!(result, -4.0);
+/// ```
+///
+/// # Notes
+///
+/// This cubic curve function uses the formula `3t^2 − 2t^3`, where `t` is typically in the
+/// range [0, 1]. The function returns `0` at `t = 1.5` and `-4` at `t = 2`.
+pub fn cubic_curve<T: Float>(t: T) -> T {
+    // https://en.wikipedia.org/wiki/Cubic_Hermite_spline
+    // 3t^2 − 2t^3
+    (t * t) * (T::from_f64(3.0) - (T::TWO * t))
 }
 
-macro_rules! max {
-    ($type: ty) => {
-        pub fn max(a: $type, b: $type) -> $type {
-            a.max(b)
-        }
-    };
+/// Linearly interpolate between two values by a given alpha value.
+///
+/// # Arguments
+///
+/// * `a`: The starting value to interpolate from
+/// * `b`: The ending value to interpolate to
+/// * `f`: The alpha value, typically in the range [0, 1]
+///
+/// # Examples
+///
+/// ```
+/// extern crate ferro_noise;
+/// use ferro_noise::math::lerp;
+///
+/// let result = lerp(0.0_f32, 1.0, 0.4);
+/// assert_eq!(result, 0.4);
+/// ```
+pub fn lerp<T: Float>(a: T, b: T, f: T) -> T {
+    a * (T::ONE - f) + f * b
 }
 
-macro_rules! min {
-    ($type: ty) => {
-        pub fn min(a: $type, b: $type) -> $type {
-            a.min(b)
-        }
-    };
+pub fn linear_curve<T: Float>(t: T) -> T {
+    t
 }
 
-macro_rules! nearly_eq {
-    ($type: ty) => {
-        /// Simple check for EPSILON difference to determine equality
-        pub fn nearly_eq(a: $type, b: $type) -> bool {
-            (a - b).abs() < <$type>::EPSILON
-        }
-    };
+pub fn max<T: Float>(a: T, b: T) -> T {
+    a.max(b)
 }
 
-macro_rules! quintic {
-    ($type: ty) => {
-        /// Computes a quintic curve value for the given input value `t`.
-        ///
-        /// # Arguments
-        ///
-        /// * `t` - The input value for which to compute the quintic curve value.
-        ///
-        /// # Examples
-        ///
-        /// ```
-        /// extern crate ferro_noise;
-        /// use ferro_noise::math::f32::quintic_curve;
-        ///
-        /// let result = quintic_curve(1.0);
-        /// assert_eq!(result, 1.0);
-        /// ```
-        pub fn quintic_curve(t: $type) -> $type {
-            // https://mrl.nyu.edu/~perlin/noise/
-            // 6t^5 - 15t^4 + 10t^3
-            return t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
-        }
-    };
+pub fn min<T: Float>(a: T, b: T) -> T {
+    a.min(b)
 }
 
-pub mod f32 {
-    clamp!(f32);
-	cubic!(f32);
-    lerp!(f32);
-	linear!(f32);
-    min!(f32);
-    max!(f32);
-    nearly_eq!(f32);
-	quintic!(f32);
+/// Simple check for EPSILON difference to determine equality
+pub fn nearly_eq<T: Float>(a: T, b: T) -> bool {
+    (a - b).abs() < T::EPSILON
 }
 
-pub mod f64 {
-    clamp!(f64);
-	cubic!(f64);
-    lerp!(f64);
-	linear!(f64);
-    min!(f64);
-    max!(f64);
-    nearly_eq!(f64);
-	quintic!(f64);
+/// Computes a quintic curve value for the given input value `t`.
+///
+/// # Arguments
+///
+/// * `t` - The input value for which to compute the quintic curve value.
+///
+/// # Examples
+///
+/// ```
+/// extern crate ferro_noise;
+/// use ferro_noise::math::quintic_curve;
+///
+/// let result = quintic_curve(1.0_f32);
+/// assert_eq!(result, 1.0);
+/// ```
+pub fn quintic_curve<T: Float>(t: T) -> T {
+    // https://mrl.nyu.edu/~perlin/noise/
+    // 6t^5 - 15t^4 + 10t^3
+    t * t * t * (t * (t * T::from_f64(6.0) - T::from_f64(15.0)) + T::from_f64(10.0))
 }
 
 #[cfg(test)]
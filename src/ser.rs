@@ -1,36 +1,83 @@
+mod abs_config;
 mod aggregate_config;
 mod bias_config;
+mod cache_config;
+mod clamp_config;
+mod config_file;
+mod config_hash;
+mod curve_config;
+mod custom_config;
+mod exponent_config;
+mod expression;
 mod fractal_config;
 mod gradient_config;
+mod graph_config;
+mod invert_config;
+mod multi_gradient_config;
 mod name_or_const;
 mod noise;
+mod pow_config;
+mod reload;
 mod scale_config;
 mod scale_offset_config;
 mod selector_config;
+mod template_config;
+mod terrace_config;
 mod transform_domain_config;
+mod turbulence_config;
 
 pub mod f32 {
-    pub(crate) use super::aggregate_config::f32::*;
-    pub(crate) use super::bias_config::f32::*;
-    pub(crate) use super::fractal_config::f32::*;
-    pub(crate) use super::gradient_config::f32::*;
-    pub(crate) use super::name_or_const::f32::*;
+    pub use super::abs_config::f32::*;
+    pub use super::aggregate_config::f32::*;
+    pub use super::bias_config::f32::*;
+    pub use super::cache_config::f32::*;
+    pub use super::clamp_config::f32::*;
+    pub use super::config_file::f32::*;
+    pub use super::curve_config::f32::*;
+    pub use super::custom_config::f32::*;
+    pub use super::exponent_config::f32::*;
+    pub use super::fractal_config::f32::*;
+    pub use super::gradient_config::f32::*;
+    pub use super::graph_config::f32::*;
+    pub use super::invert_config::f32::*;
+    pub use super::multi_gradient_config::f32::*;
+    pub use super::name_or_const::f32::*;
     pub use super::noise::f32::*;
-    pub(crate) use super::scale_config::f32::*;
-    pub(crate) use super::scale_offset_config::f32::*;
-    pub(crate) use super::selector_config::f32::*;
-    pub(crate) use super::transform_domain_config::f32::*;
+    pub use super::pow_config::f32::*;
+    pub use super::reload::f32::*;
+    pub use super::scale_config::f32::*;
+    pub use super::scale_offset_config::f32::*;
+    pub use super::selector_config::f32::*;
+    pub use super::template_config::f32::*;
+    pub use super::terrace_config::f32::*;
+    pub use super::transform_domain_config::f32::*;
+    pub use super::turbulence_config::f32::*;
 }
 
 pub mod f64 {
-    pub(crate) use super::aggregate_config::f64::*;
-    pub(crate) use super::bias_config::f64::*;
-    pub(crate) use super::fractal_config::f64::*;
-    pub(crate) use super::gradient_config::f64::*;
-    pub(crate) use super::name_or_const::f64::*;
+    pub use super::abs_config::f64::*;
+    pub use super::aggregate_config::f64::*;
+    pub use super::bias_config::f64::*;
+    pub use super::cache_config::f64::*;
+    pub use super::clamp_config::f64::*;
+    pub use super::config_file::f64::*;
+    pub use super::curve_config::f64::*;
+    pub use super::custom_config::f64::*;
+    pub use super::exponent_config::f64::*;
+    pub use super::fractal_config::f64::*;
+    pub use super::gradient_config::f64::*;
+    pub use super::graph_config::f64::*;
+    pub use super::invert_config::f64::*;
+    pub use super::multi_gradient_config::f64::*;
+    pub use super::name_or_const::f64::*;
     pub use super::noise::f64::*;
-    pub(crate) use super::scale_config::f64::*;
-    pub(crate) use super::scale_offset_config::f64::*;
-    pub(crate) use super::selector_config::f64::*;
-    pub(crate) use super::transform_domain_config::f64::*;
+    pub use super::pow_config::f64::*;
+    pub use super::reload::f64::*;
+    pub use super::scale_config::f64::*;
+    pub use super::scale_offset_config::f64::*;
+    pub use super::selector_config::f64::*;
+    pub use super::template_config::f64::*;
+    pub use super::terrace_config::f64::*;
+    pub use super::transform_domain_config::f64::*;
+    pub use super::turbulence_config::f64::*;
 }
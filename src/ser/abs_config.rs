@@ -0,0 +1,154 @@
+macro_rules! abs_config {
+    () => {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct AbsConfig {
+            pub source: NameOrConst,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        impl Default for AbsConfig {
+            fn default() -> Self {
+                Self {
+                    source: 0.0.into(),
+                    cache: None,
+                }
+            }
+        }
+
+        impl TaskDependencies for AbsConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                push_named_to_vec!(r, self.source);
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for AbsConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
+        }
+
+        impl IntoTaskSource for AbsConfig {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
+                let mut builder = AbsBuilder::new();
+
+                add_task_to_builder!(self.source, builder, source, named_source, tree);
+
+                Ok(builder.link(tree).build().into())
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{AbsBuilder, TaskSource, TaskTree};
+    abs_config!();
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{AbsBuilder, TaskSource, TaskTree};
+    abs_config!();
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use std::collections::HashMap;
+
+        use crate::ser::f32::{AbsConfig, CacheConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [abs_a]
+                abs.source = "other"
+                abs.cached = true
+
+                [abs_b]
+                abs = { source = -1 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["abs_a"],
+                TaskConfig::Abs(AbsConfig {
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["abs_b"],
+                TaskConfig::Abs(AbsConfig {
+                    source: (-1.0).into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+
+    mod f64 {
+        use std::collections::HashMap;
+
+        use crate::ser::f64::{AbsConfig, CacheConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [abs_a]
+                abs.source = "other"
+                abs.cached = true
+
+                [abs_b]
+                abs = { source = -1 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["abs_a"],
+                TaskConfig::Abs(AbsConfig {
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["abs_b"],
+                TaskConfig::Abs(AbsConfig {
+                    source: (-1.0).into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+}
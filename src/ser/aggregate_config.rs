@@ -8,7 +8,13 @@ macro_rules! aggregate_config {
             pub operator: Operation,
             pub initial: $type,
             pub source: Vec<NameOrConst>,
-            pub cached: bool,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
         }
 
         impl Default for AggregateConfig {
@@ -17,7 +23,7 @@ macro_rules! aggregate_config {
                     operator: Operation::default(),
                     initial: 0.0,
                     source: vec![0.0.into()],
-                    cached: false,
+                    cache: None,
                 }
             }
         }
@@ -33,10 +39,26 @@ macro_rules! aggregate_config {
                     })
                     .collect()
             }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                for source in self.source.iter_mut() {
+                    rename_named!(*source, rename);
+                }
+            }
+        }
+
+        impl InlineTasks for AggregateConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                for (i, source) in self.source.iter_mut().enumerate() {
+                    push_inline_to_vec!(r, *source, format!("{}.source.{}", name, i));
+                }
+                r
+            }
         }
 
         impl IntoTaskSource for AggregateConfig {
-            fn config_into(&self, tree: &TaskTree) -> TaskSource {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
                 let mut builder = AggregatorBuilder::new();
 
                 builder.initial(self.initial).operation(self.operator);
@@ -49,23 +71,32 @@ macro_rules! aggregate_config {
                         NameOrConst::Value(x) => {
                             builder.add_task(<$type>::from(*x));
                         }
+                        NameOrConst::Inline(x) => {
+                            builder.add_task(x.config_into(tree)?);
+                        }
                     }
                 }
 
-                builder.link(tree).build().into()
+                Ok(builder.link(tree).build().into())
             }
         }
     };
 }
 
 pub mod f32 {
-    use crate::ser::f32::{IntoTaskSource, NameOrConst, TaskDependencies};
+    use crate::ser::f32::{
+        deserialize_cache, push_inline_to_vec, rename_named, serialize_cache, CacheConfig,
+        InlineTasks, IntoTaskSource, NameOrConst, TaskConfig, TaskDependencies,
+    };
     use crate::task::f32::{AggregatorBuilder, Operation, TaskSource, TaskTree};
     aggregate_config!(f32);
 }
 
 pub mod f64 {
-    use crate::ser::f64::{IntoTaskSource, NameOrConst, TaskDependencies};
+    use crate::ser::f64::{
+        deserialize_cache, push_inline_to_vec, rename_named, serialize_cache, CacheConfig,
+        InlineTasks, IntoTaskSource, NameOrConst, TaskConfig, TaskDependencies,
+    };
     use crate::task::f64::{AggregatorBuilder, Operation, TaskSource, TaskTree};
     aggregate_config!(f64);
 }
@@ -73,7 +104,7 @@ pub mod f64 {
 #[cfg(test)]
 mod test {
     mod f32 {
-        use crate::ser::f32::{AggregateConfig, TaskConfig};
+        use crate::ser::f32::{AggregateConfig, CacheConfig, TaskConfig};
         use crate::task::f32::Operation;
         use std::collections::HashMap;
 
@@ -99,8 +130,7 @@ mod test {
                     initial: 1.0,
                     operator: Operation::Sub,
                     source: vec![1.0.into(), "other".to_owned().into()],
-                    cached: true,
-                    ..Default::default()
+                    cache: Some(CacheConfig::default()),
                 })
             );
 
@@ -117,7 +147,7 @@ mod test {
     }
 
     mod f64 {
-        use crate::ser::f64::{AggregateConfig, TaskConfig};
+        use crate::ser::f64::{AggregateConfig, CacheConfig, TaskConfig};
         use crate::task::f64::Operation;
         use std::collections::HashMap;
 
@@ -143,8 +173,7 @@ mod test {
                     initial: 1.0,
                     operator: Operation::Sub,
                     source: vec![1.0.into(), "other".to_owned().into()],
-                    cached: true,
-                    ..Default::default()
+                    cache: Some(CacheConfig::default()),
                 })
             );
 
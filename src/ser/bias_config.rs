@@ -9,7 +9,13 @@ macro_rules! bias_config {
             pub source: NameOrConst,
             pub min: $type,
             pub max: $type,
-            pub cached: bool,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
         }
 
         impl Default for BiasConfig {
@@ -19,7 +25,7 @@ macro_rules! bias_config {
                     source: 0.0.into(),
                     min: 1.0,
                     max: 4.0,
-                    cached: false,
+                    cache: None,
                 }
             }
         }
@@ -31,10 +37,24 @@ macro_rules! bias_config {
                 push_named_to_vec!(r, self.source);
                 r
             }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.bias, rename);
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for BiasConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.bias, format!("{}.bias", name));
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
         }
 
         impl IntoTaskSource for BiasConfig {
-            fn config_into(&self, tree: &TaskTree) -> TaskSource {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
                 let mut builder = BiasBuilder::new();
 
                 builder.min(self.min).max(self.max);
@@ -46,6 +66,9 @@ macro_rules! bias_config {
                     NameOrConst::Value(x) => {
                         builder.source(<$type>::from(*x));
                     }
+                    NameOrConst::Inline(x) => {
+                        builder.source(x.config_into(tree)?);
+                    }
                 }
 
                 match &self.bias {
@@ -55,22 +78,31 @@ macro_rules! bias_config {
                     NameOrConst::Value(x) => {
                         builder.bias(<$type>::from(*x));
                     }
+                    NameOrConst::Inline(x) => {
+                        builder.bias(x.config_into(tree)?);
+                    }
                 }
 
-                builder.link(tree).build().into()
+                Ok(builder.link(tree).build().into())
             }
         }
     };
 }
 
 pub mod f32 {
-    use crate::ser::f32::{push_named_to_vec, IntoTaskSource, NameOrConst, TaskDependencies};
+    use crate::ser::f32::{
+        deserialize_cache, push_inline_to_vec, push_named_to_vec, rename_named, serialize_cache,
+        CacheConfig, InlineTasks, IntoTaskSource, NameOrConst, TaskConfig, TaskDependencies,
+    };
     use crate::task::f32::{BiasBuilder, TaskSource, TaskTree};
     bias_config!(f32);
 }
 
 pub mod f64 {
-    use crate::ser::f64::{push_named_to_vec, IntoTaskSource, NameOrConst, TaskDependencies};
+    use crate::ser::f64::{
+        deserialize_cache, push_inline_to_vec, push_named_to_vec, rename_named, serialize_cache,
+        CacheConfig, InlineTasks, IntoTaskSource, NameOrConst, TaskConfig, TaskDependencies,
+    };
     use crate::task::f64::{BiasBuilder, TaskSource, TaskTree};
     bias_config!(f64);
 }
@@ -80,7 +112,7 @@ mod test {
     mod f32 {
         use std::collections::HashMap;
 
-        use crate::ser::f32::{BiasConfig, TaskConfig};
+        use crate::ser::f32::{BiasConfig, CacheConfig, TaskConfig};
 
         #[test]
         fn deserialize() {
@@ -106,8 +138,7 @@ mod test {
                     max: 5.0,
                     source: "other".to_owned().into(),
                     bias: 1.0.into(),
-                    cached: true,
-                    ..Default::default()
+                    cache: Some(CacheConfig::default()),
                 })
             );
 
@@ -125,7 +156,7 @@ mod test {
     mod f64 {
         use std::collections::HashMap;
 
-        use crate::ser::f64::{BiasConfig, TaskConfig};
+        use crate::ser::f64::{BiasConfig, CacheConfig, TaskConfig};
 
         #[test]
         fn deserialize() {
@@ -151,8 +182,7 @@ mod test {
                     max: 5.0,
                     source: "other".to_owned().into(),
                     bias: 1.0.into(),
-                    cached: true,
-                    ..Default::default()
+                    cache: Some(CacheConfig::default()),
                 })
             );
 
@@ -0,0 +1,189 @@
+macro_rules! cache_config {
+    ($type: ty) => {
+        use serde::de::{self, MapAccess, Visitor};
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+        use std::fmt;
+
+        /// How the output of a task is cached, e.g. `cache = { mode = "lru", size = 256 }`.
+        ///
+        /// `cached = true` is still accepted as a shorthand for `cache = { mode = "last" }`.
+        #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct CacheConfig {
+            pub mode: CacheMode,
+            /// Number of points kept by `lru` and of cells kept by `grid`
+            pub size: usize,
+            /// Size of the cells of `grid`
+            pub resolution: $type,
+            /// How close points are reused, e.g. `tolerance = { relative = 1e-6 }`
+            pub tolerance: CacheTolerance,
+        }
+
+        impl Default for CacheConfig {
+            fn default() -> Self {
+                Self {
+                    mode: CacheMode::Last,
+                    size: 256,
+                    resolution: 1.0,
+                    tolerance: CacheTolerance::default(),
+                }
+            }
+        }
+
+        impl CacheConfig {
+            pub fn strategy(&self) -> CacheStrategy {
+                match self.mode {
+                    CacheMode::Last => CacheStrategy::Last,
+                    CacheMode::Lru => CacheStrategy::Lru(self.size),
+                    CacheMode::Grid => CacheStrategy::Grid {
+                        resolution: self.resolution,
+                        size: self.size,
+                    },
+                }
+            }
+
+            /// Returns an error if the strategy or the tolerance is invalid, see
+            /// [`CacheStrategy::check`] and [`CacheTolerance::check`].
+            pub fn check(&self) -> Result<(), String> {
+                self.strategy().check()?;
+                self.tolerance.check()
+            }
+        }
+
+        /// Writes the `cache` table, the field is skipped when there is no cache.
+        pub(crate) fn serialize_cache<S: Serializer>(
+            cache: &Option<CacheConfig>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            cache.unwrap_or_default().serialize(serializer)
+        }
+
+        struct CacheVisitor;
+
+        impl<'de> Visitor<'de> for CacheVisitor {
+            type Value = Option<CacheConfig>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a boolean or a cache table")
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(value.then(CacheConfig::default))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                let config = CacheConfig::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                config.check().map_err(de::Error::custom)?;
+                Ok(Some(config))
+            }
+        }
+
+        /// Reads either the `cached` flag or a `cache` table.
+        pub(crate) fn deserialize_cache<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<CacheConfig>, D::Error> {
+            deserializer.deserialize_any(CacheVisitor)
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::task::f32::{CacheMode, CacheStrategy, CacheTolerance};
+    cache_config!(f32);
+}
+
+pub mod f64 {
+    use crate::task::f64::{CacheMode, CacheStrategy, CacheTolerance};
+    cache_config!(f64);
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod test {
+    mod f32 {
+        use std::collections::HashMap;
+
+        use crate::ser::f32::{toml::from_str, CacheConfig, TaskConfig};
+        use crate::task::f32::{CacheMode, CacheStrategy, CacheTolerance, TaskSource};
+
+        #[test]
+        fn deserialize() {
+            let data = r#"
+            a = { scale = { cached = true } }
+            b = { scale = { cached = false } }
+            c = { scale = { cache = { mode = "grid", resolution = 0.5 } } }
+            d = { scale = {} }
+            e = { scale = { cache = { tolerance = { relative = 0.001 } } } }
+            "#;
+            let config: HashMap<String, TaskConfig> = toml::from_str(data).unwrap();
+
+            assert_eq!(config["a"].cache(), Some(CacheConfig::default()));
+            assert_eq!(config["b"].cache(), None);
+            assert_eq!(config["d"].cache(), None);
+            assert_eq!(
+                config["c"].cache(),
+                Some(CacheConfig {
+                    mode: CacheMode::Grid,
+                    resolution: 0.5,
+                    ..Default::default()
+                })
+            );
+
+            assert_eq!(
+                config["e"].cache().unwrap().tolerance,
+                CacheTolerance::Relative(0.001)
+            );
+
+            let data = toml::to_string(&config).unwrap();
+            let result: HashMap<String, TaskConfig> = toml::from_str(&data).unwrap();
+            assert_eq!(result, config);
+        }
+
+        #[test]
+        fn helper_from_str() {
+            let data = r#"
+            [a]
+            constant = 1.0
+
+            [b]
+            scale = { source = "a", scale = 2.0, cache = { mode = "lru", size = 16 } }
+            "#;
+            let mut tree = from_str(data).unwrap();
+            match tree.get("b") {
+                Some(TaskSource::Cache(x)) => {
+                    assert_eq!(x.borrow().strategy(), CacheStrategy::Lru(16))
+                }
+                _ => panic!("Task 'b' isn't cached"),
+            }
+            assert_eq!(tree.sample_1d("b", 1.0), 2.0);
+
+            let result = from_str("[a]\nscale = { cache = { mode = \"fifo\" } }");
+            assert!(matches!(result, Err(x) if x.contains("unknown variant `fifo`")));
+
+            let result = from_str("[a]\nscale = { cache = { mode = \"grid\", resolution = 0.0 } }");
+            assert!(matches!(result, Err(x) if x.contains("resolution must be greater than 0")));
+
+            // Only grid caches use the resolution
+            let result = from_str("[a]\nscale = { cache = { mode = \"lru\", resolution = 0.0 } }");
+            assert!(result.is_ok());
+
+            let result = from_str("[a]\nscale = { cache = { tolerance = { quantized = -1.0 } } }");
+            assert!(matches!(result, Err(x) if x.contains("tolerance must be greater than 0")));
+        }
+    }
+
+    mod f64 {
+        use crate::ser::f64::{CacheConfig, ScaleConfig};
+        use crate::task::f64::CacheStrategy;
+
+        #[test]
+        fn deserialize() {
+            let config: ScaleConfig =
+                toml::from_str("cache = { mode = \"lru\", size = 8 }").unwrap();
+            assert_eq!(config.cache.unwrap().strategy(), CacheStrategy::Lru(8));
+
+            let config: ScaleConfig = toml::from_str("cached = true").unwrap();
+            assert_eq!(config.cache, Some(CacheConfig::default()));
+            assert_eq!(config.cache.unwrap().strategy(), CacheStrategy::Last);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+macro_rules! clamp_config {
+    () => {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct ClampConfig {
+            pub max: NameOrConst,
+            pub min: NameOrConst,
+            pub source: NameOrConst,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        impl Default for ClampConfig {
+            fn default() -> Self {
+                Self {
+                    max: 1.0.into(),
+                    min: (-1.0).into(),
+                    source: 0.0.into(),
+                    cache: None,
+                }
+            }
+        }
+
+        impl TaskDependencies for ClampConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                push_named_to_vec!(r, self.max);
+                push_named_to_vec!(r, self.min);
+                push_named_to_vec!(r, self.source);
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.max, rename);
+                rename_named!(self.min, rename);
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for ClampConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.max, format!("{}.max", name));
+                push_inline_to_vec!(r, self.min, format!("{}.min", name));
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
+        }
+
+        impl IntoTaskSource for ClampConfig {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
+                let mut builder = ClampBuilder::new();
+
+                add_task_to_builder!(self.max, builder, max, named_max, tree);
+                add_task_to_builder!(self.min, builder, min, named_min, tree);
+                add_task_to_builder!(self.source, builder, source, named_source, tree);
+
+                Ok(builder.link(tree).build().into())
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{ClampBuilder, TaskSource, TaskTree};
+    clamp_config!();
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{ClampBuilder, TaskSource, TaskTree};
+    clamp_config!();
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use std::collections::HashMap;
+
+        use crate::ser::f32::{CacheConfig, ClampConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [clamp_a]
+                clamp.source = "other"
+                clamp.min = -0.2
+                clamp.max = "limit"
+                clamp.cached = true
+
+                [clamp_b]
+                clamp = { source = "other", max = 0.5 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["clamp_a"],
+                TaskConfig::Clamp(ClampConfig {
+                    max: "limit".to_owned().into(),
+                    min: (-0.2).into(),
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["clamp_b"],
+                TaskConfig::Clamp(ClampConfig {
+                    max: 0.5.into(),
+                    source: "other".to_owned().into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+
+    mod f64 {
+        use std::collections::HashMap;
+
+        use crate::ser::f64::{CacheConfig, ClampConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [clamp_a]
+                clamp.source = "other"
+                clamp.min = -0.2
+                clamp.max = "limit"
+                clamp.cached = true
+
+                [clamp_b]
+                clamp = { source = "other", max = 0.5 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["clamp_a"],
+                TaskConfig::Clamp(ClampConfig {
+                    max: "limit".to_owned().into(),
+                    min: (-0.2).into(),
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["clamp_b"],
+                TaskConfig::Clamp(ClampConfig {
+                    max: 0.5.into(),
+                    source: "other".to_owned().into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,519 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A file to merge into a config, with its tasks and templates prefixed by `namespace`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum IncludeConfig {
+    Path(String),
+    Namespaced {
+        path: String,
+        namespace: Option<String>,
+    },
+}
+
+impl IncludeConfig {
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Path(x) => x,
+            Self::Namespaced { path, .. } => path,
+        }
+    }
+
+    /// The explicit namespace, otherwise the file name without its extension.
+    pub fn namespace(&self) -> String {
+        match self {
+            Self::Namespaced {
+                namespace: Some(x), ..
+            } => x.clone(),
+            _ => Path::new(self.path())
+                .file_stem()
+                .map(|x| x.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn namespaced(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", namespace, name)
+    }
+}
+
+macro_rules! config_file {
+    ($type: ty) => {
+        use serde::de::{Error, MapAccess, Visitor};
+        use serde::{Deserialize, Deserializer};
+        use std::collections::{HashMap, HashSet};
+        use std::fmt;
+        use std::path::{Path, PathBuf};
+
+        /// The contents of a config file.
+        ///
+        /// Every key is a task name except `include`, a list of files to merge into this one,
+        /// `templates`, a table of reusable graph fragments, `params`, a table of
+        /// [`TaskConfig::Parameter`] tasks given by their initial value, and `share`, which
+        /// builds tasks with identical configs once and adds them under every name.
+        ///
+        /// Names used by an included file but not defined in it are left unprefixed, so they
+        /// refer to the tasks of the file including it, or of a file further up the includes.
+        ///
+        /// Only the file being loaded can set `share`, it's an error in included files.
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct ConfigFile {
+            pub include: Vec<IncludeConfig>,
+            pub share: bool,
+            pub templates: HashMap<String, TemplateConfig>,
+            pub tasks: HashMap<String, TaskConfig>,
+        }
+
+        impl<'de> Deserialize<'de> for ConfigFile {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct ConfigFileVisitor;
+
+                impl<'de> Visitor<'de> for ConfigFileVisitor {
+                    type Value = ConfigFile;
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        f.write_str("a map of task names to task configs")
+                    }
+
+                    fn visit_map<A: MapAccess<'de>>(
+                        self,
+                        mut map: A,
+                    ) -> Result<ConfigFile, A::Error> {
+                        let mut file = ConfigFile::default();
+                        let mut tasks = vec![];
+                        while let Some(key) = map.next_key::<String>()? {
+                            match key.as_str() {
+                                "include" => file.include = map.next_value()?,
+                                "share" => file.share = map.next_value()?,
+                                "templates" => file.templates = map.next_value()?,
+                                "params" => {
+                                    let params: HashMap<String, $type> = map.next_value()?;
+                                    tasks.extend(
+                                        params
+                                            .into_iter()
+                                            .map(|(k, v)| (k, TaskConfig::Parameter(v))),
+                                    );
+                                }
+                                _ => tasks.push((key, map.next_value()?)),
+                            }
+                        }
+
+                        for (name, task) in tasks {
+                            if file.tasks.insert(name.clone(), task).is_some() {
+                                return Err(A::Error::custom(format!(
+                                    "Task '{}' is defined more than once",
+                                    name
+                                )));
+                            }
+                        }
+                        Ok(file)
+                    }
+                }
+
+                deserializer.deserialize_map(ConfigFileVisitor)
+            }
+        }
+
+        impl From<HashMap<String, TaskConfig>> for ConfigFile {
+            fn from(tasks: HashMap<String, TaskConfig>) -> Self {
+                Self {
+                    tasks,
+                    ..Default::default()
+                }
+            }
+        }
+
+        impl ConfigFile {
+            /// Reads and parses the file at `path`, resolving its includes relative to it.
+            pub(crate) fn load(
+                path: &Path,
+                parse: &dyn Fn(&str) -> Result<ConfigFile, String>,
+            ) -> Result<Self, String> {
+                Self::load_tracked(path, parse, &mut vec![])
+            }
+
+            /// Same as [`ConfigFile::load`], appending the path of every file read to `files`.
+            pub(crate) fn load_tracked(
+                path: &Path,
+                parse: &dyn Fn(&str) -> Result<ConfigFile, String>,
+                files: &mut Vec<PathBuf>,
+            ) -> Result<Self, String> {
+                let path = path
+                    .canonicalize()
+                    .map_err(|x| format!("Unable to read '{}': {}", path.display(), x))?;
+                let data = std::fs::read_to_string(&path)
+                    .map_err(|x| format!("Unable to read '{}': {}", path.display(), x))?;
+
+                files.push(path.clone());
+
+                let mut file = parse(&data)?;
+                let dir = path.parent().unwrap_or(Path::new("."));
+                file.resolve_includes(dir, parse, &mut vec![path.clone()], files)?;
+                Ok(file)
+            }
+
+            /// Loads every included file, relative to `dir`, and merges it into this one.
+            ///
+            /// `stack` holds the files currently being included and `files` receives every
+            /// file read.
+            pub(crate) fn resolve_includes(
+                &mut self,
+                dir: &Path,
+                parse: &dyn Fn(&str) -> Result<ConfigFile, String>,
+                stack: &mut Vec<PathBuf>,
+                files: &mut Vec<PathBuf>,
+            ) -> Result<(), String> {
+                for include in std::mem::take(&mut self.include) {
+                    let path = dir.join(include.path());
+                    let path = path
+                        .canonicalize()
+                        .map_err(|x| format!("Unable to include '{}': {}", path.display(), x))?;
+
+                    if stack.contains(&path) {
+                        return Err(format!("Include cycle detected: '{}'", path.display()));
+                    }
+
+                    let data = std::fs::read_to_string(&path)
+                        .map_err(|x| format!("Unable to include '{}': {}", path.display(), x))?;
+                    files.push(path.clone());
+                    let mut file = parse(&data)?;
+                    if file.share {
+                        return Err(format!(
+                            "Included file '{}' can't set share",
+                            path.display()
+                        ));
+                    }
+
+                    stack.push(path.clone());
+                    file.resolve_includes(path.parent().unwrap_or(dir), parse, stack, files)?;
+                    stack.pop();
+
+                    self.merge(file.into_namespace(&include.namespace())?)?;
+                }
+                Ok(())
+            }
+
+            /// Prefixes every task and template with `namespace`, along with the references
+            /// to them. References to names this file doesn't define are kept as they are.
+            fn into_namespace(mut self, namespace: &str) -> Result<Self, String> {
+                hoist_inline_tasks(&mut self.tasks)?;
+                for template in self.templates.values_mut() {
+                    hoist_inline_tasks(&mut template.tasks)?;
+                }
+
+                let tasks: HashSet<String> = self.tasks.keys().cloned().collect();
+                let templates: HashSet<String> = self.templates.keys().cloned().collect();
+
+                let rename_task = |x: &mut TaskConfig, local: &HashSet<String>| {
+                    let rename = |name: &str| -> Option<String> {
+                        (tasks.contains(name) && !local.contains(name))
+                            .then(|| namespaced(namespace, name))
+                    };
+                    x.rename_dependencies(&rename);
+                    if let TaskConfig::Template(x) = x {
+                        if templates.contains(&x.name) {
+                            x.name = namespaced(namespace, &x.name);
+                        }
+                    }
+                };
+
+                let no_locals = HashSet::new();
+                let mut result = Self::default();
+                for (name, mut task) in self.tasks {
+                    rename_task(&mut task, &no_locals);
+                    result.tasks.insert(namespaced(namespace, &name), task);
+                }
+
+                for (name, mut template) in self.templates {
+                    // Names of the fragment's own tasks hide the tasks of the file
+                    let locals: HashSet<String> = template.tasks.keys().cloned().collect();
+                    for task in template.tasks.values_mut() {
+                        rename_task(task, &locals);
+                    }
+                    result
+                        .templates
+                        .insert(namespaced(namespace, &name), template);
+                }
+
+                Ok(result)
+            }
+
+            fn merge(&mut self, other: Self) -> Result<(), String> {
+                for (name, task) in other.tasks {
+                    if self.tasks.contains_key(&name) {
+                        return Err(format!("Task '{}' is defined more than once", name));
+                    }
+                    self.tasks.insert(name, task);
+                }
+
+                for (name, template) in other.templates {
+                    if self.templates.contains_key(&name) {
+                        return Err(format!("Template '{}' is defined more than once", name));
+                    }
+                    self.templates.insert(name, template);
+                }
+
+                Ok(())
+            }
+
+            /// Expands every template instance, leaving only tasks that can be built.
+            pub(crate) fn into_tasks(self) -> Result<HashMap<String, TaskConfig>, String> {
+                let mut tasks = self.tasks;
+                expand_templates(&mut tasks, &self.templates)?;
+                Ok(tasks)
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use super::namespaced;
+    pub use super::IncludeConfig;
+    use crate::ser::f32::{
+        expand_templates, hoist_inline_tasks, TaskConfig, TaskDependencies, TemplateConfig,
+    };
+    config_file!(f32);
+}
+
+pub mod f64 {
+    use super::namespaced;
+    pub use super::IncludeConfig;
+    use crate::ser::f64::{
+        expand_templates, hoist_inline_tasks, TaskConfig, TaskDependencies, TemplateConfig,
+    };
+    config_file!(f64);
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    /// Writes `files` into a fresh directory under the system temp directory
+    fn write_files(dir: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ferro_noise_{}", dir));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, data) in files {
+            std::fs::write(dir.join(name), data).unwrap();
+        }
+        dir
+    }
+
+    mod f32 {
+        use super::write_files;
+        use crate::ser::f32::{toml::from_file, ConfigFile, IncludeConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                include = ["biomes.toml", { path = "rivers.toml", namespace = "water" }]
+
+                [templates.hill]
+                output = "shape"
+                tasks.shape = { constant = 1.0 }
+
+                [const_a]
+                constant = 1.0
+            })
+            .unwrap();
+            let config: ConfigFile = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(
+                config.include,
+                vec![
+                    IncludeConfig::Path("biomes.toml".to_owned()),
+                    IncludeConfig::Namespaced {
+                        path: "rivers.toml".to_owned(),
+                        namespace: Some("water".to_owned())
+                    }
+                ]
+            );
+            assert_eq!(config.include[0].namespace(), "biomes");
+            assert_eq!(config.include[1].namespace(), "water");
+            assert_eq!(config.templates["hill"].output, "shape");
+            assert_eq!(config.tasks.len(), 1);
+            assert_eq!(config.tasks["const_a"], TaskConfig::Constant(1.0));
+        }
+
+        #[test]
+        fn include_namespaces_tasks() {
+            let dir = write_files(
+                "include_namespaces_tasks_f32",
+                &[
+                    (
+                        "main.toml",
+                        r#"
+                        include = ["biomes.toml"]
+
+                        [global]
+                        constant = 3.0
+
+                        [world]
+                        scale = { source = "biomes.hills", scale = 2.0 }
+                        "#,
+                    ),
+                    (
+                        "biomes.toml",
+                        r#"
+                        include = ["rivers.toml"]
+
+                        [base]
+                        constant = 0.5
+
+                        [hills]
+                        scale = { source = "base", scale = "global" }
+                        "#,
+                    ),
+                    (
+                        "rivers.toml",
+                        r#"
+                        [base]
+                        constant = 0.25
+                        "#,
+                    ),
+                ],
+            );
+
+            match from_file(dir.join("main.toml")) {
+                Ok(mut x) => {
+                    assert!(x.get("biomes.base").is_some());
+                    assert!(x.get("biomes.hills").is_some());
+                    assert!(x.get("biomes.rivers.base").is_some());
+                    assert!(x.get("base").is_none());
+
+                    assert_eq!(x.sample_1d("world", 1.0), 3.0);
+                    assert_eq!(x.sample_1d("biomes.rivers.base", 1.0), 0.25);
+                }
+                Err(x) => panic!("{}", x),
+            }
+        }
+
+        #[test]
+        fn include_name_collision() {
+            let dir = write_files(
+                "include_name_collision_f32",
+                &[
+                    (
+                        "main.toml",
+                        r#"
+                        include = [{ path = "biomes.toml", namespace = "" }]
+
+                        [base]
+                        constant = 3.0
+                        "#,
+                    ),
+                    (
+                        "biomes.toml",
+                        r#"
+                        [base]
+                        constant = 0.5
+                        "#,
+                    ),
+                ],
+            );
+
+            let result = from_file(dir.join("main.toml"));
+            assert!(matches!(result, Err(x) if x.contains("'base' is defined more than once")));
+        }
+
+        #[test]
+        fn include_cycle() {
+            let dir = write_files(
+                "include_cycle_f32",
+                &[
+                    ("a.toml", "include = [\"b.toml\"]"),
+                    ("b.toml", "include = [\"c.toml\"]"),
+                    ("c.toml", "include = [\"a.toml\"]"),
+                ],
+            );
+
+            let result = from_file(dir.join("a.toml"));
+            assert!(matches!(result, Err(x) if x.contains("Include cycle detected")));
+        }
+
+        #[test]
+        fn include_missing() {
+            let dir = write_files(
+                "include_missing_f32",
+                &[("a.toml", "include = [\"b.toml\"]")],
+            );
+
+            let result = from_file(dir.join("a.toml"));
+            assert!(matches!(result, Err(x) if x.contains("Unable to include")));
+        }
+
+        #[test]
+        fn include_share() {
+            let dir = write_files(
+                "include_share_f32",
+                &[
+                    ("a.toml", "include = [\"b.toml\"]"),
+                    ("b.toml", "share = true"),
+                ],
+            );
+
+            let result = from_file(dir.join("a.toml"));
+            assert!(matches!(result, Err(x) if x.contains("can't set share")));
+        }
+    }
+
+    mod f64 {
+        use super::write_files;
+        use crate::ser::f64::toml::from_file;
+
+        #[test]
+        fn include_namespaces_tasks() {
+            let dir = write_files(
+                "include_namespaces_tasks_f64",
+                &[
+                    (
+                        "main.toml",
+                        r#"
+                        include = [{ path = "biomes.toml", namespace = "land" }]
+
+                        [world]
+                        scale = { source = "land.hills", scale = 2.0 }
+                        "#,
+                    ),
+                    (
+                        "biomes.toml",
+                        r#"
+                        [base]
+                        constant = 0.5
+
+                        [hills]
+                        scale = { source = "base", scale = 3.0 }
+                        "#,
+                    ),
+                ],
+            );
+
+            match from_file(dir.join("main.toml")) {
+                Ok(mut x) => {
+                    assert!(x.get("land.base").is_some());
+                    assert_eq!(x.sample_1d("world", 1.0), 3.0);
+                }
+                Err(x) => panic!("{}", x),
+            }
+        }
+
+        #[test]
+        fn include_cycle() {
+            let dir = write_files(
+                "include_cycle_f64",
+                &[
+                    ("a.toml", "include = [\"b.toml\"]"),
+                    ("b.toml", "include = [\"a.toml\"]"),
+                ],
+            );
+
+            let result = from_file(dir.join("a.toml"));
+            assert!(matches!(result, Err(x) if x.contains("Include cycle detected")));
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use serde::de::value::Error;
+use serde::ser::{
+    SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant,
+};
+use serde::{Serialize, Serializer};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Hashes every field of `value` through its [`Serialize`] impl, so configs holding floats
+/// can be looked up by structure.
+///
+/// Floats are hashed by their bits and maps in the order they serialize, so equal values may
+/// hash differently only when they hold `-0.0` or unordered maps. Values with the same hash
+/// still need to be compared.
+pub(crate) fn structural_hash<V: Serialize + ?Sized>(value: &V) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    // Hashing never fails, the error type is only there to satisfy `Serializer`
+    let _ = value.serialize(HashSerializer(&mut hasher));
+    hasher.finish()
+}
+
+/// Kind of each serialized item, written before it so different shapes can't collide.
+#[derive(Hash)]
+enum Tag {
+    Bool,
+    Int,
+    UInt,
+    Float,
+    Char,
+    Str,
+    Bytes,
+    None,
+    Some,
+    Unit,
+    Variant,
+    Seq,
+    Map,
+    Struct,
+    End,
+}
+
+struct HashSerializer<'a>(&'a mut DefaultHasher);
+
+impl HashSerializer<'_> {
+    fn write<H: Hash>(&mut self, tag: Tag, value: H) -> Result<(), Error> {
+        tag.hash(self.0);
+        value.hash(self.0);
+        Ok(())
+    }
+
+    fn element<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<(), Error> {
+        value.serialize(HashSerializer(self.0))
+    }
+}
+
+impl<'a> Serializer for HashSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(mut self, v: bool) -> Result<(), Error> {
+        self.write(Tag::Bool, v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(mut self, v: i64) -> Result<(), Error> {
+        self.write(Tag::Int, v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(mut self, v: u64) -> Result<(), Error> {
+        self.write(Tag::UInt, v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(mut self, v: f64) -> Result<(), Error> {
+        self.write(Tag::Float, v.to_bits())
+    }
+
+    fn serialize_char(mut self, v: char) -> Result<(), Error> {
+        self.write(Tag::Char, v)
+    }
+
+    fn serialize_str(mut self, v: &str) -> Result<(), Error> {
+        self.write(Tag::Str, v)
+    }
+
+    fn serialize_bytes(mut self, v: &[u8]) -> Result<(), Error> {
+        self.write(Tag::Bytes, v)
+    }
+
+    fn serialize_none(mut self) -> Result<(), Error> {
+        self.write(Tag::None, ())
+    }
+
+    fn serialize_some<V: Serialize + ?Sized>(mut self, value: &V) -> Result<(), Error> {
+        self.write(Tag::Some, ())?;
+        self.element(value)
+    }
+
+    fn serialize_unit(mut self) -> Result<(), Error> {
+        self.write(Tag::Unit, ())
+    }
+
+    fn serialize_unit_struct(mut self, name: &'static str) -> Result<(), Error> {
+        self.write(Tag::Struct, name)
+    }
+
+    fn serialize_unit_variant(
+        mut self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.write(Tag::Variant, variant)
+    }
+
+    fn serialize_newtype_struct<V: Serialize + ?Sized>(
+        mut self,
+        name: &'static str,
+        value: &V,
+    ) -> Result<(), Error> {
+        self.write(Tag::Struct, name)?;
+        self.element(value)
+    }
+
+    fn serialize_newtype_variant<V: Serialize + ?Sized>(
+        mut self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &V,
+    ) -> Result<(), Error> {
+        self.write(Tag::Variant, variant)?;
+        self.element(value)
+    }
+
+    fn serialize_seq(mut self, _: Option<usize>) -> Result<Self, Error> {
+        self.write(Tag::Seq, ())?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(mut self, _: usize) -> Result<Self, Error> {
+        self.write(Tag::Seq, ())?;
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(mut self, name: &'static str, _: usize) -> Result<Self, Error> {
+        self.write(Tag::Struct, name)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        mut self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Self, Error> {
+        self.write(Tag::Variant, variant)?;
+        Ok(self)
+    }
+
+    fn serialize_map(mut self, _: Option<usize>) -> Result<Self, Error> {
+        self.write(Tag::Map, ())?;
+        Ok(self)
+    }
+
+    fn serialize_struct(mut self, name: &'static str, _: usize) -> Result<Self, Error> {
+        self.write(Tag::Struct, name)?;
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        mut self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Self, Error> {
+        self.write(Tag::Variant, variant)?;
+        Ok(self)
+    }
+}
+
+/// Compound values hash their elements in order and an end marker, so nested sequences of
+/// different lengths stay distinct.
+macro_rules! hash_elements {
+    ($trait: ident, $method: ident) => {
+        impl $trait for HashSerializer<'_> {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<(), Error> {
+                self.element(value)
+            }
+
+            fn end(mut self) -> Result<(), Error> {
+                self.write(Tag::End, ())
+            }
+        }
+    };
+}
+
+hash_elements!(SerializeSeq, serialize_element);
+hash_elements!(SerializeTuple, serialize_element);
+hash_elements!(SerializeTupleStruct, serialize_field);
+hash_elements!(SerializeTupleVariant, serialize_field);
+
+impl SerializeMap for HashSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<V: Serialize + ?Sized>(&mut self, key: &V) -> Result<(), Error> {
+        self.element(key)
+    }
+
+    fn serialize_value<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(mut self) -> Result<(), Error> {
+        self.write(Tag::End, ())
+    }
+}
+
+/// Fields are hashed with their name, skipped fields are left out entirely.
+macro_rules! hash_fields {
+    ($trait: ident) => {
+        impl $trait for HashSerializer<'_> {
+            type Ok = ();
+            type Error = Error;
+
+            fn serialize_field<V: Serialize + ?Sized>(
+                &mut self,
+                key: &'static str,
+                value: &V,
+            ) -> Result<(), Error> {
+                key.hash(self.0);
+                self.element(value)
+            }
+
+            fn end(mut self) -> Result<(), Error> {
+                self.write(Tag::End, ())
+            }
+        }
+    };
+}
+
+hash_fields!(SerializeStruct);
+hash_fields!(SerializeStructVariant);
+
+#[cfg(test)]
+mod test {
+    use super::structural_hash;
+    use crate::ser::f32::{ScaleConfig, TaskConfig};
+
+    #[test]
+    fn structural_hash_configs() {
+        let config = |scale: f32| {
+            TaskConfig::Scale(ScaleConfig {
+                source: "a".to_owned().into(),
+                scale: scale.into(),
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(structural_hash(&config(2.0)), structural_hash(&config(2.0)));
+        assert_ne!(structural_hash(&config(2.0)), structural_hash(&config(3.0)));
+        assert_ne!(
+            structural_hash(&config(0.0)),
+            structural_hash(&TaskConfig::Constant(0.0))
+        );
+        assert_ne!(
+            structural_hash(&vec![vec![1], vec![]]),
+            structural_hash(&vec![vec![], vec![1]])
+        );
+    }
+}
@@ -0,0 +1,174 @@
+macro_rules! curve_config {
+    ($type: ty) => {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct CurveConfig {
+            pub interpolation: Interpolation,
+            /// Control points as `[in, out]` pairs
+            pub points: Vec<[$type; 2]>,
+            pub source: NameOrConst,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        impl Default for CurveConfig {
+            fn default() -> Self {
+                Self {
+                    interpolation: Interpolation::default(),
+                    points: vec![],
+                    source: 0.0.into(),
+                    cache: None,
+                }
+            }
+        }
+
+        impl TaskDependencies for CurveConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                push_named_to_vec!(r, self.source);
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for CurveConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
+        }
+
+        impl IntoTaskSource for CurveConfig {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
+                let mut builder = CurveBuilder::new();
+                builder
+                    .interpolation(self.interpolation)
+                    .points(self.points.clone());
+
+                add_task_to_builder!(self.source, builder, source, named_source, tree);
+
+                Ok(builder.link(tree).build().into())
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{CurveBuilder, Interpolation, TaskSource, TaskTree};
+    curve_config!(f32);
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{CurveBuilder, Interpolation, TaskSource, TaskTree};
+    curve_config!(f64);
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use std::collections::HashMap;
+
+        use crate::ser::f32::{CacheConfig, CurveConfig, TaskConfig};
+        use crate::task::f32::Interpolation;
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [curve_a]
+                curve.source = "height"
+                curve.interpolation = "monotone_cubic"
+                curve.points = [[-1.0, -1.0], [0.0, 0.2], [1.0, 1.0]]
+                curve.cached = true
+
+                [curve_b]
+                curve = { source = 0.5, points = [[0.0, 1.0]] }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["curve_a"],
+                TaskConfig::Curve(CurveConfig {
+                    interpolation: Interpolation::MonotoneCubic,
+                    points: vec![[-1.0, -1.0], [0.0, 0.2], [1.0, 1.0]],
+                    source: "height".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["curve_b"],
+                TaskConfig::Curve(CurveConfig {
+                    points: vec![[0.0, 1.0]],
+                    source: 0.5.into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+
+    mod f64 {
+        use std::collections::HashMap;
+
+        use crate::ser::f64::{CacheConfig, CurveConfig, TaskConfig};
+        use crate::task::f64::Interpolation;
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [curve_a]
+                curve.source = "height"
+                curve.interpolation = "monotone_cubic"
+                curve.points = [[-1.0, -1.0], [0.0, 0.2], [1.0, 1.0]]
+                curve.cached = true
+
+                [curve_b]
+                curve = { source = 0.5, points = [[0.0, 1.0]] }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["curve_a"],
+                TaskConfig::Curve(CurveConfig {
+                    interpolation: Interpolation::MonotoneCubic,
+                    points: vec![[-1.0, -1.0], [0.0, 0.2], [1.0, 1.0]],
+                    source: "height".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["curve_b"],
+                TaskConfig::Curve(CurveConfig {
+                    points: vec![[0.0, 1.0]],
+                    source: 0.5.into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,566 @@
+use serde::de::value::{Error, MapAccessDeserializer, MapDeserializer, SeqDeserializer};
+use serde::de::{IntoDeserializer, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A setting of a custom task, kept as written until the task type it belongs to reads it.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+#[serde(untagged)]
+pub enum ConfigValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    List(Vec<ConfigValue>),
+    Table(BTreeMap<String, ConfigValue>),
+}
+
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(x) => write!(f, "{}", x),
+            Self::Int(x) => write!(f, "{}", x),
+            Self::Float(x) => write!(f, "{}", x),
+            Self::String(x) => write!(f, "{:?}", x),
+            Self::List(x) => {
+                let items: Vec<String> = x.iter().map(|x| x.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Self::Table(x) => {
+                let items: Vec<String> = x.iter().map(|(k, v)| format!("{} = {}", k, v)).collect();
+                write!(f, "{{ {} }}", items.join(", "))
+            }
+        }
+    }
+}
+
+/// Lets the config type of a custom task be deserialized from its settings.
+impl<'de> Deserializer<'de> for ConfigValue {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Self::Bool(x) => visitor.visit_bool(x),
+            Self::Int(x) => visitor.visit_i64(x),
+            Self::Float(x) => visitor.visit_f64(x),
+            Self::String(x) => visitor.visit_string(x),
+            Self::List(x) => {
+                let mut seq = SeqDeserializer::new(x.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Self::Table(x) => {
+                let mut map = MapDeserializer::new(x.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    /// Unit variants are written as a string, other variants as a table with one entry.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Self::String(x) => visitor.visit_enum(x.into_deserializer()),
+            Self::Table(x) => visitor.visit_enum(MapAccessDeserializer::new(MapDeserializer::new(
+                x.into_iter(),
+            ))),
+            x => x.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for ConfigValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! custom_config {
+    ($type: ty) => {
+        use serde::de::DeserializeOwned;
+        use serde::{Deserialize, Serialize};
+        use std::cell::RefCell;
+        use std::collections::{BTreeMap, HashMap};
+        use std::rc::Rc;
+
+        /// Config of a task type registered with [`register_custom_task`], e.g.
+        /// `custom = { type = "river_mask", width = 2.0, inputs = { source = "terrain" } }`.
+        ///
+        /// `inputs` gives the tasks the custom task samples by field name, every other setting
+        /// is read by the config type the task was registered with.
+        #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, PartialOrd)]
+        pub struct CustomConfig {
+            #[serde(rename = "type")]
+            pub kind: String,
+            #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+            pub inputs: BTreeMap<String, NameOrConst>,
+            #[serde(flatten)]
+            pub settings: BTreeMap<String, ConfigValue>,
+            #[serde(
+                default,
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        type BuildCustomTask = dyn Fn(ConfigValue, &HashMap<String, TaskSource>) -> TaskSource;
+
+        struct CustomTaskType {
+            check: fn(ConfigValue) -> Result<(), String>,
+            build: Rc<BuildCustomTask>,
+        }
+
+        thread_local! {
+            static CUSTOM_TASKS: RefCell<HashMap<String, CustomTaskType>> =
+                RefCell::new(HashMap::new());
+        }
+
+        /// Lets configs use the task type `kind` as a [`CustomConfig`].
+        ///
+        /// The settings of the config are deserialized into `C` and given to `build` with
+        /// the tasks of its `inputs`, missing inputs aren't in the map. Registering a type
+        /// again replaces it. Types are registered for the current thread only and must be
+        /// registered before building a tree that uses them.
+        pub fn register_custom_task<C, R, F>(kind: impl Into<String>, build: F)
+        where
+            C: DeserializeOwned,
+            R: CustomTask<$type> + 'static,
+            F: Fn(C, &HashMap<String, TaskSource>) -> R + 'static,
+        {
+            let task_type = CustomTaskType {
+                check: |settings| {
+                    C::deserialize(settings)
+                        .map(|_| ())
+                        .map_err(|x| x.to_string())
+                },
+                build: Rc::new(move |settings, inputs| {
+                    let config = C::deserialize(settings).expect("settings are checked");
+                    TaskSource::custom(build(config, inputs))
+                }),
+            };
+            CUSTOM_TASKS.with(|x| x.borrow_mut().insert(kind.into(), task_type));
+        }
+
+        /// Checks that every custom task in `tasks` is registered and that its settings
+        /// match the registered config type.
+        pub(crate) fn check_custom_tasks(
+            tasks: &HashMap<String, TaskConfig>,
+        ) -> Result<(), String> {
+            for (name, task) in tasks.iter() {
+                if let TaskConfig::Custom(x) = task {
+                    x.check().map_err(|e| format!("Custom task '{}': {}", name, e))?;
+                }
+            }
+            Ok(())
+        }
+
+        impl CustomConfig {
+            fn check(&self) -> Result<(), String> {
+                CUSTOM_TASKS.with(|x| match x.borrow().get(&self.kind) {
+                    Some(task_type) => (task_type.check)(ConfigValue::Table(self.settings.clone())),
+                    None => Err(format!("Task type '{}' isn't registered", self.kind)),
+                })
+            }
+        }
+
+        impl TaskDependencies for CustomConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                for value in self.inputs.values() {
+                    push_named_to_vec!(r, value);
+                }
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                for value in self.inputs.values_mut() {
+                    rename_named!(*value, rename);
+                }
+            }
+        }
+
+        impl InlineTasks for CustomConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                for (key, value) in self.inputs.iter_mut() {
+                    push_inline_to_vec!(r, *value, format!("{}.{}", name, key));
+                }
+                r
+            }
+        }
+
+        impl IntoTaskSource for CustomConfig {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
+                let inputs = self
+                    .inputs
+                    .iter()
+                    .map(|(key, value)| {
+                        let task = match value {
+                            NameOrConst::Named(x) => {
+                                tree.get(x).cloned().unwrap_or(TaskSource::Constant(0.0))
+                            }
+                            NameOrConst::Value(x) => TaskSource::Constant(*x),
+                            NameOrConst::Inline(x) => x.config_into(tree)?,
+                        };
+                        Ok((key.clone(), task))
+                    })
+                    .collect::<Result<HashMap<String, TaskSource>, String>>()?;
+
+                let build = CUSTOM_TASKS.with(|x| match x.borrow().get(&self.kind) {
+                    Some(task_type) => Ok(task_type.build.clone()),
+                    None => Err(format!(
+                        "Task type '{}' must be registered before it is built",
+                        self.kind
+                    )),
+                })?;
+                Ok(build(ConfigValue::Table(self.settings.clone()), &inputs))
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    pub use super::ConfigValue;
+    use crate::ser::f32::{
+        deserialize_cache, push_inline_to_vec, push_named_to_vec, rename_named, serialize_cache,
+        CacheConfig, InlineTasks, IntoTaskSource, NameOrConst, TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{CustomTask, TaskSource, TaskTree};
+    custom_config!(f32);
+}
+
+pub mod f64 {
+    pub use super::ConfigValue;
+    use crate::ser::f64::{
+        deserialize_cache, push_inline_to_vec, push_named_to_vec, rename_named, serialize_cache,
+        CacheConfig, InlineTasks, IntoTaskSource, NameOrConst, TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{CustomTask, TaskSource, TaskTree};
+    custom_config!(f64);
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+
+    use crate::float::Float;
+    use crate::task::{CustomTask, Task, TaskSource};
+
+    #[derive(Clone, Copy, Debug, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Direction {
+        Up,
+        Down,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ShiftConfig<T> {
+        amount: T,
+        direction: Direction,
+    }
+
+    /// Custom task moving its source up or down by a constant amount.
+    #[derive(Debug)]
+    struct Shift<T: Float> {
+        amount: T,
+        source: TaskSource<T>,
+    }
+
+    impl<T: Float> Shift<T> {
+        fn new(config: ShiftConfig<T>, source: Option<&TaskSource<T>>) -> Self {
+            let amount = match config.direction {
+                Direction::Up => config.amount,
+                Direction::Down => -config.amount,
+            };
+            Self {
+                amount,
+                source: source.cloned().unwrap_or(TaskSource::Constant(T::ZERO)),
+            }
+        }
+    }
+
+    impl<T: Float> Task<T> for Shift<T> {
+        fn sample_1d(&mut self, x: T) -> T {
+            self.source.sample_1d(x) + self.amount
+        }
+
+        fn sample_2d(&mut self, x: T, y: T) -> T {
+            self.source.sample_2d(x, y) + self.amount
+        }
+
+        fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+            self.source.sample_3d(x, y, z) + self.amount
+        }
+    }
+
+    impl<T: Float> CustomTask<T> for Shift<T> {
+        fn kind(&self) -> &'static str {
+            "shift"
+        }
+
+        fn visit_inputs_mut(&mut self, f: &mut dyn FnMut(&str, &mut TaskSource<T>)) {
+            f("source", &mut self.source);
+        }
+    }
+
+    mod f32 {
+        use std::collections::{BTreeMap, HashMap};
+
+        use super::{Shift, ShiftConfig};
+        use crate::ser::f32::{
+            register_custom_task,
+            toml::{from_str, graph_from_str, to_string},
+            ConfigValue, CustomConfig, TaskConfig,
+        };
+
+        const CONFIG: &str = r#"
+        [base]
+        constant = 0.5
+
+        [shifted.custom]
+        type = "shift"
+        amount = 0.25
+        direction = "down"
+        inputs.source = "base"
+        cached = true
+        "#;
+
+        fn register() {
+            register_custom_task("shift", |config: ShiftConfig<f32>, inputs| {
+                Shift::new(config, inputs.get("source"))
+            });
+        }
+
+        #[test]
+        fn deserialize() {
+            let config: HashMap<String, TaskConfig> = toml::from_str(CONFIG).unwrap();
+            let expected = CustomConfig {
+                kind: "shift".to_owned(),
+                inputs: BTreeMap::from([("source".to_owned(), "base".to_owned().into())]),
+                settings: BTreeMap::from([
+                    ("amount".to_owned(), ConfigValue::Float(0.25)),
+                    (
+                        "direction".to_owned(),
+                        ConfigValue::String("down".to_owned()),
+                    ),
+                ]),
+                cache: Some(Default::default()),
+            };
+            assert_eq!(config["shifted"], TaskConfig::Custom(expected));
+
+            let data = to_string(&config).unwrap();
+            let again: HashMap<String, TaskConfig> = toml::from_str(&data).unwrap();
+            assert_eq!(again, config);
+        }
+
+        #[test]
+        fn registered_task() {
+            register();
+            let mut tree = from_str(CONFIG).unwrap();
+            assert_eq!(tree.sample_2d("shifted", 1.0, 1.0), 0.25);
+            assert_eq!(tree.get("shifted_cached").unwrap().kind(), "custom");
+        }
+
+        #[test]
+        fn inline_input() {
+            register();
+            let mut tree = from_str(
+                r#"
+                [shifted.custom]
+                type = "shift"
+                amount = 1
+                direction = "up"
+                inputs.source = "= 0.5 * 3"
+                "#,
+            )
+            .unwrap();
+            assert_eq!(tree.sample_1d("shifted", 1.0), 2.5);
+        }
+
+        #[test]
+        fn not_shared() {
+            register();
+            let shift = r#"{ type = "shift", amount = 1, direction = "up", inputs.source = 0.5 }"#;
+            let data = format!("share = true\na.custom = {}\nb.custom = {}", shift, shift);
+            let tree = from_str(&data).unwrap();
+            assert!(!tree.get("a").unwrap().ptr_eq(tree.get("b").unwrap()));
+        }
+
+        #[test]
+        fn unregistered_task() {
+            let result = from_str(r#"shifted.custom = { type = "river_mask" }"#);
+            assert_eq!(
+                result.err(),
+                Some("Custom task 'shifted': Task type 'river_mask' isn't registered".to_owned())
+            );
+        }
+
+        #[test]
+        fn invalid_settings() {
+            register();
+            let result = from_str(r#"shifted.custom = { type = "shift", amount = 1 }"#);
+            assert_eq!(
+                result.err(),
+                Some("Custom task 'shifted': missing field `direction`".to_owned())
+            );
+        }
+
+        #[test]
+        fn graph() {
+            let graph = graph_from_str(CONFIG).unwrap();
+            let node = graph.nodes.iter().find(|x| x.name == "shifted").unwrap();
+            assert_eq!(node.kind, "custom");
+            assert_eq!(
+                node.params,
+                vec![
+                    ("type".to_owned(), "shift".to_owned()),
+                    ("amount".to_owned(), "0.25".to_owned()),
+                    ("direction".to_owned(), "\"down\"".to_owned()),
+                    ("cache".to_owned(), "last".to_owned()),
+                ]
+            );
+        }
+    }
+
+    mod f64 {
+        use std::collections::{BTreeMap, HashMap};
+
+        use super::{Shift, ShiftConfig};
+        use crate::ser::f64::{
+            register_custom_task,
+            toml::{from_str, graph_from_str, to_string},
+            ConfigValue, CustomConfig, TaskConfig,
+        };
+
+        const CONFIG: &str = r#"
+        [base]
+        constant = 0.5
+
+        [shifted.custom]
+        type = "shift"
+        amount = 0.25
+        direction = "down"
+        inputs.source = "base"
+        cached = true
+        "#;
+
+        fn register() {
+            register_custom_task("shift", |config: ShiftConfig<f64>, inputs| {
+                Shift::new(config, inputs.get("source"))
+            });
+        }
+
+        #[test]
+        fn deserialize() {
+            let config: HashMap<String, TaskConfig> = toml::from_str(CONFIG).unwrap();
+            let expected = CustomConfig {
+                kind: "shift".to_owned(),
+                inputs: BTreeMap::from([("source".to_owned(), "base".to_owned().into())]),
+                settings: BTreeMap::from([
+                    ("amount".to_owned(), ConfigValue::Float(0.25)),
+                    (
+                        "direction".to_owned(),
+                        ConfigValue::String("down".to_owned()),
+                    ),
+                ]),
+                cache: Some(Default::default()),
+            };
+            assert_eq!(config["shifted"], TaskConfig::Custom(expected));
+
+            let data = to_string(&config).unwrap();
+            let again: HashMap<String, TaskConfig> = toml::from_str(&data).unwrap();
+            assert_eq!(again, config);
+        }
+
+        #[test]
+        fn registered_task() {
+            register();
+            let mut tree = from_str(CONFIG).unwrap();
+            assert_eq!(tree.sample_2d("shifted", 1.0, 1.0), 0.25);
+            assert_eq!(tree.get("shifted_cached").unwrap().kind(), "custom");
+        }
+
+        #[test]
+        fn inline_input() {
+            register();
+            let mut tree = from_str(
+                r#"
+                [shifted.custom]
+                type = "shift"
+                amount = 1
+                direction = "up"
+                inputs.source = "= 0.5 * 3"
+                "#,
+            )
+            .unwrap();
+            assert_eq!(tree.sample_1d("shifted", 1.0), 2.5);
+        }
+
+        #[test]
+        fn not_shared() {
+            register();
+            let shift = r#"{ type = "shift", amount = 1, direction = "up", inputs.source = 0.5 }"#;
+            let data = format!("share = true\na.custom = {}\nb.custom = {}", shift, shift);
+            let tree = from_str(&data).unwrap();
+            assert!(!tree.get("a").unwrap().ptr_eq(tree.get("b").unwrap()));
+        }
+
+        #[test]
+        fn unregistered_task() {
+            let result = from_str(r#"shifted.custom = { type = "river_mask" }"#);
+            assert_eq!(
+                result.err(),
+                Some("Custom task 'shifted': Task type 'river_mask' isn't registered".to_owned())
+            );
+        }
+
+        #[test]
+        fn invalid_settings() {
+            register();
+            let result = from_str(r#"shifted.custom = { type = "shift", amount = 1 }"#);
+            assert_eq!(
+                result.err(),
+                Some("Custom task 'shifted': missing field `direction`".to_owned())
+            );
+        }
+
+        #[test]
+        fn graph() {
+            let graph = graph_from_str(CONFIG).unwrap();
+            let node = graph.nodes.iter().find(|x| x.name == "shifted").unwrap();
+            assert_eq!(node.kind, "custom");
+            assert_eq!(
+                node.params,
+                vec![
+                    ("type".to_owned(), "shift".to_owned()),
+                    ("amount".to_owned(), "0.25".to_owned()),
+                    ("direction".to_owned(), "\"down\"".to_owned()),
+                    ("cache".to_owned(), "last".to_owned()),
+                ]
+            );
+        }
+    }
+}
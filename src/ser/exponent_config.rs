@@ -0,0 +1,166 @@
+macro_rules! exponent_config {
+    () => {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct ExponentConfig {
+            pub exponent: NameOrConst,
+            pub source: NameOrConst,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        impl Default for ExponentConfig {
+            fn default() -> Self {
+                Self {
+                    exponent: 1.0.into(),
+                    source: 0.0.into(),
+                    cache: None,
+                }
+            }
+        }
+
+        impl TaskDependencies for ExponentConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                push_named_to_vec!(r, self.exponent);
+                push_named_to_vec!(r, self.source);
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.exponent, rename);
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for ExponentConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.exponent, format!("{}.exponent", name));
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
+        }
+
+        impl IntoTaskSource for ExponentConfig {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
+                let mut builder = ExponentBuilder::new();
+
+                add_task_to_builder!(self.exponent, builder, exponent, named_exponent, tree);
+                add_task_to_builder!(self.source, builder, source, named_source, tree);
+
+                Ok(builder.link(tree).build().into())
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{ExponentBuilder, TaskSource, TaskTree};
+    exponent_config!();
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{ExponentBuilder, TaskSource, TaskTree};
+    exponent_config!();
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use std::collections::HashMap;
+
+        use crate::ser::f32::{CacheConfig, ExponentConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [exponent_a]
+                exponent.source = "other"
+                exponent.exponent = 2.5
+                exponent.cached = true
+
+                [exponent_b]
+                exponent = { exponent = "other", source = 1 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["exponent_a"],
+                TaskConfig::Exponent(ExponentConfig {
+                    exponent: 2.5.into(),
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["exponent_b"],
+                TaskConfig::Exponent(ExponentConfig {
+                    exponent: "other".to_owned().into(),
+                    source: 1.0.into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+
+    mod f64 {
+        use std::collections::HashMap;
+
+        use crate::ser::f64::{CacheConfig, ExponentConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [exponent_a]
+                exponent.source = "other"
+                exponent.exponent = 2.5
+                exponent.cached = true
+
+                [exponent_b]
+                exponent = { exponent = "other", source = 1 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["exponent_a"],
+                TaskConfig::Exponent(ExponentConfig {
+                    exponent: 2.5.into(),
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["exponent_b"],
+                TaskConfig::Exponent(ExponentConfig {
+                    exponent: "other".to_owned().into(),
+                    source: 1.0.into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,747 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// Prefix marking a string as an expression rather than a task name.
+const EXPRESSION_PREFIX: char = '=';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Parsed arithmetic expression over task names and constants.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Expr {
+    Number(f64),
+    Name(String),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+/// Returns the expression in `value` if it starts with `=`, `None` if it is a task name.
+pub(crate) fn expression_source(value: &str) -> Option<&str> {
+    value.trim_start().strip_prefix(EXPRESSION_PREFIX)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Op(char),
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, String> {
+    let mut tokens = vec![];
+    let mut chars: Peekable<CharIndices> = source.char_indices().peekable();
+
+    while let Some(&(i, c)) = chars.peek() {
+        match c {
+            _ if c.is_whitespace() => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '/' | '(' | ')' | ',' => {
+                tokens.push((i, Token::Op(c)));
+                chars.next();
+            }
+            '\'' => {
+                chars.next();
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '\'')) => break,
+                        Some((_, x)) => name.push(x),
+                        None => return Err(format!("Unterminated name at {}", i)),
+                    }
+                }
+                tokens.push((i, Token::Name(name)));
+            }
+            _ if c.is_ascii_digit() || c == '.' => {
+                let mut end = i;
+                let mut prev = c;
+                while let Some(&(j, x)) = chars.peek() {
+                    let exponent_sign = (x == '-' || x == '+') && (prev == 'e' || prev == 'E');
+                    if !(x.is_ascii_digit() || x == '.' || x == 'e' || x == 'E' || exponent_sign) {
+                        break;
+                    }
+                    end = j + x.len_utf8();
+                    prev = x;
+                    chars.next();
+                }
+                let number = source[i..end]
+                    .parse()
+                    .map_err(|_| format!("Invalid number '{}' at {}", &source[i..end], i))?;
+                tokens.push((i, Token::Number(number)));
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let mut end = i;
+                while let Some(&(j, x)) = chars.peek() {
+                    if !(x.is_alphanumeric() || x == '_' || x == '.') {
+                        break;
+                    }
+                    end = j + x.len_utf8();
+                    chars.next();
+                }
+                tokens.push((i, Token::Name(source[i..end].to_owned())));
+            }
+            _ => return Err(format!("Unexpected '{}' at {}", c, i)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    len: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, x)| x)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).map(|(_, x)| x.clone());
+        self.position += 1;
+        token
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map(|(i, _)| *i)
+            .unwrap_or(self.len)
+    }
+
+    fn expect(&mut self, op: char) -> Result<(), String> {
+        let offset = self.offset();
+        match self.next() {
+            Some(Token::Op(x)) if x == op => Ok(()),
+            _ => Err(format!("Expected '{}' at {}", op, offset)),
+        }
+    }
+
+    /// expression = term (("+" | "-") term)*
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek() {
+            let op = if *op == '+' {
+                BinaryOp::Add
+            } else {
+                BinaryOp::Sub
+            };
+            self.next();
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    /// term = unary (("*" | "/") unary)*
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        while let Some(Token::Op(op @ ('*' | '/'))) = self.peek() {
+            let op = if *op == '*' {
+                BinaryOp::Mul
+            } else {
+                BinaryOp::Div
+            };
+            self.next();
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    /// unary = "-" unary | atom
+    fn unary(&mut self) -> Result<Expr, String> {
+        if let Some(Token::Op('-')) = self.peek() {
+            self.next();
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.atom()
+    }
+
+    /// atom = number | name | name "(" arguments ")" | "(" expression ")"
+    fn atom(&mut self) -> Result<Expr, String> {
+        let offset = self.offset();
+        match self.next() {
+            Some(Token::Number(x)) => Ok(Expr::Number(x)),
+            Some(Token::Name(x)) => {
+                if let Some(Token::Op('(')) = self.peek() {
+                    self.next();
+                    let mut args = vec![];
+                    if let Some(Token::Op(')')) = self.peek() {
+                        self.next();
+                    } else {
+                        loop {
+                            args.push(self.expression()?);
+                            match self.next() {
+                                Some(Token::Op(',')) => continue,
+                                Some(Token::Op(')')) => break,
+                                _ => return Err(format!("Expected ',' or ')' in call to '{}'", x)),
+                            }
+                        }
+                    }
+                    Ok(Expr::Call(x, args))
+                } else {
+                    Ok(Expr::Name(x))
+                }
+            }
+            Some(Token::Op('(')) => {
+                let expr = self.expression()?;
+                self.expect(')')?;
+                Ok(expr)
+            }
+            Some(Token::Op(x)) => Err(format!("Unexpected '{}' at {}", x, offset)),
+            None => Err("Unexpected end of expression".to_owned()),
+        }
+    }
+}
+
+/// Parses an arithmetic expression such as `mountains * 0.5 + clamp(hills, 0, 1)`.
+///
+/// The functions are `min`, `max` and `avg` of any number of arguments, `abs(x)`,
+/// `clamp(x, min, max)`, `pow(x, exponent)` and `exponent(x, exponent)`.
+///
+/// Names may contain dots to reference namespaced tasks, other names can be quoted with `'`.
+pub(crate) fn parse(source: &str) -> Result<Expr, String> {
+    let parse = || {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+            len: source.len(),
+        };
+
+        let expr = parser.expression()?;
+        if parser.peek().is_some() {
+            return Err(format!("Unexpected input at {}", parser.offset()));
+        }
+
+        validate(&expr)?;
+        Ok(expr)
+    };
+
+    parse().map_err(|x| format!("{} in expression '{}'", x, source))
+}
+
+fn validate(expr: &Expr) -> Result<(), String> {
+    match expr {
+        Expr::Number(_) | Expr::Name(_) => Ok(()),
+        Expr::Neg(x) => validate(x),
+        Expr::Binary(_, a, b) => validate(a).and(validate(b)),
+        Expr::Call(name, args) => {
+            match (name.as_str(), args.len()) {
+                ("min" | "max" | "avg", 0) => {
+                    return Err(format!("'{}' expects at least one argument", name))
+                }
+                ("min" | "max" | "avg", _)
+                | ("abs", 1)
+                | ("clamp", 3)
+                | ("exponent" | "pow", 2) => {}
+                ("abs", _) => return Err("'abs' expects 1 argument".to_owned()),
+                ("clamp", _) => return Err("'clamp' expects 3 arguments".to_owned()),
+                ("exponent" | "pow", _) => {
+                    return Err(format!("'{}' expects 2 arguments", name))
+                }
+                _ => return Err(format!("Unknown function '{}'", name)),
+            }
+            args.iter().try_for_each(validate)
+        }
+    }
+}
+
+macro_rules! expression {
+    ($type: ty) => {
+        /// Compiles an expression into constants, task references and inline tasks.
+        pub(crate) fn compile(expr: &Expr) -> NameOrConst {
+            match expr {
+                Expr::Number(x) => NameOrConst::Value(*x as $type),
+                Expr::Name(x) => NameOrConst::Named(x.clone()),
+                Expr::Neg(x) => match compile(x) {
+                    NameOrConst::Value(x) => NameOrConst::Value(-x),
+                    source => TaskConfig::Invert(InvertConfig {
+                        source,
+                        ..Default::default()
+                    })
+                    .into(),
+                },
+                Expr::Binary(BinaryOp::Add, _, _) => {
+                    let mut operands = vec![];
+                    flatten(expr, BinaryOp::Add, &mut operands);
+                    fold(Operation::Add, 0.0, |a, b| a + b, operands)
+                }
+                Expr::Binary(BinaryOp::Mul, _, _) => {
+                    let mut operands = vec![];
+                    flatten(expr, BinaryOp::Mul, &mut operands);
+                    fold(Operation::Mul, 1.0, |a, b| a * b, operands)
+                }
+                Expr::Binary(BinaryOp::Sub, a, b) => match (compile(a), compile(b)) {
+                    (NameOrConst::Value(a), NameOrConst::Value(b)) => NameOrConst::Value(a - b),
+                    (a, b) => TaskConfig::ScaleOffset(ScaleOffsetConfig {
+                        source: b,
+                        scale: (-1.0).into(),
+                        offset: a,
+                        ..Default::default()
+                    })
+                    .into(),
+                },
+                Expr::Binary(BinaryOp::Div, a, b) => match (compile(a), compile(b)) {
+                    (NameOrConst::Value(a), NameOrConst::Value(b)) => NameOrConst::Value(a / b),
+                    (a, NameOrConst::Value(b)) => scale(a, 1.0 / b),
+                    (NameOrConst::Value(a), b) => aggregate(Operation::Div, a, vec![b]),
+                    (a, b) => scale(a, aggregate(Operation::Div, 1.0, vec![b])),
+                },
+                Expr::Call(name, args) => {
+                    let args = args.iter().map(compile).collect();
+                    match name.as_str() {
+                        "min" => fold(Operation::Min, <$type>::INFINITY, <$type>::min, args),
+                        "max" => fold(Operation::Max, <$type>::NEG_INFINITY, <$type>::max, args),
+                        "avg" => aggregate(Operation::Avg, 0.0, args),
+                        "abs" => {
+                            let mut args: Vec<NameOrConst> = args;
+                            match args.pop().unwrap_or_default() {
+                                NameOrConst::Value(x) => NameOrConst::Value(x.abs()),
+                                source => TaskConfig::Abs(AbsConfig {
+                                    source,
+                                    ..Default::default()
+                                })
+                                .into(),
+                            }
+                        }
+                        "clamp" => {
+                            let mut args: Vec<NameOrConst> = args;
+                            let max = args.pop().unwrap_or_default();
+                            let min = args.pop().unwrap_or_default();
+                            let source = args.pop().unwrap_or_default();
+                            match (&source, &min, &max) {
+                                (
+                                    NameOrConst::Value(x),
+                                    NameOrConst::Value(min),
+                                    NameOrConst::Value(max),
+                                ) => NameOrConst::Value(x.min(*max).max(*min)),
+                                _ => TaskConfig::Clamp(ClampConfig {
+                                    max,
+                                    min,
+                                    source,
+                                    ..Default::default()
+                                })
+                                .into(),
+                            }
+                        }
+                        "exponent" | "pow" => {
+                            let mut args: Vec<NameOrConst> = args;
+                            let exponent = args.pop().unwrap_or_default();
+                            let source = args.pop().unwrap_or_default();
+                            match (&source, &exponent, name.as_str()) {
+                                (NameOrConst::Value(x), NameOrConst::Value(e), "pow") => {
+                                    NameOrConst::Value(x.powf(*e))
+                                }
+                                (NameOrConst::Value(x), NameOrConst::Value(e), _) => {
+                                    NameOrConst::Value(((x + 1.0) * 0.5).abs().powf(*e) * 2.0 - 1.0)
+                                }
+                                (_, _, "pow") => TaskConfig::Pow(PowConfig {
+                                    exponent,
+                                    source,
+                                    ..Default::default()
+                                })
+                                .into(),
+                                _ => TaskConfig::Exponent(ExponentConfig {
+                                    exponent,
+                                    source,
+                                    ..Default::default()
+                                })
+                                .into(),
+                            }
+                        }
+                        _ => unreachable!("Unknown function '{}'", name),
+                    }
+                }
+            }
+        }
+
+        /// Collects the operands of a chain of the same associative operator.
+        fn flatten(expr: &Expr, op: BinaryOp, operands: &mut Vec<NameOrConst>) {
+            match expr {
+                Expr::Binary(x, a, b) if *x == op => {
+                    flatten(a, op, operands);
+                    flatten(b, op, operands);
+                }
+                _ => operands.push(compile(expr)),
+            }
+        }
+
+        /// Folds the constant operands into the initial value of an aggregate.
+        fn fold(
+            op: Operation,
+            initial: $type,
+            f: fn($type, $type) -> $type,
+            operands: Vec<NameOrConst>,
+        ) -> NameOrConst {
+            let mut initial = initial;
+            let mut sources = vec![];
+            for operand in operands {
+                match operand {
+                    NameOrConst::Value(x) => initial = f(initial, x),
+                    x => sources.push(x),
+                }
+            }
+
+            match (op, sources.len()) {
+                (_, 0) => NameOrConst::Value(initial),
+                (Operation::Mul, 1) => scale(sources.pop().unwrap_or_default(), initial),
+                _ => aggregate(op, initial, sources),
+            }
+        }
+
+        fn scale(source: NameOrConst, scale: impl Into<NameOrConst>) -> NameOrConst {
+            let scale = scale.into();
+            if scale == NameOrConst::Value(1.0) {
+                return source;
+            }
+            TaskConfig::Scale(ScaleConfig {
+                source,
+                scale,
+                ..Default::default()
+            })
+            .into()
+        }
+
+        fn aggregate(op: Operation, initial: $type, source: Vec<NameOrConst>) -> NameOrConst {
+            TaskConfig::Aggregate(AggregateConfig {
+                operator: op,
+                initial,
+                source,
+                ..Default::default()
+            })
+            .into()
+        }
+    };
+}
+
+pub mod f32 {
+    use super::{BinaryOp, Expr};
+    use crate::ser::f32::{
+        AbsConfig, AggregateConfig, ClampConfig, ExponentConfig, InvertConfig, NameOrConst,
+        PowConfig, ScaleConfig, ScaleOffsetConfig, TaskConfig,
+    };
+    use crate::task::f32::Operation;
+    expression!(f32);
+}
+
+pub mod f64 {
+    use super::{BinaryOp, Expr};
+    use crate::ser::f64::{
+        AbsConfig, AggregateConfig, ClampConfig, ExponentConfig, InvertConfig, NameOrConst,
+        PowConfig, ScaleConfig, ScaleOffsetConfig, TaskConfig,
+    };
+    use crate::task::f64::Operation;
+    expression!(f64);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{expression_source, parse, BinaryOp, Expr};
+
+    fn name(x: &str) -> Box<Expr> {
+        Box::new(Expr::Name(x.to_owned()))
+    }
+
+    #[test]
+    fn expression_detection() {
+        assert_eq!(expression_source("mountains"), None);
+        assert_eq!(expression_source("biomes.hills"), None);
+        assert_eq!(expression_source("task 1"), None);
+        assert_eq!(expression_source("river-mask"), None);
+        assert_eq!(expression_source("(a)"), None);
+        assert_eq!(expression_source("=a * 2"), Some("a * 2"));
+        assert_eq!(expression_source(" = clamp(a, 0, 1)"), Some(" clamp(a, 0, 1)"));
+        assert_eq!(expression_source("='river-mask'"), Some("'river-mask'"));
+    }
+
+    #[test]
+    fn parse_precedence() {
+        assert_eq!(
+            parse("mountains * 0.5 + hills"),
+            Ok(Expr::Binary(
+                BinaryOp::Add,
+                Box::new(Expr::Binary(
+                    BinaryOp::Mul,
+                    name("mountains"),
+                    Box::new(Expr::Number(0.5))
+                )),
+                name("hills")
+            ))
+        );
+
+        assert_eq!(
+            parse("a - (b - c)"),
+            Ok(Expr::Binary(
+                BinaryOp::Sub,
+                name("a"),
+                Box::new(Expr::Binary(BinaryOp::Sub, name("b"), name("c")))
+            ))
+        );
+
+        assert_eq!(
+            parse("-a / 2e-1"),
+            Ok(Expr::Binary(
+                BinaryOp::Div,
+                Box::new(Expr::Neg(name("a"))),
+                Box::new(Expr::Number(0.2))
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_names_and_calls() {
+        assert_eq!(
+            parse("clamp(biomes.erosion, 0, 1)"),
+            Ok(Expr::Call(
+                "clamp".to_owned(),
+                vec![
+                    Expr::Name("biomes.erosion".to_owned()),
+                    Expr::Number(0.0),
+                    Expr::Number(1.0)
+                ]
+            ))
+        );
+
+        assert_eq!(
+            parse("'river-mask'"),
+            Ok(Expr::Name("river-mask".to_owned()))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(parse("a +"), Err(x) if x.contains("end of expression")));
+        assert!(matches!(parse("a * * b"), Err(x) if x.contains("Unexpected '*' at 4")));
+        assert!(matches!(parse("(a + b"), Err(x) if x.contains("Expected ')'")));
+        assert!(matches!(parse("a b"), Err(x) if x.contains("Unexpected input at 2")));
+        assert!(matches!(parse("a % b"), Err(x) if x.contains("Unexpected '%'")));
+        assert!(matches!(parse("'a"), Err(x) if x.contains("Unterminated name at 0 in")));
+        assert!(matches!(parse("sin(a)"), Err(x) if x.contains("Unknown function 'sin'")));
+        assert!(matches!(parse("clamp(a, 1)"), Err(x) if x.contains("3 arguments")));
+        assert!(matches!(parse("min()"), Err(x) if x.contains("at least one")));
+        assert!(matches!(parse("abs(a, b)"), Err(x) if x.contains("1 argument")));
+        assert!(matches!(parse("pow(a)"), Err(x) if x.contains("'pow' expects 2 arguments")));
+    }
+
+    mod f32 {
+        use crate::ser::f32::{
+            toml::from_str, AbsConfig, AggregateConfig, ClampConfig, ExponentConfig, InvertConfig,
+            NameOrConst, PowConfig, ScaleConfig, ScaleOffsetConfig, TaskConfig,
+        };
+        use crate::task::f32::Operation;
+
+        #[test]
+        fn compile_folds_constants() {
+            assert_eq!(NameOrConst::parse("= (1 + 2) * 4 / 2"), Ok(6.0.into()));
+            assert_eq!(NameOrConst::parse("= -(0.5 - 1)"), Ok(0.5.into()));
+            assert_eq!(NameOrConst::parse("= clamp(2, 0, 1)"), Ok(1.0.into()));
+            assert_eq!(NameOrConst::parse("= a * 1"), Ok("a".to_owned().into()));
+            assert_eq!(NameOrConst::parse("= 'a-b'"), Ok("a-b".to_owned().into()));
+            assert_eq!(NameOrConst::parse("= a-b"), NameOrConst::parse("= a - b"));
+            assert_eq!(NameOrConst::parse("= abs(-2)"), Ok(2.0.into()));
+            assert_eq!(NameOrConst::parse("= pow(2, 3)"), Ok(8.0.into()));
+            assert_eq!(NameOrConst::parse("= exponent(0, 2)"), Ok((-0.5).into()));
+        }
+
+        #[test]
+        fn compile_modifiers() {
+            let a = || NameOrConst::from("a".to_owned());
+
+            assert_eq!(
+                NameOrConst::parse("= -a"),
+                Ok(TaskConfig::Invert(InvertConfig {
+                    source: a(),
+                    cache: None,
+                })
+                .into())
+            );
+
+            assert_eq!(
+                NameOrConst::parse("= abs(a)"),
+                Ok(TaskConfig::Abs(AbsConfig {
+                    source: a(),
+                    cache: None,
+                })
+                .into())
+            );
+
+            assert_eq!(
+                NameOrConst::parse("= clamp(a, -0.2, 1)"),
+                Ok(TaskConfig::Clamp(ClampConfig {
+                    max: 1.0.into(),
+                    min: (-0.2).into(),
+                    source: a(),
+                    cache: None,
+                })
+                .into())
+            );
+
+            assert_eq!(
+                NameOrConst::parse("= pow(a, 2)"),
+                Ok(TaskConfig::Pow(PowConfig {
+                    exponent: 2.0.into(),
+                    source: a(),
+                    cache: None,
+                })
+                .into())
+            );
+
+            assert_eq!(
+                NameOrConst::parse("= exponent(2, a)"),
+                Ok(TaskConfig::Exponent(ExponentConfig {
+                    exponent: a(),
+                    source: 2.0.into(),
+                    cache: None,
+                })
+                .into())
+            );
+        }
+
+        #[test]
+        fn compile_tasks() {
+            assert_eq!(
+                NameOrConst::parse("= 2 * a * 0.5 * b"),
+                Ok(TaskConfig::Aggregate(AggregateConfig {
+                    operator: Operation::Mul,
+                    initial: 1.0,
+                    source: vec!["a".to_owned().into(), "b".to_owned().into()],
+                    cache: None,
+                })
+                .into())
+            );
+
+            assert_eq!(
+                NameOrConst::parse("= a / 4"),
+                Ok(TaskConfig::Scale(ScaleConfig {
+                    source: "a".to_owned().into(),
+                    scale: 0.25.into(),
+                    cache: None,
+                })
+                .into())
+            );
+
+            assert_eq!(
+                NameOrConst::parse("= 1 - a"),
+                Ok(TaskConfig::ScaleOffset(ScaleOffsetConfig {
+                    source: "a".to_owned().into(),
+                    scale: (-1.0).into(),
+                    offset: 1.0.into(),
+                    cache: None,
+                })
+                .into())
+            );
+        }
+
+        #[test]
+        fn helper_from_str() {
+            let data = r#"
+            [mountains]
+            constant = 0.8
+
+            [hills]
+            constant = 0.25
+
+            [erosion]
+            constant = 1.5
+
+            [a]
+            scale = { source = "= mountains * 0.5 + hills", scale = 1.0 }
+
+            [b]
+            scale = { source = "= clamp(erosion, 0, 1) - hills / 0.5", scale = 2.0 }
+
+            [c]
+            aggregate = { operator = "add", source = ["= max(hills, mountains, 0.5)", "= 1 / erosion"] }
+
+            [d]
+            scale = { source = "= abs(-erosion) + pow(hills, 2)", scale = 1.0 }
+            "#;
+
+            match from_str(data) {
+                Ok(mut x) => {
+                    assert_eq!(x.sample_1d("a", 1.0), 0.65);
+                    assert_eq!(x.sample_1d("b", 1.0), 1.0);
+                    assert_eq!(x.sample_1d("c", 1.0), 0.8 + 1.0 / 1.5);
+                    assert_eq!(x.sample_1d("d", 1.0), 1.5625);
+                }
+                Err(x) => panic!("{}", x),
+            }
+        }
+
+        #[test]
+        fn helper_from_str_task_names() {
+            let data = r#"
+            [river-mask]
+            constant = 0.5
+
+            [a]
+            scale = { source = "river-mask", scale = 2.0 }
+
+            [b]
+            scale = { source = "= 'river-mask' * 3", scale = 1.0 }
+            "#;
+
+            match from_str(data) {
+                Ok(mut x) => {
+                    assert_eq!(x.sample_1d("a", 1.0), 1.0);
+                    assert_eq!(x.sample_1d("b", 1.0), 1.5);
+                }
+                Err(x) => panic!("{}", x),
+            }
+        }
+
+        #[test]
+        fn helper_from_str_error() {
+            let data = r#"
+            [a]
+            scale = { source = "= mountains * (0.5 + hills" }
+            "#;
+
+            assert!(matches!(from_str(data), Err(x) if x.contains("Expected ')'")));
+        }
+    }
+
+    mod f64 {
+        use crate::ser::f64::{toml::from_str, NameOrConst};
+
+        #[test]
+        fn compile_folds_constants() {
+            assert_eq!(NameOrConst::parse("= (1 + 2) * 4 / 2"), Ok(6.0.into()));
+            assert_eq!(NameOrConst::parse("= min(3, 1, 2)"), Ok(1.0.into()));
+            assert!(matches!(NameOrConst::parse("= avg(1, 3)"), Ok(NameOrConst::Inline(_))));
+        }
+
+        #[test]
+        fn helper_from_str() {
+            let data = r#"
+            [mountains]
+            constant = 0.75
+
+            [hills]
+            constant = 0.25
+
+            [a]
+            scale = { source = "= mountains * 0.5 + hills", scale = 1.0 }
+
+            [b]
+            scale = { source = "= avg(mountains, hills) - -max(hills, 0)", scale = 1.0 }
+            "#;
+
+            match from_str(data) {
+                Ok(mut x) => {
+                    assert_eq!(x.sample_1d("a", 1.0), 0.625);
+                    assert_eq!(x.sample_1d("b", 1.0), 0.75);
+                }
+                Err(x) => panic!("{}", x),
+            }
+        }
+    }
+}
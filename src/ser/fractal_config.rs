@@ -39,7 +39,13 @@ macro_rules! fractal_config {
             pub offset: Option<$type>,
             #[serde(alias = "src")]
             pub source: FractalSource,
-            pub cached: bool,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
         }
 
         impl Default for FractalConfig {
@@ -55,7 +61,7 @@ macro_rules! fractal_config {
                     octaves: 6,
                     offset: None,
                     source: FractalSource::default(),
-                    cached: false,
+                    cache: None,
                 }
             }
         }
@@ -64,10 +70,12 @@ macro_rules! fractal_config {
             fn dependencies(&self) -> Vec<String> {
                 vec![]
             }
+
+            fn rename_dependencies(&mut self, _: &dyn Fn(&str) -> Option<String>) {}
         }
 
         impl IntoTaskSource for FractalConfig {
-            fn config_into(&self, _: &TaskTree) -> TaskSource {
+            fn config_into(&self, _: &TaskTree) -> Result<TaskSource, String> {
                 let mut builder = FractalBuilder::new();
 
                 let blender: Blender = match self.interp {
@@ -88,7 +96,7 @@ macro_rules! fractal_config {
                         FractalSource::Perlin => Box::new(Perlin::new(blender)),
                     });
 
-                builder.build().into()
+                Ok(builder.build().into())
             }
         }
     };
@@ -97,7 +105,9 @@ macro_rules! fractal_config {
 pub mod f32 {
     pub use super::{FractalBlender, FractalSource};
     use crate::math::f32 as math;
-    use crate::ser::f32::{IntoTaskSource, TaskDependencies};
+    use crate::ser::f32::{
+        deserialize_cache, serialize_cache, CacheConfig, IntoTaskSource, TaskDependencies,
+    };
     use crate::source::f32::{Blender, Perlin};
     use crate::task::f32::{FractalBuilder, FractalType, TaskSource, TaskTree};
     fractal_config!(f32);
@@ -106,7 +116,9 @@ pub mod f32 {
 pub mod f64 {
     pub use super::{FractalBlender, FractalSource};
     use crate::math::f64 as math;
-    use crate::ser::f64::{IntoTaskSource, TaskDependencies};
+    use crate::ser::f64::{
+        deserialize_cache, serialize_cache, CacheConfig, IntoTaskSource, TaskDependencies,
+    };
     use crate::source::f64::{Blender, Perlin};
     use crate::task::f64::{FractalBuilder, FractalType, TaskSource, TaskTree};
     fractal_config!(f64);
@@ -117,7 +129,7 @@ mod test {
     mod f32 {
         use std::collections::HashMap;
 
-        use crate::ser::f32::{FractalConfig, TaskConfig};
+        use crate::ser::f32::{CacheConfig, FractalConfig, TaskConfig};
         use crate::task::f32::FractalType;
 
         #[test]
@@ -139,7 +151,7 @@ mod test {
                     octaves: 1,
                     fractal: FractalType::Brownian,
                     frequency: 0.5,
-                    cached: true,
+                    cache: Some(CacheConfig::default()),
                     ..Default::default()
                 })
             );
@@ -158,7 +170,7 @@ mod test {
     mod f64 {
         use std::collections::HashMap;
 
-        use crate::ser::f64::{FractalConfig, TaskConfig};
+        use crate::ser::f64::{CacheConfig, FractalConfig, TaskConfig};
         use crate::task::f64::FractalType;
 
         #[test]
@@ -180,7 +192,7 @@ mod test {
                     octaves: 1,
                     fractal: FractalType::Brownian,
                     frequency: 0.5,
-                    cached: true,
+                    cache: Some(CacheConfig::default()),
                     ..Default::default()
                 })
             );
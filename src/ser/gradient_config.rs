@@ -2,28 +2,42 @@ macro_rules! gradient_config {
     ($type: ty) => {
         use serde::{Deserialize, Serialize};
 
+        /// Gradient from `(x1, y1, z1)` shaped by `kind`.
+        ///
+        /// Radial and spherical gradients are centred on the first point, cylindrical ones
+        /// follow the axis through both points. Only linear gradients ignore `radius`.
         #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
         #[serde(default)]
         pub struct GradientConfig {
+            pub kind: GradientKind,
+            pub radius: $type,
             pub x1: $type,
             pub y1: $type,
             pub z1: $type,
             pub x2: $type,
             pub y2: $type,
             pub z2: $type,
-            pub cached: bool,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
         }
 
         impl Default for GradientConfig {
             fn default() -> Self {
                 Self {
+                    kind: GradientKind::default(),
+                    radius: 1.0,
                     x1: 0.0,
                     y1: 0.0,
                     z1: 0.0,
                     x2: 1.0,
                     y2: 1.0,
                     z2: 0.0,
-                    cached: false,
+                    cache: None,
                 }
             }
         }
@@ -32,38 +46,48 @@ macro_rules! gradient_config {
             fn dependencies(&self) -> Vec<String> {
                 vec![]
             }
+
+            fn rename_dependencies(&mut self, _: &dyn Fn(&str) -> Option<String>) {}
         }
 
         impl IntoTaskSource for GradientConfig {
-            fn config_into(&self, _: &TaskTree) -> TaskSource {
+            fn config_into(&self, _: &TaskTree) -> Result<TaskSource, String> {
                 let mut builder = GradientBuilder::new();
 
                 builder
+                    .kind(self.kind)
+                    .radius(self.radius)
                     .s1([self.x1, self.y1, self.z1])
                     .s2([self.x2, self.y2, self.z2]);
 
-                builder.build().into()
+                builder.check()?;
+                Ok(builder.build().into())
             }
         }
     };
 }
 
 pub mod f32 {
-    use crate::ser::f32::{IntoTaskSource, TaskDependencies};
-    use crate::task::f32::{GradientBuilder, TaskSource, TaskTree};
+    use crate::ser::f32::{
+        deserialize_cache, serialize_cache, CacheConfig, IntoTaskSource, TaskDependencies,
+    };
+    use crate::task::f32::{GradientBuilder, GradientKind, TaskSource, TaskTree};
     gradient_config!(f32);
 }
 
 pub mod f64 {
-    use crate::ser::f64::{IntoTaskSource, TaskDependencies};
-    use crate::task::f64::{GradientBuilder, TaskSource, TaskTree};
+    use crate::ser::f64::{
+        deserialize_cache, serialize_cache, CacheConfig, IntoTaskSource, TaskDependencies,
+    };
+    use crate::task::f64::{GradientBuilder, GradientKind, TaskSource, TaskTree};
     gradient_config!(f64);
 }
 
 #[cfg(test)]
 mod test {
     mod f32 {
-        use crate::ser::f32::{GradientConfig, TaskConfig};
+        use crate::ser::f32::{toml::from_str, CacheConfig, GradientConfig, TaskConfig};
+        use crate::task::f32::GradientKind;
         use std::collections::HashMap;
 
         #[test]
@@ -80,11 +104,14 @@ mod test {
 
                 [gradient_b]
                 gradient = { x1 = 2, y1 = 2, x2 = 4, y2 = 4 }
+
+                [gradient_c]
+                gradient = { kind = "spherical", x1 = 1, radius = 8 }
             })
             .unwrap();
             let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
 
-            assert_eq!(config.len(), 2);
+            assert_eq!(config.len(), 3);
             assert_eq!(
                 config["gradient_a"],
                 TaskConfig::Gradient(GradientConfig {
@@ -94,7 +121,7 @@ mod test {
                     y2: 5.0,
                     z1: 3.0,
                     z2: 6.0,
-                    cached: true,
+                    cache: Some(CacheConfig::default()),
                     ..Default::default()
                 })
             );
@@ -109,11 +136,41 @@ mod test {
                     ..Default::default()
                 })
             );
+
+            assert_eq!(
+                config["gradient_c"],
+                TaskConfig::Gradient(GradientConfig {
+                    kind: GradientKind::Spherical,
+                    radius: 8.0,
+                    x1: 1.0,
+                    ..Default::default()
+                })
+            );
+        }
+
+        #[test]
+        fn helper_from_str_invalid() {
+            let result = from_str("[a]\ngradient = { kind = \"radial\", radius = 0 }");
+            assert!(matches!(result, Err(x) if x.contains("radius must be greater than 0")));
+
+            let result = from_str("[a]\ngradient = { kind = \"spherical\", radius = -1 }");
+            assert!(matches!(result, Err(x) if x.contains("radius must be greater than 0")));
+
+            let result = from_str("[a]\ngradient = { kind = \"cylindrical\", x2 = 0, y2 = 0 }");
+            assert!(matches!(result, Err(x) if x.contains("points must be different")));
+
+            let result = from_str("[a]\ngradient = { x1 = 1, y1 = 1 }");
+            assert!(matches!(result, Err(x) if x.contains("points must be different")));
+
+            // Only linear gradients ignore the radius
+            let result = from_str("[a]\ngradient = { radius = 0 }");
+            assert!(result.is_ok());
         }
     }
 
     mod f64 {
-        use crate::ser::f64::{GradientConfig, TaskConfig};
+        use crate::ser::f64::{toml::from_str, CacheConfig, GradientConfig, TaskConfig};
+        use crate::task::f64::GradientKind;
         use std::collections::HashMap;
 
         #[test]
@@ -130,11 +187,14 @@ mod test {
 
                 [gradient_b]
                 gradient = { x1 = 2, y1 = 2, x2 = 4, y2 = 4 }
+
+                [gradient_c]
+                gradient = { kind = "spherical", x1 = 1, radius = 8 }
             })
             .unwrap();
             let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
 
-            assert_eq!(config.len(), 2);
+            assert_eq!(config.len(), 3);
             assert_eq!(
                 config["gradient_a"],
                 TaskConfig::Gradient(GradientConfig {
@@ -144,7 +204,7 @@ mod test {
                     y2: 5.0,
                     z1: 3.0,
                     z2: 6.0,
-                    cached: true,
+                    cache: Some(CacheConfig::default()),
                     ..Default::default()
                 })
             );
@@ -159,6 +219,35 @@ mod test {
                     ..Default::default()
                 })
             );
+
+            assert_eq!(
+                config["gradient_c"],
+                TaskConfig::Gradient(GradientConfig {
+                    kind: GradientKind::Spherical,
+                    radius: 8.0,
+                    x1: 1.0,
+                    ..Default::default()
+                })
+            );
+        }
+
+        #[test]
+        fn helper_from_str_invalid() {
+            let result = from_str("[a]\ngradient = { kind = \"radial\", radius = 0 }");
+            assert!(matches!(result, Err(x) if x.contains("radius must be greater than 0")));
+
+            let result = from_str("[a]\ngradient = { kind = \"spherical\", radius = -1 }");
+            assert!(matches!(result, Err(x) if x.contains("radius must be greater than 0")));
+
+            let result = from_str("[a]\ngradient = { kind = \"cylindrical\", x2 = 0, y2 = 0 }");
+            assert!(matches!(result, Err(x) if x.contains("points must be different")));
+
+            let result = from_str("[a]\ngradient = { x1 = 1, y1 = 1 }");
+            assert!(matches!(result, Err(x) if x.contains("points must be different")));
+
+            // Only linear gradients ignore the radius
+            let result = from_str("[a]\ngradient = { radius = 0 }");
+            assert!(result.is_ok());
         }
     }
 }
@@ -0,0 +1,344 @@
+macro_rules! graph_config {
+    ($type: ty) => {
+        use crate::graph::{Edge, Graph, Node};
+        use std::collections::HashMap;
+
+        /// Pushes `$field` as a param if it is a constant, task references become edges instead
+        macro_rules! push_value {
+            ($params: expr, $name: literal, $field: expr) => {
+                if let NameOrConst::Value(v) = &$field {
+                    $params.push(($name.to_owned(), v.to_string()));
+                }
+            };
+        }
+
+        /// Pushes the kind and shape of a gradient config as params
+        macro_rules! push_gradient {
+            ($params: expr, $x: expr) => {
+                let from = format!("({}, {}, {})", $x.x1, $x.y1, $x.z1);
+                let to = format!("({}, {}, {})", $x.x2, $x.y2, $x.z2);
+                let kind = gradient_kind_name($x.kind).to_owned();
+                $params.push(("kind".to_owned(), kind));
+                match $x.kind {
+                    GradientKind::Linear => {
+                        $params.push(("from".to_owned(), from));
+                        $params.push(("to".to_owned(), to));
+                    }
+                    GradientKind::Radial | GradientKind::Spherical => {
+                        $params.push(("centre".to_owned(), from));
+                        $params.push(("radius".to_owned(), $x.radius.to_string()));
+                    }
+                    GradientKind::Cylindrical => {
+                        $params.push(("from".to_owned(), from));
+                        $params.push(("to".to_owned(), to));
+                        $params.push(("radius".to_owned(), $x.radius.to_string()));
+                    }
+                }
+            };
+        }
+
+        impl TaskConfig {
+            /// Name of the task type as written in configs.
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    Self::Abs(_) => "abs",
+                    Self::Aggregate(_) => "aggregate",
+                    Self::Bias(_) => "bias",
+                    Self::Clamp(_) => "clamp",
+                    Self::Constant(_) => "constant",
+                    Self::Curve(_) => "curve",
+                    Self::Custom(_) => "custom",
+                    Self::Exponent(_) => "exponent",
+                    Self::Fractal(_) => "fractal",
+                    Self::Gradient(_) => "gradient",
+                    Self::Invert(_) => "invert",
+                    Self::MultiGradient(_) => "multi_gradient",
+                    Self::Parameter(_) => "parameter",
+                    Self::Pow(_) => "pow",
+                    Self::Scale(_) => "scale",
+                    Self::ScaleOffset(_) => "scale_offset",
+                    Self::Selector(_) => "selector",
+                    Self::Template(_) => "template",
+                    Self::Terrace(_) => "terrace",
+                    Self::TransformDomain(_) => "transform_domain",
+                    Self::Turbulence(_) => "turbulence",
+                }
+            }
+
+            /// The settings of the task that aren't task references, formatted for display.
+            pub fn params(&self) -> Vec<(String, String)> {
+                let mut r: Vec<(String, String)> = vec![];
+                let cache = match self {
+                    Self::Abs(x) => {
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::Aggregate(x) => {
+                        r.push(("operator".to_owned(), operation_name(x.operator).to_owned()));
+                        r.push(("initial".to_owned(), x.initial.to_string()));
+                        for (i, source) in x.source.iter().enumerate() {
+                            if let NameOrConst::Value(v) = source {
+                                r.push((format!("source.{}", i), v.to_string()));
+                            }
+                        }
+                        x.cache
+                    }
+                    Self::Bias(x) => {
+                        push_value!(r, "bias", x.bias);
+                        push_value!(r, "source", x.source);
+                        r.push(("min".to_owned(), x.min.to_string()));
+                        r.push(("max".to_owned(), x.max.to_string()));
+                        x.cache
+                    }
+                    Self::Clamp(x) => {
+                        push_value!(r, "max", x.max);
+                        push_value!(r, "min", x.min);
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::Constant(v) | Self::Parameter(v) => {
+                        r.push(("value".to_owned(), v.to_string()));
+                        None
+                    }
+                    Self::Curve(x) => {
+                        let interpolation = interpolation_name(x.interpolation);
+                        r.push(("interpolation".to_owned(), interpolation.to_owned()));
+                        r.push(("points".to_owned(), format_points(&x.points)));
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::Custom(x) => {
+                        r.push(("type".to_owned(), x.kind.clone()));
+                        for (key, value) in x.inputs.iter() {
+                            if let NameOrConst::Value(v) = value {
+                                r.push((key.clone(), v.to_string()));
+                            }
+                        }
+                        for (key, value) in x.settings.iter() {
+                            r.push((key.clone(), value.to_string()));
+                        }
+                        x.cache
+                    }
+                    Self::Exponent(x) => {
+                        push_value!(r, "exponent", x.exponent);
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::Fractal(x) => {
+                        r.push(("fractal".to_owned(), fractal_name(x.fractal).to_owned()));
+                        r.push(("octaves".to_owned(), x.octaves.to_string()));
+                        r.push(("frequency".to_owned(), x.frequency.to_string()));
+                        x.cache
+                    }
+                    Self::Gradient(x) => {
+                        push_gradient!(r, x);
+                        x.cache
+                    }
+                    Self::Invert(x) => {
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::MultiGradient(x) => {
+                        push_gradient!(r, x);
+                        r.push(("stops".to_owned(), format_stops(&x.stops)));
+                        x.cache
+                    }
+                    Self::Pow(x) => {
+                        push_value!(r, "exponent", x.exponent);
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::Scale(x) => {
+                        push_value!(r, "scale", x.scale);
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::ScaleOffset(x) => {
+                        push_value!(r, "offset", x.offset);
+                        push_value!(r, "scale", x.scale);
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::Selector(x) => {
+                        push_value!(r, "condition", x.condition);
+                        push_value!(r, "lower", x.lower);
+                        push_value!(r, "upper", x.upper);
+                        push_value!(r, "falloff", x.falloff);
+                        push_value!(r, "threshold", x.threshold);
+                        x.cache
+                    }
+                    Self::Template(x) => {
+                        r.push(("template".to_owned(), x.name.clone()));
+                        None
+                    }
+                    Self::Terrace(x) => {
+                        r.push(("levels".to_owned(), format_levels(&x.levels)));
+                        r.push(("smooth".to_owned(), x.smooth.to_string()));
+                        r.push(("invert".to_owned(), x.invert.to_string()));
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::TransformDomain(x) => {
+                        let operation = domain_operation_name(x.operation);
+                        r.push(("operation".to_owned(), operation.to_owned()));
+                        if x.operation == DomainOperation::Affine {
+                            r.push(("matrix".to_owned(), format_matrix(&x.matrix)));
+                        }
+                        push_value!(r, "dx", x.dx);
+                        push_value!(r, "dy", x.dy);
+                        push_value!(r, "dz", x.dz);
+                        for (i, op) in x.operations.iter().enumerate() {
+                            let prefix = format!("operations.{}", i);
+                            let operation = domain_operation_name(op.operation);
+                            r.push((format!("{}.operation", prefix), operation.to_owned()));
+                            if op.operation == DomainOperation::Affine {
+                                r.push((format!("{}.matrix", prefix), format_matrix(&op.matrix)));
+                            }
+                            for (axis, value) in [("dx", &op.dx), ("dy", &op.dy), ("dz", &op.dz)] {
+                                if let NameOrConst::Value(v) = value {
+                                    r.push((format!("{}.{}", prefix, axis), v.to_string()));
+                                }
+                            }
+                        }
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                    Self::Turbulence(x) => {
+                        r.push(("iterations".to_owned(), x.iterations.to_string()));
+                        push_value!(r, "dx", x.dx);
+                        push_value!(r, "dy", x.dy);
+                        push_value!(r, "dz", x.dz);
+                        push_value!(r, "power", x.power);
+                        push_value!(r, "source", x.source);
+                        x.cache
+                    }
+                };
+                if let Some(cache) = cache {
+                    r.push(("cache".to_owned(), cache.strategy().to_string()));
+                }
+                r
+            }
+        }
+
+        /// Describes the tasks of a config and the tasks they reference, without building them.
+        ///
+        /// Templates are expanded and inline tasks hoisted the same way as when the tree is
+        /// built, so node names match the names of the built tasks.
+        pub fn config_graph(config: ConfigFile) -> Result<Graph, String> {
+            let mut tasks: HashMap<String, TaskConfig> = config.into_tasks()?;
+            hoist_inline_tasks(&mut tasks)?;
+
+            let mut graph = Graph::default();
+            for (name, task) in tasks.iter() {
+                graph.nodes.push(Node {
+                    name: name.clone(),
+                    kind: task.kind().to_owned(),
+                    params: task.params(),
+                });
+                for dependency in task.dependencies() {
+                    graph.edges.push(Edge {
+                        from: dependency,
+                        to: name.clone(),
+                        label: None,
+                    });
+                }
+            }
+            graph.sort();
+            Ok(graph)
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        hoist_inline_tasks, ConfigFile, NameOrConst, TaskConfig, TaskDependencies,
+    };
+    use crate::task::{
+        domain_operation_name, format_levels, format_matrix, format_points, format_stops,
+        fractal_name, gradient_kind_name, interpolation_name, operation_name, DomainOperation,
+        GradientKind,
+    };
+    graph_config!(f32);
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        hoist_inline_tasks, ConfigFile, NameOrConst, TaskConfig, TaskDependencies,
+    };
+    use crate::task::{
+        domain_operation_name, format_levels, format_matrix, format_points, format_stops,
+        fractal_name, gradient_kind_name, interpolation_name, operation_name, DomainOperation,
+        GradientKind,
+    };
+    graph_config!(f64);
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod test {
+    const CONFIG: &str = r#"
+    [params]
+    height = 0.5
+
+    [base]
+    fractal = { octaves = 2, frequency = 0.5, cache = { mode = "lru", size = 64 } }
+
+    [height_map]
+    scale = { source = "base", scale = "= height * 2" }
+    "#;
+
+    mod f32 {
+        use super::CONFIG;
+        use crate::ser::f32::toml::graph_from_str;
+
+        #[test]
+        fn config_graph() {
+            let graph = graph_from_str(CONFIG).unwrap();
+            assert_eq!(
+                graph.to_dot(),
+                concat!(
+                    "digraph {\n",
+                    "    node [shape=box];\n",
+                    "    \"base\" [label=\"base\\nfractal\\nfractal = brownian\\noctaves = 2\\n",
+                    "frequency = 0.5\\ncache = lru, size = 64\"];\n",
+                    "    \"height\" [label=\"height\\nparameter\\nvalue = 0.5\"];\n",
+                    "    \"height_map\" [label=\"height_map\\nscale\"];\n",
+                    "    \"height_map.scale\" [label=\"height_map.scale\\nscale\\nscale = 2\"];\n",
+                    "    \"base\" -> \"height_map\";\n",
+                    "    \"height\" -> \"height_map.scale\";\n",
+                    "    \"height_map.scale\" -> \"height_map\";\n",
+                    "}\n"
+                )
+            );
+        }
+
+        #[test]
+        fn config_graph_errors() {
+            let result = graph_from_str("[a]\ntemplate = { name = \"b\" }");
+            assert!(result.is_err());
+        }
+    }
+
+    mod f64 {
+        use super::CONFIG;
+        use crate::ser::f64::toml::graph_from_str;
+
+        #[test]
+        fn config_graph() {
+            let graph = graph_from_str(CONFIG).unwrap();
+            assert_eq!(
+                graph.to_mermaid(),
+                concat!(
+                    "flowchart LR\n",
+                    "    n0[\"base<br/>fractal<br/>fractal = brownian<br/>octaves = 2<br/>",
+                    "frequency = 0.5<br/>cache = lru, size = 64\"]\n",
+                    "    n1[\"height<br/>parameter<br/>value = 0.5\"]\n",
+                    "    n2[\"height_map<br/>scale\"]\n",
+                    "    n3[\"height_map.scale<br/>scale<br/>scale = 2\"]\n",
+                    "    n0 --> n2\n",
+                    "    n1 --> n3\n",
+                    "    n3 --> n2\n",
+                )
+            );
+        }
+    }
+}
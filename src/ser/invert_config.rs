@@ -0,0 +1,154 @@
+macro_rules! invert_config {
+    () => {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct InvertConfig {
+            pub source: NameOrConst,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        impl Default for InvertConfig {
+            fn default() -> Self {
+                Self {
+                    source: 0.0.into(),
+                    cache: None,
+                }
+            }
+        }
+
+        impl TaskDependencies for InvertConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                push_named_to_vec!(r, self.source);
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for InvertConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
+        }
+
+        impl IntoTaskSource for InvertConfig {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
+                let mut builder = InvertBuilder::new();
+
+                add_task_to_builder!(self.source, builder, source, named_source, tree);
+
+                Ok(builder.link(tree).build().into())
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{InvertBuilder, TaskSource, TaskTree};
+    invert_config!();
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{InvertBuilder, TaskSource, TaskTree};
+    invert_config!();
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use std::collections::HashMap;
+
+        use crate::ser::f32::{CacheConfig, InvertConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [invert_a]
+                invert.source = "other"
+                invert.cached = true
+
+                [invert_b]
+                invert = { source = 0.5 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["invert_a"],
+                TaskConfig::Invert(InvertConfig {
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["invert_b"],
+                TaskConfig::Invert(InvertConfig {
+                    source: 0.5.into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+
+    mod f64 {
+        use std::collections::HashMap;
+
+        use crate::ser::f64::{CacheConfig, InvertConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [invert_a]
+                invert.source = "other"
+                invert.cached = true
+
+                [invert_b]
+                invert = { source = 0.5 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["invert_a"],
+                TaskConfig::Invert(InvertConfig {
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["invert_b"],
+                TaskConfig::Invert(InvertConfig {
+                    source: 0.5.into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,278 @@
+macro_rules! multi_gradient_config {
+    ($type: ty) => {
+        use serde::{Deserialize, Serialize};
+
+        /// Gradient shaped like a `GradientConfig`, through `stops`.
+        ///
+        /// Stop positions run from 0 at `(x1, y1, z1)` to 1 at `(x2, y2, z2)`, or at `radius`
+        /// for every kind but linear.
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct MultiGradientConfig {
+            pub kind: GradientKind,
+            pub radius: $type,
+            pub x1: $type,
+            pub y1: $type,
+            pub z1: $type,
+            pub x2: $type,
+            pub y2: $type,
+            pub z2: $type,
+            pub stops: Vec<GradientStop>,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        impl Default for MultiGradientConfig {
+            fn default() -> Self {
+                Self {
+                    kind: GradientKind::default(),
+                    radius: 1.0,
+                    x1: 0.0,
+                    y1: 0.0,
+                    z1: 0.0,
+                    x2: 1.0,
+                    y2: 1.0,
+                    z2: 0.0,
+                    stops: vec![],
+                    cache: None,
+                }
+            }
+        }
+
+        impl TaskDependencies for MultiGradientConfig {
+            fn dependencies(&self) -> Vec<String> {
+                vec![]
+            }
+
+            fn rename_dependencies(&mut self, _: &dyn Fn(&str) -> Option<String>) {}
+        }
+
+        impl IntoTaskSource for MultiGradientConfig {
+            fn config_into(&self, _: &TaskTree) -> Result<TaskSource, String> {
+                let mut builder = MultiGradientBuilder::new();
+
+                builder
+                    .kind(self.kind)
+                    .radius(self.radius)
+                    .s1([self.x1, self.y1, self.z1])
+                    .s2([self.x2, self.y2, self.z2])
+                    .stops(self.stops.clone());
+
+                builder.check()?;
+                Ok(builder.build().into())
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        deserialize_cache, serialize_cache, CacheConfig, IntoTaskSource, TaskDependencies,
+    };
+    use crate::task::f32::{
+        GradientKind, GradientStop, MultiGradientBuilder, TaskSource, TaskTree,
+    };
+    multi_gradient_config!(f32);
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        deserialize_cache, serialize_cache, CacheConfig, IntoTaskSource, TaskDependencies,
+    };
+    use crate::task::f64::{
+        GradientKind, GradientStop, MultiGradientBuilder, TaskSource, TaskTree,
+    };
+    multi_gradient_config!(f64);
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use crate::ser::f32::{toml::from_str, MultiGradientConfig, TaskConfig};
+        use crate::task::f32::{GradientKind, GradientStop, Interpolation};
+        use std::collections::HashMap;
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [bands]
+                multi_gradient.kind = "radial"
+                multi_gradient.radius = 100
+                multi_gradient.stops = [
+                    { position = 0, value = -1 },
+                    { position = 0.4, value = -0.2, interpolation = "monotone_cubic" },
+                    { position = 0.45, value = 0.1 },
+                    { position = 1, value = 1 },
+                ]
+
+                [ramp]
+                multi_gradient = { x2 = 4, y2 = 0, stops = [{ position = 0.5, value = 2 }] }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["bands"],
+                TaskConfig::MultiGradient(MultiGradientConfig {
+                    kind: GradientKind::Radial,
+                    radius: 100.0,
+                    stops: vec![
+                        GradientStop {
+                            position: 0.0,
+                            value: -1.0,
+                            interpolation: Interpolation::Linear,
+                        },
+                        GradientStop {
+                            position: 0.4,
+                            value: -0.2,
+                            interpolation: Interpolation::MonotoneCubic,
+                        },
+                        GradientStop {
+                            position: 0.45,
+                            value: 0.1,
+                            interpolation: Interpolation::Linear,
+                        },
+                        GradientStop {
+                            position: 1.0,
+                            value: 1.0,
+                            interpolation: Interpolation::Linear,
+                        },
+                    ],
+                    ..Default::default()
+                })
+            );
+
+            assert_eq!(
+                config["ramp"],
+                TaskConfig::MultiGradient(MultiGradientConfig {
+                    x2: 4.0,
+                    y2: 0.0,
+                    stops: vec![GradientStop {
+                        position: 0.5,
+                        value: 2.0,
+                        interpolation: Interpolation::Linear,
+                    }],
+                    ..Default::default()
+                })
+            );
+        }
+
+        #[test]
+        fn helper_from_str_invalid() {
+            let result = from_str("[a]\nmulti_gradient = { kind = \"radial\", radius = 0 }");
+            assert!(matches!(result, Err(x) if x.contains("radius must be greater than 0")));
+
+            let result = from_str("[a]\nmulti_gradient = { kind = \"spherical\", radius = -1 }");
+            assert!(matches!(result, Err(x) if x.contains("radius must be greater than 0")));
+
+            let result =
+                from_str("[a]\nmulti_gradient = { kind = \"cylindrical\", x2 = 0, y2 = 0 }");
+            assert!(matches!(result, Err(x) if x.contains("points must be different")));
+
+            let result = from_str("[a]\nmulti_gradient = { x1 = 1, y1 = 1 }");
+            assert!(matches!(result, Err(x) if x.contains("points must be different")));
+
+            // Only linear gradients ignore the radius
+            let result = from_str("[a]\nmulti_gradient = { radius = 0 }");
+            assert!(result.is_ok());
+        }
+    }
+
+    mod f64 {
+        use crate::ser::f64::{toml::from_str, MultiGradientConfig, TaskConfig};
+        use crate::task::f64::{GradientKind, GradientStop, Interpolation};
+        use std::collections::HashMap;
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [bands]
+                multi_gradient.kind = "radial"
+                multi_gradient.radius = 100
+                multi_gradient.stops = [
+                    { position = 0, value = -1 },
+                    { position = 0.4, value = -0.2, interpolation = "monotone_cubic" },
+                    { position = 0.45, value = 0.1 },
+                    { position = 1, value = 1 },
+                ]
+
+                [ramp]
+                multi_gradient = { x2 = 4, y2 = 0, stops = [{ position = 0.5, value = 2 }] }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["bands"],
+                TaskConfig::MultiGradient(MultiGradientConfig {
+                    kind: GradientKind::Radial,
+                    radius: 100.0,
+                    stops: vec![
+                        GradientStop {
+                            position: 0.0,
+                            value: -1.0,
+                            interpolation: Interpolation::Linear,
+                        },
+                        GradientStop {
+                            position: 0.4,
+                            value: -0.2,
+                            interpolation: Interpolation::MonotoneCubic,
+                        },
+                        GradientStop {
+                            position: 0.45,
+                            value: 0.1,
+                            interpolation: Interpolation::Linear,
+                        },
+                        GradientStop {
+                            position: 1.0,
+                            value: 1.0,
+                            interpolation: Interpolation::Linear,
+                        },
+                    ],
+                    ..Default::default()
+                })
+            );
+
+            assert_eq!(
+                config["ramp"],
+                TaskConfig::MultiGradient(MultiGradientConfig {
+                    x2: 4.0,
+                    y2: 0.0,
+                    stops: vec![GradientStop {
+                        position: 0.5,
+                        value: 2.0,
+                        interpolation: Interpolation::Linear,
+                    }],
+                    ..Default::default()
+                })
+            );
+        }
+
+        #[test]
+        fn helper_from_str_invalid() {
+            let result = from_str("[a]\nmulti_gradient = { kind = \"radial\", radius = 0 }");
+            assert!(matches!(result, Err(x) if x.contains("radius must be greater than 0")));
+
+            let result = from_str("[a]\nmulti_gradient = { kind = \"spherical\", radius = -1 }");
+            assert!(matches!(result, Err(x) if x.contains("radius must be greater than 0")));
+
+            let result =
+                from_str("[a]\nmulti_gradient = { kind = \"cylindrical\", x2 = 0, y2 = 0 }");
+            assert!(matches!(result, Err(x) if x.contains("points must be different")));
+
+            let result = from_str("[a]\nmulti_gradient = { x1 = 1, y1 = 1 }");
+            assert!(matches!(result, Err(x) if x.contains("points must be different")));
+
+            // Only linear gradients ignore the radius
+            let result = from_str("[a]\nmulti_gradient = { radius = 0 }");
+            assert!(result.is_ok());
+        }
+    }
+}
@@ -2,11 +2,38 @@ macro_rules! name_or_const {
 	($type: ty) => {
 		use serde::{Deserialize, Serialize};
 		
+		/// A task reference, a constant or an inline task.
+		///
+		/// Strings starting with `=` are parsed as arithmetic expressions (e.g.
+		/// `"= mountains * 0.5 + clamp(hills, 0, 1)"`) and compiled into inline tasks. Any other
+		/// string is the name of a task, even if it contains operators like `river-mask`.
 		#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, PartialOrd)]
-		#[serde(untagged)]
+		#[serde(untagged, try_from = "RawNameOrConst")]
 		pub enum NameOrConst {
 			Named(String),
 			Value($type),
+			/// Anonymous task defined in place, given a generated name when the tree is built
+			Inline(Box<TaskConfig>),
+		}
+		
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum RawNameOrConst {
+			Named(String),
+			Value($type),
+			Inline(Box<TaskConfig>),
+		}
+		
+		impl TryFrom<RawNameOrConst> for NameOrConst {
+			type Error = String;
+		
+			fn try_from(value: RawNameOrConst) -> Result<Self, Self::Error> {
+				match value {
+					RawNameOrConst::Named(x) => NameOrConst::parse(&x),
+					RawNameOrConst::Value(x) => Ok(NameOrConst::Value(x)),
+					RawNameOrConst::Inline(x) => Ok(NameOrConst::Inline(x)),
+				}
+			}
 		}
 		
 		impl Default for NameOrConst {
@@ -27,11 +54,22 @@ macro_rules! name_or_const {
 			}
 		}
 		
+		impl From<TaskConfig> for NameOrConst {
+			fn from(value: TaskConfig) -> Self {
+				NameOrConst::Inline(Box::new(value))
+			}
+		}
+		
 		impl NameOrConst {
 			pub fn is_named(&self) -> bool {
-				match &self {
-					NameOrConst::Named(_) => true,
-					_ => false,
+				matches!(self, NameOrConst::Named(_))
+			}
+		
+			/// Parses a task name or an arithmetic expression starting with `=`.
+			pub fn parse(value: &str) -> Result<Self, String> {
+				match expression_source(value) {
+					Some(expression) => parse(expression).map(|x| compile(&x)),
+					None => Ok(NameOrConst::Named(value.to_owned())),
 				}
 			}
 		}
@@ -48,23 +86,59 @@ macro_rules! push_named_to_vec {
     };
 }
 
+macro_rules! rename_named {
+    ($val:expr, $rename:expr) => {
+        match &mut $val {
+            NameOrConst::Named(x) => {
+                if let Some(name) = $rename(x) {
+                    *x = name;
+                }
+            }
+            NameOrConst::Inline(x) => x.rename_dependencies($rename),
+            _ => {}
+        }
+    };
+}
+
+/// Replaces an inline task with a reference to `$name`, pushing the task onto `$vec`
+macro_rules! push_inline_to_vec {
+    ($vec:expr, $val:expr, $name:expr) => {
+        if let NameOrConst::Inline(_) = &$val {
+            let name: String = $name;
+            let task = std::mem::replace(&mut $val, NameOrConst::Named(name.clone()));
+            if let NameOrConst::Inline(task) = task {
+                $vec.push((name, *task));
+            }
+        }
+    };
+}
+
 macro_rules! add_task_to_builder {
     ($val:expr, $builder:expr, $func:ident, $named:ident, $tree:expr) => {
         match &$val {
             NameOrConst::Named(x) => $builder.$named(x),
             NameOrConst::Value(x) => $builder.$func(x.clone()),
+            NameOrConst::Inline(x) => $builder.$func(x.config_into($tree)?),
         };
     };
 }
 
 pub mod f32 {
+	use crate::ser::expression::{f32::compile, expression_source, parse};
+	use crate::ser::f32::TaskConfig;
 	name_or_const!(f32);
+	pub(crate) use push_inline_to_vec;
 	pub(crate) use push_named_to_vec;
+	pub(crate) use rename_named;
 	pub(crate) use add_task_to_builder;
 }
 
 pub mod f64 {
+	use crate::ser::expression::{f64::compile, expression_source, parse};
+	use crate::ser::f64::TaskConfig;
 	name_or_const!(f64);
+	pub(crate) use push_inline_to_vec;
 	pub(crate) use push_named_to_vec;
+	pub(crate) use rename_named;
 	pub(crate) use add_task_to_builder;
 }
@@ -1,12 +1,24 @@
 pub(crate) trait TaskDependencies {
     fn dependencies(&self) -> Vec<String>;
+
+    /// Renames every named dependency for which `rename` returns a new name.
+    fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>);
 }
 
 macro_rules! into_task_source {
     ($type: ty) => {
         pub(crate) trait IntoTaskSource {
-            #[must_use]
-            fn config_into(&self, tree: &TaskTree) -> TaskSource;
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String>;
+        }
+    };
+}
+
+macro_rules! inline_tasks {
+    () => {
+        pub(crate) trait InlineTasks {
+            /// Takes every inline task out of the config, replacing each with a reference to
+            /// a name derived from `name` and the field it was declared in.
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)>;
         }
     };
 }
@@ -17,16 +29,30 @@ macro_rules! task_config {
 
         #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, PartialOrd)]
         #[serde(rename_all = "snake_case")]
-        pub(crate) enum TaskConfig {
+        pub enum TaskConfig {
+            Abs(AbsConfig),
             Aggregate(AggregateConfig),
             Bias(BiasConfig),
+            Clamp(ClampConfig),
             Constant($type),
+            Curve(CurveConfig),
+            /// Task type registered with `register_custom_task`
+            Custom(CustomConfig),
+            Exponent(ExponentConfig),
             Fractal(FractalConfig),
             Gradient(GradientConfig),
+            Invert(InvertConfig),
+            MultiGradient(MultiGradientConfig),
+            /// Value that can be changed through `TaskTree::set_param` once the tree is built
+            Parameter($type),
+            Pow(PowConfig),
             Scale(ScaleConfig),
             ScaleOffset(ScaleOffsetConfig),
             Selector(SelectorConfig),
+            Template(TemplateInstanceConfig),
+            Terrace(TerraceConfig),
             TransformDomain(TransformDomainConfig),
+            Turbulence(TurbulenceConfig),
         }
 
         impl Default for TaskConfig {
@@ -38,47 +64,137 @@ macro_rules! task_config {
         impl TaskDependencies for TaskConfig {
             fn dependencies(&self) -> Vec<String> {
                 match &self {
+                    Self::Abs(x) => x.dependencies(),
                     Self::Aggregate(x) => x.dependencies(),
                     Self::Bias(x) => x.dependencies(),
+                    Self::Clamp(x) => x.dependencies(),
                     Self::Constant(_) => vec![],
+                    Self::Curve(x) => x.dependencies(),
+                    Self::Custom(x) => x.dependencies(),
+                    Self::Exponent(x) => x.dependencies(),
                     Self::Fractal(x) => x.dependencies(),
                     Self::Gradient(x) => x.dependencies(),
+                    Self::Invert(x) => x.dependencies(),
+                    Self::MultiGradient(x) => x.dependencies(),
+                    Self::Parameter(_) => vec![],
+                    Self::Pow(x) => x.dependencies(),
                     Self::Scale(x) => x.dependencies(),
                     Self::ScaleOffset(x) => x.dependencies(),
                     Self::Selector(x) => x.dependencies(),
+                    Self::Template(x) => x.dependencies(),
+                    Self::Terrace(x) => x.dependencies(),
                     Self::TransformDomain(x) => x.dependencies(),
+                    Self::Turbulence(x) => x.dependencies(),
+                }
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                match self {
+                    Self::Abs(x) => x.rename_dependencies(rename),
+                    Self::Aggregate(x) => x.rename_dependencies(rename),
+                    Self::Bias(x) => x.rename_dependencies(rename),
+                    Self::Clamp(x) => x.rename_dependencies(rename),
+                    Self::Constant(_) => {}
+                    Self::Curve(x) => x.rename_dependencies(rename),
+                    Self::Custom(x) => x.rename_dependencies(rename),
+                    Self::Exponent(x) => x.rename_dependencies(rename),
+                    Self::Fractal(x) => x.rename_dependencies(rename),
+                    Self::Gradient(x) => x.rename_dependencies(rename),
+                    Self::Invert(x) => x.rename_dependencies(rename),
+                    Self::MultiGradient(x) => x.rename_dependencies(rename),
+                    Self::Parameter(_) => {}
+                    Self::Pow(x) => x.rename_dependencies(rename),
+                    Self::Scale(x) => x.rename_dependencies(rename),
+                    Self::ScaleOffset(x) => x.rename_dependencies(rename),
+                    Self::Selector(x) => x.rename_dependencies(rename),
+                    Self::Template(x) => x.rename_dependencies(rename),
+                    Self::Terrace(x) => x.rename_dependencies(rename),
+                    Self::TransformDomain(x) => x.rename_dependencies(rename),
+                    Self::Turbulence(x) => x.rename_dependencies(rename),
+                }
+            }
+        }
+
+        impl InlineTasks for TaskConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                match self {
+                    Self::Abs(x) => x.inline_tasks(name),
+                    Self::Aggregate(x) => x.inline_tasks(name),
+                    Self::Bias(x) => x.inline_tasks(name),
+                    Self::Clamp(x) => x.inline_tasks(name),
+                    Self::Constant(_) => vec![],
+                    Self::Curve(x) => x.inline_tasks(name),
+                    Self::Custom(x) => x.inline_tasks(name),
+                    Self::Exponent(x) => x.inline_tasks(name),
+                    Self::Fractal(_) => vec![],
+                    Self::Gradient(_) => vec![],
+                    Self::Invert(x) => x.inline_tasks(name),
+                    Self::MultiGradient(_) => vec![],
+                    Self::Parameter(_) => vec![],
+                    Self::Pow(x) => x.inline_tasks(name),
+                    Self::Scale(x) => x.inline_tasks(name),
+                    Self::ScaleOffset(x) => x.inline_tasks(name),
+                    Self::Selector(x) => x.inline_tasks(name),
+                    Self::Template(x) => x.inline_tasks(name),
+                    Self::Terrace(x) => x.inline_tasks(name),
+                    Self::TransformDomain(x) => x.inline_tasks(name),
+                    Self::Turbulence(x) => x.inline_tasks(name),
                 }
             }
         }
 
         impl IntoTaskSource for TaskConfig {
-            fn config_into(&self, tree: &TaskTree) -> TaskSource {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
                 match &self {
+                    TaskConfig::Abs(x) => x.config_into(tree),
                     TaskConfig::Aggregate(x) => x.config_into(tree),
                     TaskConfig::Bias(x) => x.config_into(tree),
-                    TaskConfig::Constant(x) => TaskSource::from(*x),
+                    TaskConfig::Clamp(x) => x.config_into(tree),
+                    TaskConfig::Constant(x) => Ok(TaskSource::from(*x)),
+                    TaskConfig::Curve(x) => x.config_into(tree),
+                    TaskConfig::Custom(x) => x.config_into(tree),
+                    TaskConfig::Exponent(x) => x.config_into(tree),
                     TaskConfig::Fractal(x) => x.config_into(tree),
                     TaskConfig::Gradient(x) => x.config_into(tree),
+                    TaskConfig::Invert(x) => x.config_into(tree),
+                    TaskConfig::MultiGradient(x) => x.config_into(tree),
+                    TaskConfig::Parameter(x) => Ok(TaskSource::from(Parameter::new(*x))),
+                    TaskConfig::Pow(x) => x.config_into(tree),
                     TaskConfig::Scale(x) => x.config_into(tree),
                     TaskConfig::ScaleOffset(x) => x.config_into(tree),
                     TaskConfig::Selector(x) => x.config_into(tree),
+                    TaskConfig::Template(x) => x.config_into(tree),
+                    TaskConfig::Terrace(x) => x.config_into(tree),
                     TaskConfig::TransformDomain(x) => x.config_into(tree),
+                    TaskConfig::Turbulence(x) => x.config_into(tree),
                 }
             }
         }
 
         impl TaskConfig {
-            fn cached(&self) -> bool {
+            pub(crate) fn cache(&self) -> Option<CacheConfig> {
                 match &self {
-                    Self::Aggregate(x) => x.cached,
-                    Self::Bias(x) => x.cached,
-                    Self::Constant(_) => false,
-                    Self::Fractal(x) => x.cached,
-                    Self::Gradient(x) => x.cached,
-                    Self::Scale(x) => x.cached,
-                    Self::ScaleOffset(x) => x.cached,
-                    Self::Selector(x) => x.cached,
-                    Self::TransformDomain(x) => x.cached,
+                    Self::Abs(x) => x.cache,
+                    Self::Aggregate(x) => x.cache,
+                    Self::Bias(x) => x.cache,
+                    Self::Clamp(x) => x.cache,
+                    Self::Constant(_) => None,
+                    Self::Curve(x) => x.cache,
+                    Self::Custom(x) => x.cache,
+                    Self::Exponent(x) => x.cache,
+                    Self::Fractal(x) => x.cache,
+                    Self::Gradient(x) => x.cache,
+                    Self::Invert(x) => x.cache,
+                    Self::MultiGradient(x) => x.cache,
+                    Self::Parameter(_) => None,
+                    Self::Pow(x) => x.cache,
+                    Self::Scale(x) => x.cache,
+                    Self::ScaleOffset(x) => x.cache,
+                    Self::Selector(x) => x.cache,
+                    Self::Template(_) => None,
+                    Self::Terrace(x) => x.cache,
+                    Self::TransformDomain(x) => x.cache,
+                    Self::Turbulence(x) => x.cache,
                 }
             }
         }
@@ -87,7 +203,28 @@ macro_rules! task_config {
 
 macro_rules! sort_tasks {
     () => {
-        use std::collections::HashMap;
+        /// Moves every inline task into `tasks` under a generated `<parent>.<field>` name so it
+        /// can be sorted and built like any other named task.
+        pub(crate) fn hoist_inline_tasks(
+            tasks: &mut HashMap<String, TaskConfig>,
+        ) -> Result<(), String> {
+            let mut pending: Vec<String> = tasks.keys().cloned().collect();
+            while let Some(name) = pending.pop() {
+                let inline = match tasks.get_mut(&name) {
+                    Some(task) => task.inline_tasks(&name),
+                    None => continue,
+                };
+
+                for (inline_name, task) in inline {
+                    if tasks.contains_key(&inline_name) {
+                        return Err(format!("Inline task '{}' already exists", inline_name));
+                    }
+                    tasks.insert(inline_name.clone(), task);
+                    pending.push(inline_name);
+                }
+            }
+            Ok(())
+        }
 
         /// https://en.wikipedia.org/wiki/Topological_sorting#Kahn's_algorithm
         ///
@@ -97,18 +234,22 @@ macro_rules! sort_tasks {
         /// while S is not empty do
         /// remove a node n from S
         ///     add n to L
-        /// 	for each node m with an edge e from n to m do
-        /// 	remove edge e from the graph
-        /// 	if m has no other incoming edges then
-        /// 		insert m into S
+        ///     for each node m with an edge e from n to m do
+        ///     remove edge e from the graph
+        ///     if m has no other incoming edges then
+        ///         insert m into S
         ///
         /// if graph has edges then
-        /// 	return error   (graph has at least one cycle)
+        ///     return error   (graph has at least one cycle)
         /// else
-        /// 	return L   (a topologically sorted order)
+        ///     return L   (a topologically sorted order)
+        ///
+        /// Inline tasks are hoisted into `tasks` before sorting, see [`hoist_inline_tasks`].
         pub(crate) fn sort_tasks(
-            tasks: &HashMap<String, TaskConfig>,
+            tasks: &mut HashMap<String, TaskConfig>,
         ) -> Result<Vec<String>, String> {
+            hoist_inline_tasks(tasks)?;
+
             let graph_tasks: Vec<&String> = tasks.keys().collect();
             let index_of = |o: &String| -> Option<usize> {
                 for (i, t) in graph_tasks.iter().enumerate() {
@@ -169,81 +310,242 @@ macro_rules! sort_tasks {
     };
 }
 
-#[cfg(feature = "toml")]
-macro_rules! from_str {
+macro_rules! from_config {
     () => {
-        pub fn from_str(data: impl Into<String>) -> Result<Box<TaskTree>, String> {
-            let result: Result<HashMap<String, TaskConfig>, ::toml::de::Error> =
-                ::toml::from_str(&data.into());
-            match result {
-                Ok(mut result) => {
-                    let sorted_tasks = sort_tasks(&result)?;
-                    let mut tree = Box::new(TaskTree::new());
-
-                    for task_name in sorted_tasks {
-                        let mut name = task_name.clone();
-                        let config: &TaskConfig = result.entry(name.clone()).or_default();
-
-                        if config.cached() {
-                            name = format!("{}_cached", name);
-                        }
-                        let task: TaskSource = config.config_into(tree.as_ref());
-                        tree.add_task(&name, task);
-
-                        if config.cached() {
-                            tree.add_task(
-                                &task_name,
-                                CacheBuilder::new().named_source(name).link(&tree).build(),
-                            );
-                        }
+        /// Builds a [`TaskTree`] from a set of named task configs, adding each task
+        /// after its dependencies and wrapping `cached` tasks in a cache.
+        ///
+        /// If the config sets `share = true`, tasks with identical configs and dependencies
+        /// are built once and added under every name, see [`TaskTree::aliases`].
+        ///
+        /// Template instances are expanded first, so `config` must already have its includes
+        /// resolved.
+        pub fn from_config(config: impl Into<ConfigFile>) -> Result<Box<TaskTree>, String> {
+            let config = config.into();
+            let mut shared = config.share.then(SharedTasks::default);
+            let mut config = config.into_tasks()?;
+            let sorted_tasks = sort_tasks(&mut config)?;
+            check_custom_tasks(&config)?;
+            let mut tree = Box::new(TaskTree::new());
+
+            for task_name in sorted_tasks {
+                let config: &TaskConfig = config.entry(task_name.clone()).or_default();
+                match shared.as_mut().and_then(|x| x.find(&task_name, config)) {
+                    Some(original) => share_config_task(&mut tree, &task_name, &original),
+                    None => add_config_task(&mut tree, &task_name, config)?,
+                }
+            }
+
+            Ok(tree)
+        }
+
+        /// Finds tasks with the same config as a task seen earlier, so identical subgraphs are
+        /// built once and shared under every name.
+        ///
+        /// Configs are compared after renaming their dependencies to the first task with the
+        /// same config, so tasks built from identical tasks are identical too. Parameters are
+        /// never shared since each can be set on its own, nor are custom tasks since they may
+        /// hold state of their own.
+        #[derive(Default)]
+        pub(crate) struct SharedTasks {
+            keys: HashMap<u64, Vec<(TaskConfig, String)>>,
+            originals: HashMap<String, String>,
+        }
+
+        impl SharedTasks {
+            /// Returns the first task seen with the same config as `task_name`, tasks must be
+            /// given after their dependencies.
+            pub(crate) fn find(&mut self, task_name: &str, config: &TaskConfig) -> Option<String> {
+                if let TaskConfig::Parameter(_) | TaskConfig::Custom(_) = config {
+                    return None;
+                }
+
+                let mut config = config.clone();
+                config.rename_dependencies(&|x| self.originals.get(x).cloned());
+
+                let candidates = self.keys.entry(structural_hash(&config)).or_default();
+                let original = match candidates.iter().find(|(x, _)| *x == config) {
+                    Some((_, original)) => original.clone(),
+                    None => {
+                        candidates.push((config, task_name.to_owned()));
+                        task_name.to_owned()
                     }
+                };
+                self.originals
+                    .insert(task_name.to_owned(), original.clone());
+                (original != task_name).then_some(original)
+            }
+        }
 
-                    Ok(tree)
+        /// Adds the task built for `original` to `tree` under `task_name` too.
+        pub(crate) fn share_config_task(tree: &mut TaskTree, task_name: &str, original: &str) {
+            for (from, to) in [
+                (original.to_owned(), task_name.to_owned()),
+                (
+                    format!("{}_cached", original),
+                    format!("{}_cached", task_name),
+                ),
+            ] {
+                if let Some(task) = tree.get(from) {
+                    tree.add_task(to, task.clone());
                 }
-                Err(x) => Err(x.to_string()),
             }
         }
+
+        /// Builds `config` and adds it to `tree` under `task_name`, behind a cache if the
+        /// config is `cached`.
+        pub(crate) fn add_config_task(
+            tree: &mut TaskTree,
+            task_name: &str,
+            config: &TaskConfig,
+        ) -> Result<(), String> {
+            let cache = config.cache();
+            let mut name = task_name.to_owned();
+
+            if let Some(cache) = cache {
+                cache
+                    .check()
+                    .map_err(|x| format!("Task '{}': {}", task_name, x))?;
+                name = format!("{}_cached", name);
+            }
+            let task: TaskSource = config.config_into(tree)?;
+            tree.add_task(&name, task);
+
+            if let Some(cache) = cache {
+                tree.add_task(
+                    task_name,
+                    CacheBuilder::new()
+                        .strategy(cache.strategy())
+                        .tolerance(cache.tolerance)
+                        .named_source(name)
+                        .link(tree)
+                        .build(),
+                );
+            }
+            Ok(())
+        }
+    };
+}
+
+macro_rules! from_str {
+    ($from_str: path, $to_string: path) => {
+        use super::{config_graph, from_config, ConfigFile, ReloadableTree, TaskConfig, TaskTree};
+        use crate::graph::Graph;
+        use std::collections::HashMap;
+        use std::path::Path;
+
+        fn parse(data: &str) -> Result<ConfigFile, String> {
+            $from_str(data).map_err(|x| x.to_string())
+        }
+
+        /// Parses `data` into a config and builds a [`TaskTree`] from it.
+        ///
+        /// Included files are resolved relative to the working directory.
+        pub fn from_str(data: impl Into<String>) -> Result<Box<TaskTree>, String> {
+            let data: String = data.into();
+            let mut config = parse(&data)?;
+            config.resolve_includes(Path::new("."), &parse, &mut vec![], &mut vec![])?;
+            from_config(config)
+        }
+
+        /// Reads the config at `path` and builds a [`TaskTree`] from it.
+        ///
+        /// Included files are resolved relative to the directory of `path`.
+        pub fn from_file(path: impl AsRef<Path>) -> Result<Box<TaskTree>, String> {
+            from_config(ConfigFile::load(path.as_ref(), &parse)?)
+        }
+
+        /// Reads the config at `path` into a [`ReloadableTree`] that can pick up later changes
+        /// to the file.
+        pub fn reloadable(path: impl AsRef<Path>) -> Result<ReloadableTree, String> {
+            ReloadableTree::load(path.as_ref(), parse)
+        }
+
+        /// Parses `data` into a config and describes its tasks, see [`config_graph`].
+        pub fn graph_from_str(data: impl Into<String>) -> Result<Graph, String> {
+            let data: String = data.into();
+            let mut config = parse(&data)?;
+            config.resolve_includes(Path::new("."), &parse, &mut vec![], &mut vec![])?;
+            config_graph(config)
+        }
+
+        /// Reads the config at `path` and describes its tasks, see [`config_graph`].
+        pub fn graph_from_file(path: impl AsRef<Path>) -> Result<Graph, String> {
+            config_graph(ConfigFile::load(path.as_ref(), &parse)?)
+        }
+
+        /// Serializes a set of named task configs into a string that [`from_str`] accepts.
+        ///
+        /// Only tasks are written, the includes, templates and `share` of a [`ConfigFile`]
+        /// can't be written back.
+        pub fn to_string(config: &HashMap<String, TaskConfig>) -> Result<String, String> {
+            $to_string(config).map_err(|x| x.to_string())
+        }
+    };
+}
+
+macro_rules! config_formats {
+    () => {
+        #[cfg(feature = "json")]
+        pub mod json {
+            from_str!(::serde_json::from_str, ::serde_json::to_string);
+        }
+
+        #[cfg(feature = "ron")]
+        pub mod ron {
+            from_str!(::ron::from_str, ::ron::to_string);
+        }
+
+        #[cfg(feature = "toml")]
+        pub mod toml {
+            from_str!(::toml::from_str, ::toml::to_string);
+        }
+
+        #[cfg(feature = "yaml")]
+        pub mod yaml {
+            from_str!(::serde_yaml::from_str, ::serde_yaml::to_string);
+        }
     };
 }
 
 pub mod f32 {
     pub(crate) use super::TaskDependencies;
+    use crate::ser::config_hash::structural_hash;
     use crate::ser::f32::{
-        AggregateConfig, BiasConfig, FractalConfig, GradientConfig, ScaleConfig, ScaleOffsetConfig,
-        SelectorConfig, TransformDomainConfig,
+        check_custom_tasks, config_graph, AbsConfig, AggregateConfig, BiasConfig, CacheConfig,
+        ClampConfig, ConfigFile, CurveConfig, CustomConfig, ExponentConfig, FractalConfig,
+        GradientConfig, InvertConfig, MultiGradientConfig, PowConfig, ReloadableTree, ScaleConfig,
+        ScaleOffsetConfig, SelectorConfig, TemplateInstanceConfig, TerraceConfig,
+        TransformDomainConfig, TurbulenceConfig,
     };
-    use crate::task::f32::{CacheBuilder, TaskSource, TaskTree};
+    use crate::task::f32::{CacheBuilder, Parameter, TaskSource, TaskTree};
+    use std::collections::HashMap;
     into_task_source!(f32);
+    inline_tasks!();
     task_config!(f32);
     sort_tasks!();
-
-    #[cfg(feature = "toml")]
-    pub mod toml {
-        use super::{sort_tasks, CacheBuilder, IntoTaskSource, TaskConfig};
-        use crate::task::f32::{TaskSource, TaskTree};
-        use std::collections::HashMap;
-        from_str!();
-    }
+    from_config!();
+    config_formats!();
 }
 
 pub mod f64 {
     pub(crate) use super::TaskDependencies;
+    use crate::ser::config_hash::structural_hash;
     use crate::ser::f64::{
-        AggregateConfig, BiasConfig, FractalConfig, GradientConfig, ScaleConfig, ScaleOffsetConfig,
-        SelectorConfig, TransformDomainConfig,
+        check_custom_tasks, config_graph, AbsConfig, AggregateConfig, BiasConfig, CacheConfig,
+        ClampConfig, ConfigFile, CurveConfig, CustomConfig, ExponentConfig, FractalConfig,
+        GradientConfig, InvertConfig, MultiGradientConfig, PowConfig, ReloadableTree, ScaleConfig,
+        ScaleOffsetConfig, SelectorConfig, TemplateInstanceConfig, TerraceConfig,
+        TransformDomainConfig, TurbulenceConfig,
     };
-    use crate::task::f64::{CacheBuilder, TaskSource, TaskTree};
+    use crate::task::f64::{CacheBuilder, Parameter, TaskSource, TaskTree};
+    use std::collections::HashMap;
     into_task_source!(f64);
+    inline_tasks!();
     task_config!(f64);
     sort_tasks!();
-
-    #[cfg(feature = "toml")]
-    pub mod toml {
-        use super::{sort_tasks, CacheBuilder, IntoTaskSource, TaskConfig};
-        use crate::task::f64::{TaskSource, TaskTree};
-        use std::collections::HashMap;
-        from_str!();
-    }
+    from_config!();
+    config_formats!();
 }
 
 #[cfg(feature = "toml")]
@@ -254,8 +556,11 @@ pub mod toml {
             use std::collections::HashMap;
 
             use crate::ser::f32::{
-                sort_tasks, toml::from_str, FractalConfig, FractalSource, TaskConfig,
+                from_config, sort_tasks,
+                toml::{from_str, to_string},
+                CacheConfig, FractalConfig, FractalSource, ScaleConfig, TaskConfig,
             };
+            use crate::task::f32::CacheMode;
 
             #[test]
             fn generic_parse() {
@@ -277,7 +582,7 @@ pub mod toml {
                         octaves: 1,
                         frequency: 0.5,
                         source: FractalSource::Perlin,
-                        cached: true,
+                        cache: Some(CacheConfig::default()),
                         ..Default::default()
                     })
                 );
@@ -296,9 +601,10 @@ pub mod toml {
                     fractal = { octaves = 1, frequency = 0.5, source = "perlin" }
                 })
                 .unwrap();
-                let config: HashMap<String, TaskConfig> = ::toml::from_str(data.as_str()).unwrap();
+                let mut config: HashMap<String, TaskConfig> =
+                    ::toml::from_str(data.as_str()).unwrap();
 
-                let all_possible = vec![
+                let all_possible = [
                     vec![
                         "const_a".to_owned(),
                         "fractal_a".to_owned(),
@@ -311,7 +617,7 @@ pub mod toml {
                     ],
                 ];
 
-                let sorted = sort_tasks(&config);
+                let sorted = sort_tasks(&mut config);
                 assert!(sorted.is_ok());
                 let sorted = sorted.unwrap();
                 assert_eq!(sorted.len(), 3);
@@ -338,6 +644,396 @@ pub mod toml {
                     Err(x) => panic!("{}", x),
                 }
             }
+
+            #[test]
+            fn inline_sort() {
+                let data = ::toml::to_string(&::toml::toml! {
+                    [scale_a]
+                    scale = { scale = { constant = 2.0 }, source = { fractal = { octaves = 4 } } }
+                })
+                .unwrap();
+                let mut config: HashMap<String, TaskConfig> =
+                    ::toml::from_str(data.as_str()).unwrap();
+
+                assert_eq!(
+                    config["scale_a"],
+                    TaskConfig::Scale(ScaleConfig {
+                        scale: TaskConfig::Constant(2.0).into(),
+                        source: TaskConfig::Fractal(FractalConfig {
+                            octaves: 4,
+                            ..Default::default()
+                        })
+                        .into(),
+                        ..Default::default()
+                    })
+                );
+
+                let sorted = sort_tasks(&mut config).unwrap();
+                assert_eq!(sorted.len(), 3);
+                assert_eq!(sorted.last().unwrap(), "scale_a");
+                assert_eq!(
+                    config["scale_a"],
+                    TaskConfig::Scale(ScaleConfig {
+                        scale: "scale_a.scale".to_owned().into(),
+                        source: "scale_a.source".to_owned().into(),
+                        ..Default::default()
+                    })
+                );
+                assert_eq!(config["scale_a.scale"], TaskConfig::Constant(2.0));
+            }
+
+            #[test]
+            fn inline_name_collision() {
+                let data = r#"
+                "scale_a.source" = { constant = 1.0 }
+                scale_a = { scale = { source = { constant = 2.0 } } }
+                "#;
+                assert!(from_str(data).is_err());
+            }
+
+            #[test]
+            fn helper_from_str_inline() {
+                let data = r#"
+                [select_a]
+                selector.condition = { aggregate = { source = [0.25, { constant = 0.5 }] } }
+                selector.lower = 0.0
+                selector.upper = { scale = { scale = 2.0, source = 1.0 } }
+                "#;
+                match from_str(data) {
+                    Ok(mut x) => {
+                        assert!(x.get("select_a.condition").is_some());
+                        assert!(x.get("select_a.condition.source.1").is_some());
+                        assert!(x.get("select_a.upper").is_some());
+
+                        assert_eq!(x.sample_1d("select_a", 1.0), 2.0);
+                    }
+                    Err(x) => panic!("{}", x),
+                }
+            }
+
+            #[test]
+            fn helper_from_str_params() {
+                let data = r#"
+                [params]
+                sea_level = 0.25
+
+                [height]
+                parameter = 2.0
+
+                [land]
+                scale = { source = "sea_level", scale = "height" }
+
+                [coast]
+                aggregate = { source = ["= sea_level * 2", 1.0] }
+                "#;
+                match from_str(data) {
+                    Ok(mut x) => {
+                        assert_eq!(x.param("sea_level"), Some(0.25));
+                        assert_eq!(x.sample_1d("land", 1.0), 0.5);
+                        assert_eq!(x.sample_1d("coast", 1.0), 1.5);
+
+                        assert!(x.set_param("sea_level", 0.5));
+                        assert!(x.set_param("height", 3.0));
+                        assert_eq!(x.sample_1d("land", 1.0), 1.5);
+                        assert_eq!(x.sample_1d("coast", 1.0), 2.0);
+                    }
+                    Err(x) => panic!("{}", x),
+                }
+
+                let data = r#"
+                [params]
+                sea_level = 0.25
+
+                [sea_level]
+                constant = 1.0
+                "#;
+                let result = from_str(data);
+                assert!(
+                    matches!(result, Err(x) if x.contains("'sea_level' is defined more than once"))
+                );
+            }
+
+            #[test]
+            fn helper_from_config_invalid_cache() {
+                let config = HashMap::from([(
+                    "a".to_owned(),
+                    TaskConfig::Fractal(FractalConfig {
+                        cache: Some(CacheConfig {
+                            mode: CacheMode::Grid,
+                            resolution: 0.0,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                )]);
+                let result = from_config(config);
+                assert!(
+                    matches!(result, Err(x) if x == "Task 'a': Cache resolution must be greater than 0, got 0")
+                );
+            }
+
+            #[test]
+            fn helper_from_str_shared() {
+                let data = r#"
+                share = true
+
+                [params]
+                low = 0.5
+                high = 0.5
+
+                [hills]
+                fractal = { octaves = 2, frequency = 0.5 }
+
+                [mountains]
+                fractal = { frequency = 0.5, octaves = 2 }
+
+                [hills_scaled]
+                scale = { source = "hills", scale = 2.0, cached = true }
+
+                [mountains_scaled]
+                scale = { source = "mountains", scale = 2.0, cached = true }
+
+                [valleys]
+                fractal = { octaves = 3, frequency = 0.5 }
+
+                [valleys_scaled]
+                scale = { source = "valleys", scale = 2.0, cached = true }
+                "#;
+                let tree = from_str(data).unwrap();
+                let shared = |a: &str, b: &str| tree.get(a).unwrap().ptr_eq(tree.get(b).unwrap());
+
+                assert!(shared("hills", "mountains"));
+                assert!(shared("hills_scaled", "mountains_scaled"));
+                assert!(shared("hills_scaled_cached", "mountains_scaled_cached"));
+                assert!(!shared("hills", "valleys"));
+                assert!(!shared("hills_scaled", "valleys_scaled"));
+                assert!(!shared("low", "high"));
+            }
+
+            #[test]
+            fn helper_from_str_shared_aliases() {
+                let data = r#"
+                share = true
+                x = { scale = { source = 1.0, scale = 2.0 } }
+                y = { scale = { source = 1.0, scale = 2.0 } }
+                ux = { scale = { source = "x", scale = 1.0 } }
+                uy = { scale = { source = "y", scale = 1.0 } }
+                "#;
+                let mut tree = from_str(data).unwrap();
+                assert_eq!(tree.aliases("y"), ["x", "y"]);
+                assert_eq!(tree.dependencies("uy"), ["x", "y"]);
+                assert_eq!(tree.dependents("y"), ["ux", "uy"]);
+
+                // Replacing one name replaces the shared task, so every name stays consistent
+                assert!(tree.replace("y", 100.0).is_ok());
+                assert_eq!(tree.sample_1d("x", 1.0), 100.0);
+                assert_eq!(tree.sample_1d("ux", 1.0), 100.0);
+                assert_eq!(tree.sample_1d("uy", 1.0), 100.0);
+            }
+
+            #[test]
+            fn helper_from_str_not_shared() {
+                let data = r#"
+                x = { scale = { source = 1.0, scale = 2.0 } }
+                y = { scale = { source = 1.0, scale = 2.0 } }
+                ux = { scale = { source = "x", scale = 1.0 } }
+                uy = { scale = { source = "y", scale = 1.0 } }
+                "#;
+                let mut tree = from_str(data).unwrap();
+                assert!(!tree.get("x").unwrap().ptr_eq(tree.get("y").unwrap()));
+                assert_eq!(tree.dependencies("uy"), ["y"]);
+                assert_eq!(tree.dependents("y"), ["uy"]);
+
+                let graph = tree.graph();
+                assert!(graph.edges.iter().any(|x| x.from == "y" && x.to == "uy"));
+                assert!(!graph.edges.iter().any(|x| x.from == "x" && x.to == "uy"));
+
+                assert!(tree.replace("y", 100.0).is_ok());
+                assert_eq!(tree.sample_1d("x", 1.0), 2.0);
+                assert_eq!(tree.sample_1d("ux", 1.0), 2.0);
+                assert_eq!(tree.sample_1d("uy", 1.0), 100.0);
+            }
+
+            #[test]
+            fn helper_to_string() {
+                let config = HashMap::from([
+                    ("const_a".to_owned(), TaskConfig::Constant(1.0)),
+                    (
+                        "fractal_a".to_owned(),
+                        TaskConfig::Fractal(FractalConfig {
+                            octaves: 1,
+                            cache: Some(CacheConfig::default()),
+                            ..Default::default()
+                        }),
+                    ),
+                ]);
+
+                let data = to_string(&config).unwrap();
+                let result: HashMap<String, TaskConfig> = ::toml::from_str(&data).unwrap();
+                assert_eq!(result, config);
+                assert!(from_str(data).is_ok());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+pub mod json {
+    #[cfg(test)]
+    mod test {
+        mod f32 {
+            use std::collections::HashMap;
+
+            use crate::ser::f32::{
+                json::from_str, json::to_string, CacheConfig, ScaleConfig, TaskConfig,
+            };
+
+            #[test]
+            fn helper_from_str() {
+                let data = r#"{
+                    "const_a": { "constant": 1.0 },
+                    "fractal_a": { "fractal": { "octaves": 1, "frequency": 0.5, "cached": true } },
+                    "scale_a": { "scale": { "scale": 2, "source": "const_a" } }
+                }"#;
+                match from_str(data) {
+                    Ok(mut x) => {
+                        assert!(x.get("fractal_a").is_some());
+                        assert!(x.get("fractal_a_cached").is_some());
+
+                        assert_eq!(x.sample_1d("scale_a", 1.0), 2.0);
+                    }
+                    Err(x) => panic!("{}", x),
+                }
+            }
+
+            #[test]
+            fn helper_to_string() {
+                let config = HashMap::from([
+                    ("const_a".to_owned(), TaskConfig::Constant(1.0)),
+                    (
+                        "scale_a".to_owned(),
+                        TaskConfig::Scale(ScaleConfig {
+                            scale: 2.0.into(),
+                            source: "const_a".to_owned().into(),
+                            cache: Some(CacheConfig::default()),
+                        }),
+                    ),
+                ]);
+
+                let data = to_string(&config).unwrap();
+                let result: HashMap<String, TaskConfig> = ::serde_json::from_str(&data).unwrap();
+                assert_eq!(result, config);
+                assert_eq!(from_str(data).unwrap().sample_1d("scale_a", 1.0), 2.0);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ron")]
+pub mod ron {
+    #[cfg(test)]
+    mod test {
+        mod f32 {
+            use std::collections::HashMap;
+
+            use crate::ser::f32::{
+                ron::from_str, ron::to_string, CacheConfig, ScaleConfig, TaskConfig,
+            };
+
+            #[test]
+            fn helper_from_str() {
+                let data = r#"{
+                    "const_a": constant(1.0),
+                    "fractal_a": fractal((octaves: 1, frequency: 0.5, cached: true)),
+                    "scale_a": scale((scale: 2.0, source: "const_a")),
+                }"#;
+                match from_str(data) {
+                    Ok(mut x) => {
+                        assert!(x.get("fractal_a").is_some());
+                        assert!(x.get("fractal_a_cached").is_some());
+
+                        assert_eq!(x.sample_1d("scale_a", 1.0), 2.0);
+                    }
+                    Err(x) => panic!("{}", x),
+                }
+            }
+
+            #[test]
+            fn helper_to_string() {
+                let config = HashMap::from([
+                    ("const_a".to_owned(), TaskConfig::Constant(1.0)),
+                    (
+                        "scale_a".to_owned(),
+                        TaskConfig::Scale(ScaleConfig {
+                            scale: 2.0.into(),
+                            source: "const_a".to_owned().into(),
+                            cache: Some(CacheConfig::default()),
+                        }),
+                    ),
+                ]);
+
+                let data = to_string(&config).unwrap();
+                let result: HashMap<String, TaskConfig> = ::ron::from_str(&data).unwrap();
+                assert_eq!(result, config);
+                assert_eq!(from_str(data).unwrap().sample_1d("scale_a", 1.0), 2.0);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "yaml")]
+pub mod yaml {
+    #[cfg(test)]
+    mod test {
+        mod f32 {
+            use std::collections::HashMap;
+
+            use crate::ser::f32::{
+                yaml::from_str, yaml::to_string, CacheConfig, ScaleConfig, TaskConfig,
+            };
+
+            #[test]
+            fn helper_from_str() {
+                let data = r#"
+                const_a: !constant 1.0
+                fractal_a: !fractal
+                  octaves: 1
+                  frequency: 0.5
+                  cached: true
+                scale_a: !scale
+                  scale: 2
+                  source: const_a
+                "#;
+                match from_str(data) {
+                    Ok(mut x) => {
+                        assert!(x.get("fractal_a").is_some());
+                        assert!(x.get("fractal_a_cached").is_some());
+
+                        assert_eq!(x.sample_1d("scale_a", 1.0), 2.0);
+                    }
+                    Err(x) => panic!("{}", x),
+                }
+            }
+
+            #[test]
+            fn helper_to_string() {
+                let config = HashMap::from([
+                    ("const_a".to_owned(), TaskConfig::Constant(1.0)),
+                    (
+                        "scale_a".to_owned(),
+                        TaskConfig::Scale(ScaleConfig {
+                            scale: 2.0.into(),
+                            source: "const_a".to_owned().into(),
+                            cache: Some(CacheConfig::default()),
+                        }),
+                    ),
+                ]);
+
+                let data = to_string(&config).unwrap();
+                let result: HashMap<String, TaskConfig> = ::serde_yaml::from_str(&data).unwrap();
+                assert_eq!(result, config);
+                assert_eq!(from_str(data).unwrap().sample_1d("scale_a", 1.0), 2.0);
+            }
         }
     }
 }
@@ -0,0 +1,166 @@
+macro_rules! pow_config {
+    () => {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct PowConfig {
+            pub exponent: NameOrConst,
+            pub source: NameOrConst,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        impl Default for PowConfig {
+            fn default() -> Self {
+                Self {
+                    exponent: 1.0.into(),
+                    source: 0.0.into(),
+                    cache: None,
+                }
+            }
+        }
+
+        impl TaskDependencies for PowConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                push_named_to_vec!(r, self.exponent);
+                push_named_to_vec!(r, self.source);
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.exponent, rename);
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for PowConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.exponent, format!("{}.exponent", name));
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
+        }
+
+        impl IntoTaskSource for PowConfig {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
+                let mut builder = PowBuilder::new();
+
+                add_task_to_builder!(self.exponent, builder, exponent, named_exponent, tree);
+                add_task_to_builder!(self.source, builder, source, named_source, tree);
+
+                Ok(builder.link(tree).build().into())
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{PowBuilder, TaskSource, TaskTree};
+    pow_config!();
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{PowBuilder, TaskSource, TaskTree};
+    pow_config!();
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use std::collections::HashMap;
+
+        use crate::ser::f32::{CacheConfig, PowConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [pow_a]
+                pow.source = "other"
+                pow.exponent = 2
+                pow.cached = true
+
+                [pow_b]
+                pow = { exponent = "other", source = 0.5 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["pow_a"],
+                TaskConfig::Pow(PowConfig {
+                    exponent: 2.0.into(),
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["pow_b"],
+                TaskConfig::Pow(PowConfig {
+                    exponent: "other".to_owned().into(),
+                    source: 0.5.into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+
+    mod f64 {
+        use std::collections::HashMap;
+
+        use crate::ser::f64::{CacheConfig, PowConfig, TaskConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [pow_a]
+                pow.source = "other"
+                pow.exponent = 2
+                pow.cached = true
+
+                [pow_b]
+                pow = { exponent = "other", source = 0.5 }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["pow_a"],
+                TaskConfig::Pow(PowConfig {
+                    exponent: 2.0.into(),
+                    source: "other".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["pow_b"],
+                TaskConfig::Pow(PowConfig {
+                    exponent: "other".to_owned().into(),
+                    source: 0.5.into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+}
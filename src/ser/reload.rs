@@ -0,0 +1,367 @@
+macro_rules! reload {
+    () => {
+        use std::collections::{HashMap, HashSet};
+        use std::path::{Path, PathBuf};
+        use std::time::SystemTime;
+
+        /// A [`TaskTree`] built from a config file that can be reloaded while it is in use.
+        ///
+        /// Reloading only rebuilds the tasks whose config changed and the tasks depending on
+        /// them, every other task keeps its state, including the values of parameters. If the
+        /// new config fails to load the previous tree is kept.
+        pub struct ReloadableTree {
+            path: PathBuf,
+            parse: fn(&str) -> Result<ConfigFile, String>,
+            files: Vec<(PathBuf, Option<SystemTime>)>,
+            config: HashMap<String, TaskConfig>,
+            share: bool,
+            tree: Box<TaskTree>,
+        }
+
+        fn modified(path: &Path) -> Option<SystemTime> {
+            std::fs::metadata(path).and_then(|x| x.modified()).ok()
+        }
+
+        impl ReloadableTree {
+            pub(crate) fn load(
+                path: &Path,
+                parse: fn(&str) -> Result<ConfigFile, String>,
+            ) -> Result<Self, String> {
+                let mut result = Self {
+                    path: path.to_owned(),
+                    parse,
+                    files: vec![],
+                    config: HashMap::new(),
+                    share: false,
+                    tree: Box::new(TaskTree::new()),
+                };
+                result.reload()?;
+                Ok(result)
+            }
+
+            /// The path of the config file.
+            pub fn path(&self) -> &Path {
+                &self.path
+            }
+
+            pub fn tree(&self) -> &TaskTree {
+                &self.tree
+            }
+
+            pub fn tree_mut(&mut self) -> &mut TaskTree {
+                &mut self.tree
+            }
+
+            /// Returns true if the config file or one of the files it includes has been modified
+            /// since the last reload.
+            pub fn changed(&self) -> bool {
+                self.files
+                    .iter()
+                    .any(|(path, time)| modified(path) != *time)
+            }
+
+            /// Reloads the config if [`ReloadableTree::changed`], see [`ReloadableTree::reload`].
+            pub fn reload_if_changed(&mut self) -> Result<Vec<String>, String> {
+                if self.changed() {
+                    self.reload()
+                } else {
+                    Ok(vec![])
+                }
+            }
+
+            /// Reads the config file again and rebuilds the tasks that changed, returning their
+            /// names.
+            ///
+            /// On error the current tree is kept and the error is only reported again once the
+            /// files change.
+            pub fn reload(&mut self) -> Result<Vec<String>, String> {
+                for (path, time) in self.files.iter_mut() {
+                    *time = modified(path);
+                }
+
+                let mut paths = vec![];
+                let file = ConfigFile::load_tracked(&self.path, &self.parse, &mut paths);
+
+                // On error the files read so far are tracked too, so fixing them triggers a reload
+                if file.is_ok() {
+                    self.files.clear();
+                }
+                for path in paths {
+                    if !self.files.iter().any(|(x, _)| *x == path) {
+                        let time = modified(&path);
+                        self.files.push((path, time));
+                    }
+                }
+
+                let file = file?;
+                let share = file.share;
+                let mut shared = share.then(SharedTasks::default);
+                let mut config = file.into_tasks()?;
+                let sorted_tasks = sort_tasks(&mut config)?;
+                check_custom_tasks(&config)?;
+
+                let mut tree = Box::new(TaskTree::new());
+                let mut rebuilt: HashSet<&str> = HashSet::new();
+                for task_name in sorted_tasks.iter() {
+                    let task = &config[task_name];
+                    let original = shared.as_mut().and_then(|x| x.find(task_name, task));
+                    // Turning sharing on or off changes which tasks are aliases, so all are rebuilt
+                    let unchanged = self.share == share
+                        && self.config.get(task_name) == Some(task)
+                        && !task
+                            .dependencies()
+                            .iter()
+                            .any(|x| rebuilt.contains(x.as_str()));
+
+                    let cached_name = format!("{}_cached", task_name);
+                    match (original, unchanged, self.tree.get(task_name)) {
+                        // Shared tasks follow their original, even when it is the one rebuilt
+                        (Some(original), _, old) => {
+                            share_config_task(&mut tree, task_name, &original);
+                            let new = tree.get(task_name);
+                            if !unchanged || !matches!((old, new), (Some(x), Some(y)) if x.ptr_eq(y))
+                            {
+                                rebuilt.insert(task_name);
+                            }
+                        }
+                        (None, true, Some(old)) => {
+                            tree.add_task(task_name, old.clone());
+                            if let Some(old) = self.tree.get(&cached_name) {
+                                tree.add_task(cached_name, old.clone());
+                            }
+                        }
+                        _ => {
+                            add_config_task(&mut tree, task_name, task)?;
+                            rebuilt.insert(task_name);
+                        }
+                    }
+                }
+
+                let mut rebuilt: Vec<String> = rebuilt.into_iter().map(|x| x.to_owned()).collect();
+                rebuilt.sort();
+
+                self.tree = tree;
+                self.config = config;
+                self.share = share;
+                Ok(rebuilt)
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        add_config_task, check_custom_tasks, share_config_task, sort_tasks, ConfigFile,
+        SharedTasks, TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::TaskTree;
+    reload!();
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        add_config_task, check_custom_tasks, share_config_task, sort_tasks, ConfigFile,
+        SharedTasks, TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::TaskTree;
+    reload!();
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, SystemTime};
+
+    /// Writes `files` into a fresh directory under the system temp directory
+    fn write_files(dir: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ferro_noise_{}", dir));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, data) in files {
+            std::fs::write(dir.join(name), data).unwrap();
+        }
+        dir
+    }
+
+    /// Replaces the contents of `path`, moving its modification time forward so the change is
+    /// seen regardless of the file system's timestamp resolution
+    fn touch(path: &Path, data: &str) {
+        std::fs::write(path, data).unwrap();
+        let time = SystemTime::now() + Duration::from_secs(10);
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(time).unwrap();
+    }
+
+    const CONFIG: &str = r#"
+    [params]
+    height = 0.5
+
+    [a]
+    constant = 1.0
+
+    [b]
+    fractal = { octaves = 2 }
+
+    [c]
+    scale = { source = "a", scale = "height" }
+
+    [d]
+    scale = { source = "b", scale = 2.0, cached = true }
+    "#;
+
+    mod f32 {
+        use super::{touch, write_files, CONFIG};
+        use crate::ser::f32::toml::reloadable;
+        use crate::ser::f32::ReloadableTree;
+        use crate::task::f32::TaskSource;
+        use std::rc::Rc;
+
+        #[test]
+        fn reload_rebuilds_changed_tasks() {
+            let dir = write_files(
+                "reload_rebuilds_changed_tasks_f32",
+                &[("main.toml", CONFIG)],
+            );
+            let mut result = reloadable(dir.join("main.toml")).unwrap();
+
+            let fractal = match result.tree().get("b") {
+                Some(TaskSource::Fractal(x)) => x.clone(),
+                _ => panic!("Task 'b' isn't a fractal"),
+            };
+            let d = result.tree_mut().sample_2d("d", 0.5, 0.25);
+            assert!(result.tree_mut().set_param("height", 0.25));
+
+            std::fs::write(dir.join("main.toml"), CONFIG.replace("1.0", "2.0")).unwrap();
+            assert_eq!(result.reload(), Ok(vec!["a".to_owned(), "c".to_owned()]));
+
+            let tree = result.tree_mut();
+            assert_eq!(tree.param("height"), Some(0.25));
+            assert_eq!(tree.sample_1d("c", 1.0), 0.5);
+            assert_eq!(tree.sample_2d("d", 0.5, 0.25), d);
+            assert!(tree.get("d_cached").is_some());
+            assert!(
+                matches!(tree.get("b"), Some(TaskSource::Fractal(x)) if Rc::ptr_eq(x, &fractal))
+            );
+
+            let config = CONFIG.replace("octaves = 2", "octaves = 3");
+            std::fs::write(dir.join("main.toml"), config).unwrap();
+            assert_eq!(
+                result.reload(),
+                Ok(vec![
+                    "a".to_owned(),
+                    "b".to_owned(),
+                    "c".to_owned(),
+                    "d".to_owned()
+                ])
+            );
+            assert_eq!(result.tree().param("height"), Some(0.25));
+        }
+
+        #[test]
+        fn reload_keeps_tree_on_error() {
+            let dir = write_files("reload_keeps_tree_on_error_f32", &[("main.toml", CONFIG)]);
+            let mut result = reloadable(dir.join("main.toml")).unwrap();
+
+            touch(&dir.join("main.toml"), "[a\nconstant = 2.0");
+            assert!(result.reload_if_changed().is_err());
+            assert_eq!(result.tree_mut().sample_1d("c", 1.0), 0.5);
+            assert!(!result.changed());
+
+            touch(&dir.join("main.toml"), &CONFIG.replace("\"a\"", "\"e\""));
+            assert!(
+                matches!(result.reload_if_changed(), Err(x) if x.contains("'e' doesn't exist"))
+            );
+            assert_eq!(result.tree_mut().sample_1d("c", 1.0), 0.5);
+
+            // Only the last successful load counts as the previous config
+            touch(&dir.join("main.toml"), CONFIG);
+            assert_eq!(result.reload_if_changed(), Ok(vec![]));
+            assert_eq!(result.tree_mut().sample_1d("c", 1.0), 0.5);
+        }
+
+        #[test]
+        fn reload_shares_identical_tasks() {
+            let config =
+                "share = true\n[a]\nfractal = { octaves = 2 }\n[b]\nfractal = { octaves = 2 }";
+            let dir = write_files(
+                "reload_shares_identical_tasks_f32",
+                &[("main.toml", config)],
+            );
+            let mut result = reloadable(dir.join("main.toml")).unwrap();
+            let shared = |x: &ReloadableTree| {
+                let tree = x.tree();
+                tree.get("a").unwrap().ptr_eq(tree.get("b").unwrap())
+            };
+            assert!(shared(&result));
+
+            let changed = config.replacen("octaves = 2", "octaves = 3", 1);
+            std::fs::write(dir.join("main.toml"), changed).unwrap();
+            assert_eq!(result.reload(), Ok(vec!["a".to_owned()]));
+            assert!(!shared(&result));
+
+            // Either task can end up as the original, the other one is rebuilt to share it
+            std::fs::write(dir.join("main.toml"), config).unwrap();
+            assert!(matches!(result.reload(), Ok(x) if x.contains(&"a".to_owned())));
+            assert!(shared(&result));
+
+            // Turning sharing off gives each name its own task again
+            let unshared = config.replace("share = true", "share = false");
+            std::fs::write(dir.join("main.toml"), unshared).unwrap();
+            assert!(matches!(result.reload(), Ok(x) if x.len() == 2));
+            assert!(!shared(&result));
+        }
+
+        #[test]
+        fn reload_if_changed() {
+            let dir = write_files(
+                "reload_if_changed_f32",
+                &[
+                    (
+                        "main.toml",
+                        "include = [\"biomes.toml\"]\n[a]\nconstant = 1.0",
+                    ),
+                    ("biomes.toml", "[hills]\nconstant = 0.5"),
+                ],
+            );
+            let mut result = reloadable(dir.join("main.toml")).unwrap();
+
+            assert!(!result.changed());
+            assert_eq!(result.reload_if_changed(), Ok(vec![]));
+
+            touch(&dir.join("biomes.toml"), "[hills]\nconstant = 0.25");
+            assert!(result.changed());
+            assert_eq!(
+                result.reload_if_changed(),
+                Ok(vec!["biomes.hills".to_owned()])
+            );
+            assert_eq!(result.tree_mut().sample_1d("biomes.hills", 1.0), 0.25);
+            assert!(!result.changed());
+        }
+    }
+
+    mod f64 {
+        use super::{write_files, CONFIG};
+        use crate::ser::f64::toml::reloadable;
+
+        #[test]
+        fn reload_rebuilds_changed_tasks() {
+            let dir = write_files(
+                "reload_rebuilds_changed_tasks_f64",
+                &[("main.toml", CONFIG)],
+            );
+            let mut result = reloadable(dir.join("main.toml")).unwrap();
+            assert_eq!(result.tree_mut().sample_1d("c", 1.0), 0.5);
+
+            std::fs::write(dir.join("main.toml"), CONFIG.replace("0.5", "0.25")).unwrap();
+            assert_eq!(
+                result.reload(),
+                Ok(vec!["c".to_owned(), "height".to_owned()])
+            );
+            assert_eq!(result.tree_mut().sample_1d("c", 1.0), 0.25);
+
+            std::fs::write(dir.join("main.toml"), "[c\n").unwrap();
+            assert!(result.reload().is_err());
+            assert_eq!(result.tree_mut().sample_1d("c", 1.0), 0.25);
+        }
+    }
+}
@@ -7,7 +7,13 @@ macro_rules! scale_config {
         pub struct ScaleConfig {
             pub scale: NameOrConst,
             pub source: NameOrConst,
-            pub cached: bool,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
         }
 
         impl Default for ScaleConfig {
@@ -15,7 +21,7 @@ macro_rules! scale_config {
                 Self {
                     scale: 1.0.into(),
                     source: 0.0.into(),
-                    cached: false,
+                    cache: None,
                 }
             }
         }
@@ -27,16 +33,30 @@ macro_rules! scale_config {
                 push_named_to_vec!(r, self.source);
                 r
             }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.scale, rename);
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for ScaleConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.scale, format!("{}.scale", name));
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
         }
 
         impl IntoTaskSource for ScaleConfig {
-            fn config_into(&self, tree: &TaskTree) -> TaskSource {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
                 let mut builder = ScaleBuilder::new();
 
                 add_task_to_builder!(self.source, builder, source, named_source, tree);
                 add_task_to_builder!(self.scale, builder, scale, named_scale, tree);
 
-                builder.link(tree).build().into()
+                Ok(builder.link(tree).build().into())
             }
         }
     };
@@ -44,7 +64,9 @@ macro_rules! scale_config {
 
 pub mod f32 {
     use crate::ser::f32::{
-        add_task_to_builder, push_named_to_vec, IntoTaskSource, NameOrConst, TaskDependencies,
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
     };
     use crate::task::f32::{ScaleBuilder, TaskSource, TaskTree};
     scale_config!();
@@ -52,7 +74,9 @@ pub mod f32 {
 
 pub mod f64 {
     use crate::ser::f64::{
-        add_task_to_builder, push_named_to_vec, IntoTaskSource, NameOrConst, TaskDependencies,
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
     };
     use crate::task::f64::{ScaleBuilder, TaskSource, TaskTree};
     scale_config!();
@@ -63,7 +87,7 @@ mod test {
     mod f32 {
         use std::collections::HashMap;
 
-        use crate::ser::f32::{ScaleConfig, TaskConfig};
+        use crate::ser::f32::{CacheConfig, ScaleConfig, TaskConfig};
 
         #[test]
         fn deserialize() {
@@ -85,8 +109,7 @@ mod test {
                 TaskConfig::Scale(ScaleConfig {
                     source: "other".to_owned().into(),
                     scale: 1.0.into(),
-                    cached: true,
-                    ..Default::default()
+                    cache: Some(CacheConfig::default()),
                 })
             );
 
@@ -104,7 +127,7 @@ mod test {
     mod f64 {
         use std::collections::HashMap;
 
-        use crate::ser::f64::{ScaleConfig, TaskConfig};
+        use crate::ser::f64::{CacheConfig, ScaleConfig, TaskConfig};
 
         #[test]
         fn deserialize() {
@@ -126,8 +149,7 @@ mod test {
                 TaskConfig::Scale(ScaleConfig {
                     source: "other".to_owned().into(),
                     scale: 1.0.into(),
-                    cached: true,
-                    ..Default::default()
+                    cache: Some(CacheConfig::default()),
                 })
             );
 
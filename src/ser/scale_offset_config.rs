@@ -8,7 +8,13 @@ macro_rules! scale_offset_config {
             pub offset: NameOrConst,
             pub scale: NameOrConst,
             pub source: NameOrConst,
-            pub cached: bool,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
         }
 
         impl Default for ScaleOffsetConfig {
@@ -17,7 +23,7 @@ macro_rules! scale_offset_config {
                     offset: 0.0.into(),
                     scale: 1.0.into(),
                     source: 0.0.into(),
-                    cached: false,
+                    cache: None,
                 }
             }
         }
@@ -30,17 +36,33 @@ macro_rules! scale_offset_config {
                 push_named_to_vec!(r, self.source);
                 r
             }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.offset, rename);
+                rename_named!(self.scale, rename);
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for ScaleOffsetConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.offset, format!("{}.offset", name));
+                push_inline_to_vec!(r, self.scale, format!("{}.scale", name));
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
         }
 
         impl IntoTaskSource for ScaleOffsetConfig {
-            fn config_into(&self, tree: &TaskTree) -> TaskSource {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
                 let mut builder = ScaleOffsetBuilder::new();
 
                 add_task_to_builder!(self.offset, builder, offset, named_offset, tree);
                 add_task_to_builder!(self.source, builder, source, named_source, tree);
                 add_task_to_builder!(self.scale, builder, scale, named_scale, tree);
 
-                builder.link(tree).build().into()
+                Ok(builder.link(tree).build().into())
             }
         }
     };
@@ -48,7 +70,9 @@ macro_rules! scale_offset_config {
 
 pub mod f32 {
     use crate::ser::f32::{
-        add_task_to_builder, push_named_to_vec, IntoTaskSource, NameOrConst, TaskDependencies,
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
     };
     use crate::task::f32::{ScaleOffsetBuilder, TaskSource, TaskTree};
     scale_offset_config!();
@@ -56,7 +80,9 @@ pub mod f32 {
 
 pub mod f64 {
     use crate::ser::f64::{
-        add_task_to_builder, push_named_to_vec, IntoTaskSource, NameOrConst, TaskDependencies,
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
     };
     use crate::task::f64::{ScaleOffsetBuilder, TaskSource, TaskTree};
     scale_offset_config!();
@@ -67,7 +93,7 @@ mod test {
     mod f32 {
         use std::collections::HashMap;
 
-        use crate::ser::f32::{ScaleOffsetConfig, TaskConfig};
+        use crate::ser::f32::{CacheConfig, ScaleOffsetConfig, TaskConfig};
 
         #[test]
         fn deserialize() {
@@ -91,8 +117,7 @@ mod test {
                     source: "other".to_owned().into(),
                     scale: 1.0.into(),
                     offset: 2.0.into(),
-                    cached: true,
-                    ..Default::default()
+                    cache: Some(CacheConfig::default()),
                 })
             );
 
@@ -110,7 +135,7 @@ mod test {
     mod f64 {
         use std::collections::HashMap;
 
-        use crate::ser::f64::{ScaleOffsetConfig, TaskConfig};
+        use crate::ser::f64::{CacheConfig, ScaleOffsetConfig, TaskConfig};
 
         #[test]
         fn deserialize() {
@@ -134,8 +159,7 @@ mod test {
                     source: "other".to_owned().into(),
                     scale: 1.0.into(),
                     offset: 2.0.into(),
-                    cached: true,
-                    ..Default::default()
+                    cache: Some(CacheConfig::default()),
                 })
             );
 
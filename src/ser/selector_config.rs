@@ -14,7 +14,13 @@ macro_rules! selector_config {
             pub falloff: NameOrConst,
             /// threadhold/pivot/boundry to determine when lower or upper is used
             pub threshold: NameOrConst,
-            pub cached: bool,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
         }
 
         impl Default for SelectorConfig {
@@ -26,7 +32,7 @@ macro_rules! selector_config {
                     upper: 1.0.into(),
                     falloff: 0.0.into(),
                     threshold: 0.0.into(),
-                    cached: false,
+                    cache: None,
                 }
             }
         }
@@ -41,10 +47,30 @@ macro_rules! selector_config {
                 push_named_to_vec!(r, self.threshold);
                 r
             }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.condition, rename);
+                rename_named!(self.lower, rename);
+                rename_named!(self.upper, rename);
+                rename_named!(self.falloff, rename);
+                rename_named!(self.threshold, rename);
+            }
+        }
+
+        impl InlineTasks for SelectorConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.condition, format!("{}.condition", name));
+                push_inline_to_vec!(r, self.lower, format!("{}.lower", name));
+                push_inline_to_vec!(r, self.upper, format!("{}.upper", name));
+                push_inline_to_vec!(r, self.falloff, format!("{}.falloff", name));
+                push_inline_to_vec!(r, self.threshold, format!("{}.threshold", name));
+                r
+            }
         }
 
         impl IntoTaskSource for SelectorConfig {
-            fn config_into(&self, tree: &TaskTree) -> TaskSource {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
                 let mut builder = SelectorBuilder::new();
 
                 let blender: Blender = match self.interp {
@@ -60,7 +86,7 @@ macro_rules! selector_config {
                 add_task_to_builder!(self.falloff, builder, falloff, named_falloff, tree);
                 add_task_to_builder!(self.threshold, builder, threshold, named_threshold, tree);
 
-                builder.link(tree).build().into()
+                Ok(builder.link(tree).build().into())
             }
         }
     };
@@ -69,8 +95,9 @@ macro_rules! selector_config {
 pub mod f32 {
     use crate::math::f32 as math;
     use crate::ser::f32::{
-        add_task_to_builder, push_named_to_vec, FractalBlender, IntoTaskSource, NameOrConst,
-        TaskDependencies,
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, FractalBlender, InlineTasks, IntoTaskSource,
+        NameOrConst, TaskConfig, TaskDependencies,
     };
     use crate::source::f32::Blender;
     use crate::task::f32::{SelectorBuilder, TaskSource, TaskTree};
@@ -81,8 +108,9 @@ pub mod f32 {
 pub mod f64 {
     use crate::math::f64 as math;
     use crate::ser::f64::{
-        add_task_to_builder, push_named_to_vec, FractalBlender, IntoTaskSource, NameOrConst,
-        TaskDependencies,
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, FractalBlender, InlineTasks, IntoTaskSource,
+        NameOrConst, TaskConfig, TaskDependencies,
     };
     use crate::source::f64::Blender;
     use crate::task::f64::{SelectorBuilder, TaskSource, TaskTree};
@@ -93,7 +121,7 @@ pub mod f64 {
 #[cfg(test)]
 mod test {
     mod f32 {
-        use crate::ser::f32::{FractalBlender, SelectorConfig, TaskConfig};
+        use crate::ser::f32::{CacheConfig, FractalBlender, SelectorConfig, TaskConfig};
         use std::collections::HashMap;
 
         #[test]
@@ -118,7 +146,7 @@ mod test {
                     interp: FractalBlender::Linear,
                     condition: (-1.0).into(),
                     upper: 2.0.into(),
-                    cached: true,
+                    cache: Some(CacheConfig::default()),
                     ..Default::default()
                 })
             );
@@ -136,7 +164,7 @@ mod test {
     }
 
     mod f64 {
-        use crate::ser::f64::{FractalBlender, SelectorConfig, TaskConfig};
+        use crate::ser::f64::{CacheConfig, FractalBlender, SelectorConfig, TaskConfig};
         use std::collections::HashMap;
 
         #[test]
@@ -161,7 +189,7 @@ mod test {
                     interp: FractalBlender::Linear,
                     condition: (-1.0).into(),
                     upper: 2.0.into(),
-                    cached: true,
+                    cache: Some(CacheConfig::default()),
                     ..Default::default()
                 })
             );
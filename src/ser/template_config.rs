@@ -0,0 +1,351 @@
+macro_rules! template_config {
+    () => {
+        use serde::{Deserialize, Serialize};
+        use std::collections::{BTreeMap, HashMap};
+
+        /// A reusable graph fragment, instantiated by name through a `template` task.
+        ///
+        /// Every task of the fragment is added to the tree as `<instance>.<task>`, except
+        /// `output` which takes the name of the instance itself. Names that are not part of the
+        /// fragment refer to tasks outside of it.
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+        #[serde(default)]
+        pub struct TemplateConfig {
+            pub output: String,
+            pub tasks: HashMap<String, TaskConfig>,
+        }
+
+        impl Default for TemplateConfig {
+            fn default() -> Self {
+                Self {
+                    output: "output".to_owned(),
+                    tasks: HashMap::new(),
+                }
+            }
+        }
+
+        impl TemplateConfig {
+            /// Creates the tasks of an instance named `instance`.
+            ///
+            /// `overrides` replaces fragment tasks by name, either with a constant, an inline
+            /// task or a reference to a task outside of the fragment.
+            pub(crate) fn instantiate(
+                &self,
+                instance: &str,
+                overrides: &BTreeMap<String, NameOrConst>,
+            ) -> Result<HashMap<String, TaskConfig>, String> {
+                if !self.tasks.contains_key(&self.output) {
+                    return Err(format!("Template output '{}' doesn't exist", self.output));
+                }
+
+                if let Some(name) = overrides.keys().find(|x| !self.tasks.contains_key(*x)) {
+                    return Err(format!("Template has no task '{}' to override", name));
+                }
+
+                let rename = |name: &str| -> Option<String> {
+                    match overrides.get(name) {
+                        Some(NameOrConst::Named(x)) => Some(x.clone()),
+                        _ if name == self.output => Some(instance.to_owned()),
+                        _ if self.tasks.contains_key(name) => {
+                            Some(format!("{}.{}", instance, name))
+                        }
+                        _ => None,
+                    }
+                };
+
+                let mut result = HashMap::with_capacity(self.tasks.len());
+                for (name, task) in self.tasks.iter() {
+                    let task = match overrides.get(name) {
+                        Some(NameOrConst::Named(_)) if *name == self.output => {
+                            return Err(format!(
+                                "Template output '{}' can't be replaced by a named task",
+                                name
+                            ));
+                        }
+                        Some(NameOrConst::Named(_)) => continue,
+                        Some(NameOrConst::Value(x)) => TaskConfig::Constant(*x),
+                        Some(NameOrConst::Inline(x)) => (**x).clone(),
+                        None => {
+                            let mut task = task.clone();
+                            task.rename_dependencies(&rename);
+                            task
+                        }
+                    };
+                    result.insert(rename(name).unwrap_or_else(|| name.clone()), task);
+                }
+
+                Ok(result)
+            }
+        }
+
+        /// Instance of a [`TemplateConfig`], replaced by the template's tasks before sorting.
+        #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct TemplateInstanceConfig {
+            pub name: String,
+            #[serde(alias = "with")]
+            pub overrides: BTreeMap<String, NameOrConst>,
+        }
+
+        impl TaskDependencies for TemplateInstanceConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                for value in self.overrides.values() {
+                    push_named_to_vec!(r, value);
+                }
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                for value in self.overrides.values_mut() {
+                    rename_named!(*value, rename);
+                }
+            }
+        }
+
+        impl InlineTasks for TemplateInstanceConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                for (key, value) in self.overrides.iter_mut() {
+                    push_inline_to_vec!(r, *value, format!("{}.{}", name, key));
+                }
+                r
+            }
+        }
+
+        /// Only the tasks an instance expands to can be built, see [`expand_templates`].
+        impl IntoTaskSource for TemplateInstanceConfig {
+            fn config_into(&self, _: &TaskTree) -> Result<TaskSource, String> {
+                Err(format!(
+                    "Template instance of '{}' must be expanded before it is built",
+                    self.name
+                ))
+            }
+        }
+
+        /// Replaces every template instance in `tasks` with the tasks of its template.
+        pub(crate) fn expand_templates(
+            tasks: &mut HashMap<String, TaskConfig>,
+            templates: &HashMap<String, TemplateConfig>,
+        ) -> Result<(), String> {
+            hoist_inline_tasks(tasks)?;
+
+            // Each pending instance carries the chain of templates that produced it
+            let mut pending: Vec<(String, Vec<String>)> = tasks
+                .iter()
+                .filter(|(_, x)| matches!(x, TaskConfig::Template(_)))
+                .map(|(name, _)| (name.clone(), vec![]))
+                .collect();
+
+            while let Some((name, mut chain)) = pending.pop() {
+                let instance = match tasks.remove(&name) {
+                    Some(TaskConfig::Template(x)) => x,
+                    _ => continue,
+                };
+
+                let template = templates
+                    .get(&instance.name)
+                    .ok_or_else(|| format!("Template '{}' doesn't exist", instance.name))?;
+
+                if chain.contains(&instance.name) {
+                    chain.push(instance.name);
+                    return Err(format!("Template cycle detected: {}", chain.join(" -> ")));
+                }
+                chain.push(instance.name.clone());
+
+                let mut expanded = template.instantiate(&name, &instance.overrides)?;
+                hoist_inline_tasks(&mut expanded)?;
+
+                for (task_name, task) in expanded {
+                    if tasks.contains_key(&task_name) {
+                        return Err(format!("Task '{}' is defined more than once", task_name));
+                    }
+                    if let TaskConfig::Template(_) = &task {
+                        pending.push((task_name.clone(), chain.clone()));
+                    }
+                    tasks.insert(task_name, task);
+                }
+            }
+
+            Ok(())
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        hoist_inline_tasks, push_inline_to_vec, push_named_to_vec, rename_named, InlineTasks,
+        IntoTaskSource, NameOrConst, TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{TaskSource, TaskTree};
+    template_config!();
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        hoist_inline_tasks, push_inline_to_vec, push_named_to_vec, rename_named, InlineTasks,
+        IntoTaskSource, NameOrConst, TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{TaskSource, TaskTree};
+    template_config!();
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use crate::ser::f32::toml::from_str;
+        use crate::ser::f32::{IntoTaskSource, TaskConfig, TemplateInstanceConfig};
+        use crate::task::f32::TaskTree;
+
+        const HILL: &str = r#"
+        [templates.hill]
+        output = "shape"
+        tasks.base = { constant = 0.5 }
+        tasks.height = { constant = 1.0 }
+        tasks.shape = { scale = { source = "base", scale = "height" } }
+        "#;
+
+        #[test]
+        fn instantiate() {
+            let data = format!(
+                r#"{}
+                [big]
+                template = {{ name = "hill", overrides = {{ height = 4.0 }} }}
+
+                [small]
+                template = {{ name = "hill" }}
+                "#,
+                HILL
+            );
+
+            match from_str(data) {
+                Ok(mut x) => {
+                    assert!(x.get("big.base").is_some());
+                    assert!(x.get("big.height").is_some());
+                    assert!(x.get("big.shape").is_none());
+                    assert!(x.get("small.base").is_some());
+
+                    assert_eq!(x.sample_1d("big", 1.0), 2.0);
+                    assert_eq!(x.sample_1d("small", 1.0), 0.5);
+                }
+                Err(x) => panic!("{}", x),
+            }
+        }
+
+        #[test]
+        fn instantiate_named_override() {
+            let data = format!(
+                r#"{}
+                [global]
+                constant = 3.0
+
+                [big]
+                template = {{ name = "hill", with = {{ height = "global", base = {{ constant = 2.0 }} }} }}
+                "#,
+                HILL
+            );
+
+            match from_str(data) {
+                Ok(mut x) => {
+                    assert!(x.get("big.height").is_none());
+                    assert_eq!(x.sample_1d("big", 1.0), 6.0);
+                }
+                Err(x) => panic!("{}", x),
+            }
+        }
+
+        #[test]
+        fn instantiate_inline() {
+            let data = format!(
+                r#"{}
+                [world]
+                scale = {{ scale = 2.0, source = {{ template = {{ name = "hill" }} }} }}
+                "#,
+                HILL
+            );
+
+            match from_str(data) {
+                Ok(mut x) => {
+                    assert!(x.get("world.source.base").is_some());
+                    assert_eq!(x.sample_1d("world", 1.0), 1.0);
+                }
+                Err(x) => panic!("{}", x),
+            }
+        }
+
+        #[test]
+        fn instantiate_errors() {
+            let unknown = r#"
+            [big]
+            template = { name = "hill" }
+            "#;
+            assert!(matches!(from_str(unknown), Err(x) if x.contains("'hill' doesn't exist")));
+
+            let bad_override = format!(
+                "{}\n[big]\ntemplate = {{ name = \"hill\", overrides = {{ depth = 1.0 }} }}",
+                HILL
+            );
+            assert!(matches!(from_str(bad_override), Err(x) if x.contains("no task 'depth'")));
+
+            let collision = format!(
+                "{}\n[big]\ntemplate = {{ name = \"hill\" }}\n[\"big.base\"]\nconstant = 1.0",
+                HILL
+            );
+            assert!(
+                matches!(from_str(collision), Err(x) if x.contains("'big.base' is defined more than once"))
+            );
+
+            let cycle = r#"
+            [templates.a]
+            tasks.output = { template = { name = "b" } }
+
+            [templates.b]
+            tasks.output = { template = { name = "a" } }
+
+            [big]
+            template = { name = "a" }
+            "#;
+            assert!(
+                matches!(from_str(cycle), Err(x) if x.contains("Template cycle detected: a -> b -> a"))
+            );
+        }
+
+        #[test]
+        fn unexpanded_instance() {
+            let config = TaskConfig::Template(TemplateInstanceConfig {
+                name: "hill".to_owned(),
+                ..Default::default()
+            });
+            assert_eq!(
+                config.config_into(&TaskTree::new()).err(),
+                Some("Template instance of 'hill' must be expanded before it is built".to_owned())
+            );
+        }
+    }
+
+    mod f64 {
+        use crate::ser::f64::toml::from_str;
+
+        #[test]
+        fn instantiate() {
+            let data = r#"
+            [templates.hill]
+            output = "shape"
+            tasks.base = { constant = 0.5 }
+            tasks.height = { constant = 1.0 }
+            tasks.shape = { scale = { source = "base", scale = "height" } }
+
+            [big]
+            template = { name = "hill", overrides = { height = 4.0 } }
+            "#;
+
+            match from_str(data) {
+                Ok(mut x) => {
+                    assert!(x.get("big.base").is_some());
+                    assert_eq!(x.sample_1d("big", 1.0), 2.0);
+                }
+                Err(x) => panic!("{}", x),
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+macro_rules! terrace_config {
+    ($type: ty) => {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct TerraceConfig {
+            pub invert: bool,
+            pub levels: Vec<$type>,
+            pub smooth: bool,
+            pub source: NameOrConst,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        impl Default for TerraceConfig {
+            fn default() -> Self {
+                Self {
+                    invert: false,
+                    levels: vec![],
+                    smooth: true,
+                    source: 0.0.into(),
+                    cache: None,
+                }
+            }
+        }
+
+        impl TaskDependencies for TerraceConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                push_named_to_vec!(r, self.source);
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for TerraceConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
+        }
+
+        impl IntoTaskSource for TerraceConfig {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
+                let mut builder = TerraceBuilder::new();
+                builder
+                    .invert(self.invert)
+                    .levels(self.levels.clone())
+                    .smooth(self.smooth);
+
+                add_task_to_builder!(self.source, builder, source, named_source, tree);
+
+                Ok(builder.link(tree).build().into())
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{TaskSource, TaskTree, TerraceBuilder};
+    terrace_config!(f32);
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{TaskSource, TaskTree, TerraceBuilder};
+    terrace_config!(f64);
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use std::collections::HashMap;
+
+        use crate::ser::f32::{CacheConfig, TaskConfig, TerraceConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [terrace_a]
+                terrace.source = "height"
+                terrace.levels = [-1.0, -0.2, 0.4, 1.0]
+                terrace.smooth = false
+                terrace.invert = true
+                terrace.cached = true
+
+                [terrace_b]
+                terrace = { source = "height", levels = [0.0, 1.0] }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["terrace_a"],
+                TaskConfig::Terrace(TerraceConfig {
+                    invert: true,
+                    levels: vec![-1.0, -0.2, 0.4, 1.0],
+                    smooth: false,
+                    source: "height".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["terrace_b"],
+                TaskConfig::Terrace(TerraceConfig {
+                    levels: vec![0.0, 1.0],
+                    source: "height".to_owned().into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+
+    mod f64 {
+        use std::collections::HashMap;
+
+        use crate::ser::f64::{CacheConfig, TaskConfig, TerraceConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [terrace_a]
+                terrace.source = "height"
+                terrace.levels = [-1.0, -0.2, 0.4, 1.0]
+                terrace.smooth = false
+                terrace.invert = true
+                terrace.cached = true
+
+                [terrace_b]
+                terrace = { source = "height", levels = [0.0, 1.0] }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["terrace_a"],
+                TaskConfig::Terrace(TerraceConfig {
+                    invert: true,
+                    levels: vec![-1.0, -0.2, 0.4, 1.0],
+                    smooth: false,
+                    source: "height".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                })
+            );
+
+            assert_eq!(
+                config["terrace_b"],
+                TaskConfig::Terrace(TerraceConfig {
+                    levels: vec![0.0, 1.0],
+                    source: "height".to_owned().into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+}
@@ -2,6 +2,35 @@ macro_rules! transform_domain_config {
     ($type: ty) => {
         use serde::{Deserialize, Serialize};
 
+        /// Entry of `operations` in a transform domain.
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct DomainOperationConfig {
+            pub operation: DomainOperation,
+            pub dx: NameOrConst,
+            pub dy: NameOrConst,
+            pub dz: NameOrConst,
+            /// Rows of the matrix used by the affine operation
+            pub matrix: [[$type; 3]; 3],
+        }
+
+        impl Default for DomainOperationConfig {
+            fn default() -> Self {
+                Self {
+                    operation: DomainOperation::default(),
+                    dx: 0.0.into(),
+                    dy: 0.0.into(),
+                    dz: 0.0.into(),
+                    matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                }
+            }
+        }
+
+        /// Transforms the domain of `source` by `operation`, followed by every operation of
+        /// `operations` in order.
+        ///
+        /// The top-level operation is always applied, with its defaults it translates by zero
+        /// and leaves the domain unchanged.
         #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
         #[serde(default)]
         pub struct TransformDomainConfig {
@@ -9,8 +38,18 @@ macro_rules! transform_domain_config {
             pub dx: NameOrConst,
             pub dy: NameOrConst,
             pub dz: NameOrConst,
+            /// Rows of the matrix used by the affine operation
+            pub matrix: [[$type; 3]; 3],
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            pub operations: Vec<DomainOperationConfig>,
             pub source: NameOrConst,
-            pub cached: bool,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
         }
 
         impl Default for TransformDomainConfig {
@@ -20,8 +59,10 @@ macro_rules! transform_domain_config {
                     dx: 0.0.into(),
                     dy: 0.0.into(),
                     dz: 0.0.into(),
+                    matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                    operations: vec![],
                     source: 1.0.into(),
-                    cached: false,
+                    cache: None,
                 }
             }
         }
@@ -32,22 +73,64 @@ macro_rules! transform_domain_config {
                 push_named_to_vec!(r, self.dx);
                 push_named_to_vec!(r, self.dy);
                 push_named_to_vec!(r, self.dz);
+                for operation in self.operations.iter() {
+                    push_named_to_vec!(r, operation.dx);
+                    push_named_to_vec!(r, operation.dy);
+                    push_named_to_vec!(r, operation.dz);
+                }
                 push_named_to_vec!(r, self.source);
                 r
             }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.dx, rename);
+                rename_named!(self.dy, rename);
+                rename_named!(self.dz, rename);
+                for operation in self.operations.iter_mut() {
+                    rename_named!(operation.dx, rename);
+                    rename_named!(operation.dy, rename);
+                    rename_named!(operation.dz, rename);
+                }
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for TransformDomainConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.dx, format!("{}.dx", name));
+                push_inline_to_vec!(r, self.dy, format!("{}.dy", name));
+                push_inline_to_vec!(r, self.dz, format!("{}.dz", name));
+                for (i, operation) in self.operations.iter_mut().enumerate() {
+                    let prefix = format!("{}.operations.{}", name, i);
+                    push_inline_to_vec!(r, operation.dx, format!("{}.dx", prefix));
+                    push_inline_to_vec!(r, operation.dy, format!("{}.dy", prefix));
+                    push_inline_to_vec!(r, operation.dz, format!("{}.dz", prefix));
+                }
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
         }
 
         impl IntoTaskSource for TransformDomainConfig {
-            fn config_into(&self, tree: &TaskTree) -> TaskSource {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
                 let mut builder = TransformDomainBuilder::new();
 
-                builder.operation(self.operation);
+                builder.operation(self.operation).matrix(self.matrix);
                 add_task_to_builder!(self.dx, builder, value_x, named_value_x, tree);
                 add_task_to_builder!(self.dy, builder, value_y, named_value_y, tree);
                 add_task_to_builder!(self.dz, builder, value_z, named_value_z, tree);
+                for operation in self.operations.iter() {
+                    builder
+                        .add_operation(operation.operation)
+                        .matrix(operation.matrix);
+                    add_task_to_builder!(operation.dx, builder, value_x, named_value_x, tree);
+                    add_task_to_builder!(operation.dy, builder, value_y, named_value_y, tree);
+                    add_task_to_builder!(operation.dz, builder, value_z, named_value_z, tree);
+                }
                 add_task_to_builder!(self.source, builder, source, named_source, tree);
 
-                builder.link(tree).build().into()
+                Ok(builder.link(tree).build().into())
             }
         }
     };
@@ -55,7 +138,9 @@ macro_rules! transform_domain_config {
 
 pub mod f32 {
     use crate::ser::f32::{
-        add_task_to_builder, push_named_to_vec, IntoTaskSource, NameOrConst, TaskDependencies,
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
     };
     use crate::task::f32::{DomainOperation, TaskSource, TaskTree, TransformDomainBuilder};
     transform_domain_config!(f32);
@@ -63,7 +148,9 @@ pub mod f32 {
 
 pub mod f64 {
     use crate::ser::f64::{
-        add_task_to_builder, push_named_to_vec, IntoTaskSource, NameOrConst, TaskDependencies,
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
     };
     use crate::task::f64::{DomainOperation, TaskSource, TaskTree, TransformDomainBuilder};
     transform_domain_config!(f64);
@@ -72,9 +159,45 @@ pub mod f64 {
 #[cfg(test)]
 mod test {
     mod f32 {
-        use crate::ser::f32::{TaskConfig, TransformDomainConfig};
+        use crate::ser::f32::{
+            toml::from_str, CacheConfig, DomainOperationConfig, TaskConfig, TransformDomainConfig,
+        };
+        use crate::task::f32::{DomainOperation, TaskSource};
         use std::collections::HashMap;
 
+        #[test]
+        fn helper_from_str_operations() {
+            let data = r#"
+            [moved]
+            transform_domain = { dx = 1, operations = [{ operation = "scale", dx = 2 }] }
+
+            [scaled]
+            transform_domain.operations = [{ operation = "scale", dx = 2 }]
+            "#;
+            let tree = from_str(data).unwrap();
+            let operations = |name: &str| match tree.get(name) {
+                Some(TaskSource::Domain(x)) => x
+                    .borrow()
+                    .steps
+                    .iter()
+                    .map(|x| (x.operation, x.value[0].clone()))
+                    .collect::<Vec<_>>(),
+                _ => panic!("Task '{}' isn't a transform domain", name),
+            };
+
+            // The top-level operation always comes first, translating by zero by default
+            let [first, second] = [DomainOperation::Translate, DomainOperation::Scale];
+            let result = operations("moved");
+            assert_eq!(result.len(), 2);
+            assert!(matches!(result[0], (x, TaskSource::Constant(v)) if x == first && v == 1.0));
+            assert!(matches!(result[1], (x, TaskSource::Constant(v)) if x == second && v == 2.0));
+
+            let result = operations("scaled");
+            assert_eq!(result.len(), 2);
+            assert!(matches!(result[0], (x, TaskSource::Constant(v)) if x == first && v == 0.0));
+            assert!(matches!(result[1], (x, TaskSource::Constant(v)) if x == second && v == 2.0));
+        }
+
         #[test]
         fn deserialize() {
             let data = toml::to_string(&toml::toml! {
@@ -86,18 +209,33 @@ mod test {
 
                 [transform_domain_b]
                 transform_domain = { dx = "dx" }
+
+                [transform_domain_c]
+                transform_domain.operation = "rotate"
+                transform_domain.dz = 30
+
+                [transform_domain_d]
+                transform_domain.operation = "affine"
+                transform_domain.matrix = [[1, 0.5, 0], [0, 1, 0], [0, 0, 2]]
+                transform_domain.dx = 4
+
+                [transform_domain_e]
+                transform_domain.operations = [
+                    { operation = "scale", dx = 2, dy = 2, dz = 2 },
+                    { operation = "warp", dx = "warp_x" },
+                ]
             })
             .unwrap();
             let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
 
-            assert_eq!(config.len(), 2);
+            assert_eq!(config.len(), 5);
             assert_eq!(
                 config["transform_domain_a"],
                 TaskConfig::TransformDomain(TransformDomainConfig {
                     dx: "dx".to_owned().into(),
                     dy: (-1.0).into(),
                     source: 2.0.into(),
-                    cached: true,
+                    cache: Some(CacheConfig::default()),
                     ..Default::default()
                 })
             );
@@ -109,11 +247,52 @@ mod test {
                     ..Default::default()
                 })
             );
+
+            assert_eq!(
+                config["transform_domain_c"],
+                TaskConfig::TransformDomain(TransformDomainConfig {
+                    operation: DomainOperation::Rotate,
+                    dz: 30.0.into(),
+                    ..Default::default()
+                })
+            );
+
+            assert_eq!(
+                config["transform_domain_d"],
+                TaskConfig::TransformDomain(TransformDomainConfig {
+                    operation: DomainOperation::Affine,
+                    matrix: [[1.0, 0.5, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 2.0]],
+                    dx: 4.0.into(),
+                    ..Default::default()
+                })
+            );
+
+            assert_eq!(
+                config["transform_domain_e"],
+                TaskConfig::TransformDomain(TransformDomainConfig {
+                    operations: vec![
+                        DomainOperationConfig {
+                            operation: DomainOperation::Scale,
+                            dx: 2.0.into(),
+                            dy: 2.0.into(),
+                            dz: 2.0.into(),
+                            ..Default::default()
+                        },
+                        DomainOperationConfig {
+                            operation: DomainOperation::Warp,
+                            dx: "warp_x".to_owned().into(),
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                })
+            );
         }
     }
 
     mod f64 {
-        use crate::ser::f64::{TaskConfig, TransformDomainConfig};
+        use crate::ser::f64::{DomainOperationConfig, TaskConfig, TransformDomainConfig};
+        use crate::task::f64::DomainOperation;
         use std::collections::HashMap;
 
         #[test]
@@ -126,11 +305,26 @@ mod test {
 
                 [transform_domain_b]
                 transform_domain = { dx = "dx" }
+
+                [transform_domain_c]
+                transform_domain.operation = "rotate"
+                transform_domain.dz = 30
+
+                [transform_domain_d]
+                transform_domain.operation = "affine"
+                transform_domain.matrix = [[1, 0.5, 0], [0, 1, 0], [0, 0, 2]]
+                transform_domain.dx = 4
+
+                [transform_domain_e]
+                transform_domain.operations = [
+                    { operation = "scale", dx = 2, dy = 2, dz = 2 },
+                    { operation = "warp", dx = "warp_x" },
+                ]
             })
             .unwrap();
             let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
 
-            assert_eq!(config.len(), 2);
+            assert_eq!(config.len(), 5);
             assert_eq!(
                 config["transform_domain_a"],
                 TaskConfig::TransformDomain(TransformDomainConfig {
@@ -148,6 +342,46 @@ mod test {
                     ..Default::default()
                 })
             );
+
+            assert_eq!(
+                config["transform_domain_c"],
+                TaskConfig::TransformDomain(TransformDomainConfig {
+                    operation: DomainOperation::Rotate,
+                    dz: 30.0.into(),
+                    ..Default::default()
+                })
+            );
+
+            assert_eq!(
+                config["transform_domain_d"],
+                TaskConfig::TransformDomain(TransformDomainConfig {
+                    operation: DomainOperation::Affine,
+                    matrix: [[1.0, 0.5, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 2.0]],
+                    dx: 4.0.into(),
+                    ..Default::default()
+                })
+            );
+
+            assert_eq!(
+                config["transform_domain_e"],
+                TaskConfig::TransformDomain(TransformDomainConfig {
+                    operations: vec![
+                        DomainOperationConfig {
+                            operation: DomainOperation::Scale,
+                            dx: 2.0.into(),
+                            dy: 2.0.into(),
+                            dz: 2.0.into(),
+                            ..Default::default()
+                        },
+                        DomainOperationConfig {
+                            operation: DomainOperation::Warp,
+                            dx: "warp_x".to_owned().into(),
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                })
+            );
         }
     }
 }
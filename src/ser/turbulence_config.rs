@@ -0,0 +1,209 @@
+macro_rules! turbulence_config {
+    () => {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+        #[serde(default)]
+        pub struct TurbulenceConfig {
+            pub dx: NameOrConst,
+            pub dy: NameOrConst,
+            pub dz: NameOrConst,
+            pub iterations: usize,
+            pub power: NameOrConst,
+            pub source: NameOrConst,
+            #[serde(
+                alias = "cached",
+                deserialize_with = "deserialize_cache",
+                serialize_with = "serialize_cache",
+                skip_serializing_if = "Option::is_none"
+            )]
+            pub cache: Option<CacheConfig>,
+        }
+
+        impl Default for TurbulenceConfig {
+            fn default() -> Self {
+                Self {
+                    dx: 0.0.into(),
+                    dy: 0.0.into(),
+                    dz: 0.0.into(),
+                    iterations: 1,
+                    power: 1.0.into(),
+                    source: 0.0.into(),
+                    cache: None,
+                }
+            }
+        }
+
+        impl TaskDependencies for TurbulenceConfig {
+            fn dependencies(&self) -> Vec<String> {
+                let mut r = vec![];
+                push_named_to_vec!(r, self.dx);
+                push_named_to_vec!(r, self.dy);
+                push_named_to_vec!(r, self.dz);
+                push_named_to_vec!(r, self.power);
+                push_named_to_vec!(r, self.source);
+                r
+            }
+
+            fn rename_dependencies(&mut self, rename: &dyn Fn(&str) -> Option<String>) {
+                rename_named!(self.dx, rename);
+                rename_named!(self.dy, rename);
+                rename_named!(self.dz, rename);
+                rename_named!(self.power, rename);
+                rename_named!(self.source, rename);
+            }
+        }
+
+        impl InlineTasks for TurbulenceConfig {
+            fn inline_tasks(&mut self, name: &str) -> Vec<(String, TaskConfig)> {
+                let mut r = vec![];
+                push_inline_to_vec!(r, self.dx, format!("{}.dx", name));
+                push_inline_to_vec!(r, self.dy, format!("{}.dy", name));
+                push_inline_to_vec!(r, self.dz, format!("{}.dz", name));
+                push_inline_to_vec!(r, self.power, format!("{}.power", name));
+                push_inline_to_vec!(r, self.source, format!("{}.source", name));
+                r
+            }
+        }
+
+        impl IntoTaskSource for TurbulenceConfig {
+            fn config_into(&self, tree: &TaskTree) -> Result<TaskSource, String> {
+                let mut builder = TurbulenceBuilder::new();
+
+                builder.iterations(self.iterations);
+                add_task_to_builder!(self.dx, builder, dx, named_dx, tree);
+                add_task_to_builder!(self.dy, builder, dy, named_dy, tree);
+                add_task_to_builder!(self.dz, builder, dz, named_dz, tree);
+                add_task_to_builder!(self.power, builder, power, named_power, tree);
+                add_task_to_builder!(self.source, builder, source, named_source, tree);
+
+                Ok(builder.link(tree).build().into())
+            }
+        }
+    };
+}
+
+pub mod f32 {
+    use crate::ser::f32::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f32::{TaskSource, TaskTree, TurbulenceBuilder};
+    turbulence_config!();
+}
+
+pub mod f64 {
+    use crate::ser::f64::{
+        add_task_to_builder, deserialize_cache, push_inline_to_vec, push_named_to_vec,
+        rename_named, serialize_cache, CacheConfig, InlineTasks, IntoTaskSource, NameOrConst,
+        TaskConfig, TaskDependencies,
+    };
+    use crate::task::f64::{TaskSource, TaskTree, TurbulenceBuilder};
+    turbulence_config!();
+}
+
+#[cfg(test)]
+mod test {
+    mod f32 {
+        use std::collections::HashMap;
+
+        use crate::ser::f32::{CacheConfig, FractalConfig, TaskConfig, TurbulenceConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [turbulence_a]
+                turbulence.source = "height"
+                turbulence.dx = "warp_x"
+                turbulence.dy = { fractal = { octaves = 3 } }
+                turbulence.power = 0.25
+                turbulence.iterations = 2
+                turbulence.cached = true
+
+                [turbulence_b]
+                turbulence = { source = "height", dx = "warp_x" }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["turbulence_a"],
+                TaskConfig::Turbulence(TurbulenceConfig {
+                    dx: "warp_x".to_owned().into(),
+                    dy: TaskConfig::Fractal(FractalConfig {
+                        octaves: 3,
+                        ..Default::default()
+                    })
+                    .into(),
+                    iterations: 2,
+                    power: 0.25.into(),
+                    source: "height".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                    ..Default::default()
+                })
+            );
+
+            assert_eq!(
+                config["turbulence_b"],
+                TaskConfig::Turbulence(TurbulenceConfig {
+                    dx: "warp_x".to_owned().into(),
+                    source: "height".to_owned().into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+
+    mod f64 {
+        use std::collections::HashMap;
+
+        use crate::ser::f64::{CacheConfig, FractalConfig, TaskConfig, TurbulenceConfig};
+
+        #[test]
+        fn deserialize() {
+            let data = toml::to_string(&toml::toml! {
+                [turbulence_a]
+                turbulence.source = "height"
+                turbulence.dx = "warp_x"
+                turbulence.dy = { fractal = { octaves = 3 } }
+                turbulence.power = 0.25
+                turbulence.iterations = 2
+                turbulence.cached = true
+
+                [turbulence_b]
+                turbulence = { source = "height", dx = "warp_x" }
+            })
+            .unwrap();
+            let config: HashMap<String, TaskConfig> = toml::from_str(data.as_str()).unwrap();
+
+            assert_eq!(config.len(), 2);
+            assert_eq!(
+                config["turbulence_a"],
+                TaskConfig::Turbulence(TurbulenceConfig {
+                    dx: "warp_x".to_owned().into(),
+                    dy: TaskConfig::Fractal(FractalConfig {
+                        octaves: 3,
+                        ..Default::default()
+                    })
+                    .into(),
+                    iterations: 2,
+                    power: 0.25.into(),
+                    source: "height".to_owned().into(),
+                    cache: Some(CacheConfig::default()),
+                    ..Default::default()
+                })
+            );
+
+            assert_eq!(
+                config["turbulence_b"],
+                TaskConfig::Turbulence(TurbulenceConfig {
+                    dx: "warp_x".to_owned().into(),
+                    source: "height".to_owned().into(),
+                    ..Default::default()
+                })
+            );
+        }
+    }
+}
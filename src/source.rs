@@ -1,48 +1,65 @@
 mod blender;
+mod cylindrical_gradient;
 mod gradient;
 mod perlin_noise;
+mod radial_gradient;
+mod spherical_gradient;
 
-macro_rules! noise_trait {
-	($type: ty) => {
-		/// Trait for generating noise values.
-		pub trait Noise : Debug {
-			/// Evaluates the noise function at the given x-coordinate.
-			fn sample_1d(&mut self, x: $type) -> $type;
+use alloc::boxed::Box;
+use core::fmt::Debug;
 
-			/// Evaluates the noise function at the given (x, y) coordinates.
-			fn sample_2d(&mut self, x: $type, y: $type) -> $type;
+use crate::float::Float;
 
-			/// Evaluates the noise function at the given (x, y, z) coordinates.
-			fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type;
-		}
-	};
+pub use blender::Blender;
+pub use cylindrical_gradient::CylindricalGradient;
+pub use gradient::Gradient;
+pub use perlin_noise::Perlin;
+pub use radial_gradient::RadialGradient;
+pub use spherical_gradient::SphericalGradient;
+
+/// Trait for generating noise values.
+pub trait Noise<T: Float> : Debug {
+	/// Evaluates the noise function at the given x-coordinate.
+	fn sample_1d(&mut self, x: T) -> T;
+
+	/// Evaluates the noise function at the given (x, y) coordinates.
+	fn sample_2d(&mut self, x: T, y: T) -> T;
+
+	/// Evaluates the noise function at the given (x, y, z) coordinates.
+	fn sample_3d(&mut self, x: T, y: T, z: T) -> T;
 }
 
-macro_rules! boxed_noise_trait {
-	() => {
-		pub trait BoxNoise: Noise {
-			fn box_clone(&self) -> Box<dyn Noise + 'static>;
-		}
-	};
+pub trait BoxNoise<T: Float>: Noise<T> {
+	fn box_clone(&self) -> Box<dyn Noise<T> + 'static>;
 }
 
 pub mod f32 {
-	use std::fmt::Debug;
-	noise_trait!(f32);
-	boxed_noise_trait!();
-
-	pub use super::blender::f32::Blender;
-	pub use super::perlin_noise::f32::Perlin;
-	pub use super::gradient::f32::Gradient;
+	pub use super::{BoxNoise, Noise};
+	pub type Blender = super::Blender<f32>;
+	pub type CylindricalGradient = super::CylindricalGradient<f32>;
+	pub type Gradient = super::Gradient<f32>;
+	pub type Perlin = super::Perlin<f32>;
+	pub type RadialGradient = super::RadialGradient<f32>;
+	pub type SphericalGradient = super::SphericalGradient<f32>;
 }
 
 pub mod f64 {
-	use std::fmt::Debug;
-	noise_trait!(f64);
-	boxed_noise_trait!();
-
-	pub use super::blender::f64::Blender;
-	pub use super::perlin_noise::f64::Perlin;
-	pub use super::gradient::f64::Gradient;
+	pub use super::{BoxNoise, Noise};
+	pub type Blender = super::Blender<f64>;
+	pub type CylindricalGradient = super::CylindricalGradient<f64>;
+	pub type Gradient = super::Gradient<f64>;
+	pub type Perlin = super::Perlin<f64>;
+	pub type RadialGradient = super::RadialGradient<f64>;
+	pub type SphericalGradient = super::SphericalGradient<f64>;
 }
 
+pub mod fixed {
+	pub use super::{BoxNoise, Noise};
+	pub use crate::float::Fixed;
+	pub type Blender = super::Blender<Fixed>;
+	pub type CylindricalGradient = super::CylindricalGradient<Fixed>;
+	pub type Gradient = super::Gradient<Fixed>;
+	pub type Perlin = super::Perlin<Fixed>;
+	pub type RadialGradient = super::RadialGradient<Fixed>;
+	pub type SphericalGradient = super::SphericalGradient<Fixed>;
+}
@@ -1,7 +1 @@
-pub mod f32 {
-	pub type Blender = fn(f32) -> f32;
-}
-
-pub mod f64 {
-	pub type Blender = fn(f64) -> f64;
-}
\ No newline at end of file
+pub type Blender<T> = fn(T) -> T;
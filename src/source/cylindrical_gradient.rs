@@ -0,0 +1,120 @@
+use super::gradient::{check_radius, falloff, Point};
+use super::Noise;
+use crate::float::Float;
+
+/// A gradient by the distance from the axis through two points.
+///
+/// Samples are -1 on the axis and rise to 1 at `radius`. 1D and 2D samples lie on the line
+/// and plane through `s1`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate ferro_noise;
+/// use ferro_noise::source::f64::{CylindricalGradient, Noise};
+///
+/// // A cylinder along the y axis.
+/// let mut grad = CylindricalGradient::new([0.0, 0.0, 0.0], [0.0, 1.0, 0.0], 2.0);
+///
+/// assert_eq!(grad.sample_3d(0.0, 5.0, 0.0), -1.0);
+/// assert_eq!(grad.sample_3d(1.0, -3.0, 0.0), 0.0);
+/// assert_eq!(grad.sample_3d(0.0, 0.0, 4.0), 1.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct CylindricalGradient<T: Float> {
+	s1: Point<T>,
+	dir: Point<T>,
+	radius: T,
+}
+
+impl<T: Float> Default for CylindricalGradient<T> {
+	fn default() -> Self {
+		Self::new([T::ZERO; 3], [T::ZERO, T::ZERO, T::ONE], T::ONE)
+	}
+}
+
+impl<T: Float> CylindricalGradient<T> {
+	/// Creates a new CylindricalGradient around the axis through `s1` and `s2`.
+	///
+	/// # Arguments
+	///
+	/// * `s1`: A point on the axis.
+	/// * `s2`: Another point on the axis, only its direction from `s1` matters.
+	/// * `radius`: The distance from the axis sampled as 1.
+	pub fn new(s1: [T; 3], s2: [T; 3], radius: T) -> Self {
+		check_radius(radius);
+		let s1: Point<T> = s1.into();
+		let s2: Point<T> = s2.into();
+		let dir = s2 - s1;
+		let len = dir.length();
+		if len <= T::ZERO {
+			panic!("Gradient segment must have a greater length than 0.0");
+		}
+		Self {
+			s1,
+			dir: dir / len,
+			radius,
+		}
+	}
+
+	fn eval(&self, p: Point<T>) -> T {
+		let dp = p - self.s1;
+		let along = self.dir.scale(dp.dot(self.dir));
+		falloff((dp - along).length(), self.radius)
+	}
+}
+
+impl<T: Float> Noise<T> for CylindricalGradient<T> {
+	fn sample_1d(&mut self, x: T) -> T {
+		self.eval(Point {
+			x,
+			y: self.s1.y,
+			z: self.s1.z,
+		})
+	}
+
+	fn sample_2d(&mut self, x: T, y: T) -> T {
+		self.eval(Point { x, y, z: self.s1.z })
+	}
+
+	fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+		self.eval(Point { x, y, z })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	mod f32 {
+		use crate::source::f32::{CylindricalGradient, Noise};
+
+		#[test]
+		fn cylindrical_gradient() {
+			let mut grad = CylindricalGradient::new([1.0, 1.0, 0.0], [1.0, 3.0, 0.0], 2.0);
+			assert_eq!(grad.sample_1d(1.0), -1.0);
+			assert_eq!(grad.sample_1d(2.0), 0.0);
+
+			assert_eq!(grad.sample_2d(1.0, -6.0), -1.0);
+			assert_eq!(grad.sample_2d(0.0, 6.0), 0.0);
+
+			assert_eq!(grad.sample_3d(1.0, 9.0, 1.0), 0.0);
+			assert_eq!(grad.sample_3d(1.0, 9.0, 3.0), 1.0);
+		}
+	}
+
+	mod f64 {
+		use crate::source::f64::{CylindricalGradient, Noise};
+
+		#[test]
+		fn cylindrical_gradient() {
+			let mut grad = CylindricalGradient::new([1.0, 1.0, 0.0], [1.0, 3.0, 0.0], 2.0);
+			assert_eq!(grad.sample_1d(1.0), -1.0);
+			assert_eq!(grad.sample_1d(2.0), 0.0);
+
+			assert_eq!(grad.sample_2d(1.0, -6.0), -1.0);
+			assert_eq!(grad.sample_2d(0.0, 6.0), 0.0);
+
+			assert_eq!(grad.sample_3d(1.0, 9.0, 1.0), 0.0);
+			assert_eq!(grad.sample_3d(1.0, 9.0, 3.0), 1.0);
+		}
+	}
+}
@@ -1,206 +1,214 @@
+use core::ops::{Div, Sub};
+
+use super::Noise;
+use crate::float::Float;
+use crate::math;
+
 const MAX_GRADIENT_ENTRY: usize = 3;
 
-macro_rules! point {
-	($type: ty) => {
-		use std::ops::{Div, Sub};
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub(super) struct Point<T: Float> {
+	pub(super) x: T,
+	pub(super) y: T,
+	pub(super) z: T,
+}
 
-		#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
-		struct Point {
-			x: $type,
-			y: $type,
-			z: $type,
+impl<T: Float> Default for Point<T> {
+	fn default() -> Self {
+		Self {
+			x: T::ZERO,
+			y: T::ZERO,
+			z: T::ZERO,
 		}
-		
-		impl Default for Point {
-			fn default() -> Self {
-				Self {
-					x: 0.0,
-					y: 0.0,
-					z: 0.0,
-				}
-			}
+	}
+}
+
+impl<T: Float> Sub for Point<T> {
+	type Output = Point<T>;
+	fn sub(self, rhs: Self) -> Self::Output {
+		Self {
+			x: self.x - rhs.x,
+			y: self.y - rhs.y,
+			z: self.z - rhs.z,
 		}
-		
-		impl Sub for Point {
-			type Output = Point;
-			fn sub(self, rhs: Self) -> Self::Output {
-				Self {
-					x: self.x - rhs.x,
-					y: self.y - rhs.y,
-					z: self.z - rhs.z,
-				}
-			}
+	}
+}
+
+impl<T: Float> Div<T> for Point<T> {
+	type Output = Point<T>;
+	fn div(self, rhs: T) -> Self::Output {
+		Self {
+			x: self.x / rhs,
+			y: self.y / rhs,
+			z: self.z / rhs,
 		}
-		
-		impl Div<$type> for Point {
-			type Output = Point;
-			fn div(self, rhs: $type) -> Self::Output {
-				Self {
-					x: self.x / rhs,
-					y: self.y / rhs,
-					z: self.z / rhs,
-				}
-			}
+	}
+}
+
+impl<T: Float> From<[T; MAX_GRADIENT_ENTRY]> for Point<T> {
+	fn from(value: [T; MAX_GRADIENT_ENTRY]) -> Self {
+		Self {
+			x: value[0],
+			y: value[1],
+			z: value[2],
 		}
-		
-		impl From<[$type; MAX_GRADIENT_ENTRY]> for Point {
-			fn from(value: [$type; MAX_GRADIENT_ENTRY]) -> Self {
-				Self {
-					x: value[0],
-					y: value[1],
-					z: value[2],
-				}
-			}
+	}
+}
+
+impl<T: Float> Point<T> {
+	pub fn dot(self, rhs: Self) -> T {
+		self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+	}
+
+	pub fn length(self) -> T {
+		let len = self.dot(self);
+		match len {
+			_ if len <= T::ZERO => T::ZERO,
+			x => x.sqrt(),
 		}
-		
-		impl Point {
-			pub fn dot(self, rhs: Self) -> $type {
-				self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
-			}
+	}
+
+	pub fn scale(self, rhs: T) -> Self {
+		Self {
+			x: self.x * rhs,
+			y: self.y * rhs,
+			z: self.z * rhs,
 		}
-	};
+	}
 }
 
-macro_rules! gradient {
-	($type: ty) => {
-		/// A struct representing the gradient (i.e., the rate of change) between two points on a line segment.
-		///
-		/// # Fields
-		///
-		/// * `s1`: The first point on the line segment.
-		/// * `s2`: The second point on the line segment.
-		///
-		/// # Examples
-		///
-		/// ```
-		/// extern crate ferro_noise;
-		/// use ferro_noise::source::f64::{Noise, Gradient};
-		///
-		/// // Define two points on a line segment.
-		/// let s1 = [0.0, 0.0, 0.0];
-		/// let s2 = [1.0, 1.0, 0.0];
-		///
-		/// // Create a Gradient struct to calculate the gradient between the points.
-		/// let mut grad = Gradient::new(s1, s2);
-		///
-		/// // Calculate the gradient for a point on the line segment.
-		/// let gradient = grad.sample_2d(0.5, 0.5);
-		/// assert_eq!(gradient, 0.0);
-		/// ```
-		#[derive(Clone, Debug, PartialEq, PartialOrd)]
-		pub struct Gradient {
-			s1: Point,
-			dir: Point,
-			mag: $type,
-		}
-		
-		impl Default for Gradient {
-			fn default() -> Self {
-				let s1 = [0.0; MAX_GRADIENT_ENTRY].into();
-				let s2 = [1.0, 1.0, 0.0].into();
-		
-				let (dir, mag) = Gradient::pre_calc(&s1, &s2);
-				Self {
-					s1: s1,
-					dir: dir,
-					mag: mag,
-				}
-			}
+/// Maps a distance from the centre of a shaped gradient to the range [-1, 1], reaching 1 at
+/// `radius`.
+pub(super) fn falloff<T: Float>(distance: T, radius: T) -> T {
+	let clampped = math::clamp(distance / radius, T::ZERO, T::ONE);
+	math::lerp(-T::ONE, T::ONE, clampped)
+}
+
+pub(super) fn check_radius<T: Float>(radius: T) {
+	if radius <= T::ZERO {
+		panic!("Gradient radius must be greater than 0.0");
+	}
+}
+
+/// A struct representing the gradient (i.e., the rate of change) between two points on a line segment.
+///
+/// # Fields
+///
+/// * `s1`: The first point on the line segment.
+/// * `s2`: The second point on the line segment.
+///
+/// # Examples
+///
+/// ```
+/// extern crate ferro_noise;
+/// use ferro_noise::source::f64::{Noise, Gradient};
+///
+/// // Define two points on a line segment.
+/// let s1 = [0.0, 0.0, 0.0];
+/// let s2 = [1.0, 1.0, 0.0];
+///
+/// // Create a Gradient struct to calculate the gradient between the points.
+/// let mut grad = Gradient::new(s1, s2);
+///
+/// // Calculate the gradient for a point on the line segment.
+/// let gradient = grad.sample_2d(0.5, 0.5);
+/// assert_eq!(gradient, 0.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Gradient<T: Float> {
+	s1: Point<T>,
+	dir: Point<T>,
+	mag: T,
+}
+
+impl<T: Float> Default for Gradient<T> {
+	fn default() -> Self {
+		let s1 = [T::ZERO; MAX_GRADIENT_ENTRY].into();
+		let s2 = [T::ONE, T::ONE, T::ZERO].into();
+
+		let (dir, mag) = Gradient::pre_calc(&s1, &s2);
+		Self {
+			s1,
+			dir,
+			mag,
 		}
-		
-		impl Gradient {
-			/// Creates a new Gradient struct with the given line segment endpoints.
-			///
-			/// # Arguments
-			///
-			/// * `s1`: The first point on the line segment.
-			/// * `s2`: The second point on the line segment.
-			pub fn new(s1: [$type; MAX_GRADIENT_ENTRY], s2: [$type; MAX_GRADIENT_ENTRY]) -> Self {
-				let s1 = s1.into();
-				let s2 = s2.into();
-				let (dir, mag) = Gradient::pre_calc(&s1, &s2);
-				Self {
-					s1: s1,
-					dir: dir,
-					mag: mag,
-				}
-			}
-		
-			fn pre_calc(s1: &Point, s2: &Point) -> (Point, $type) {
-				let direction = *s2 - *s1;
-				let len = direction.dot(direction);
-		
-				if len <= 0.0 {
-					panic!("Gradient segment must have a greater length than 0.0");
-				}
-				// sqrt + a little margin to acount for floating point error
-				let len = len.sqrt();
-		
-				let direction = direction / len;
-		
-				(direction, len)
-			}
-		
-			fn eval(&mut self, p1: Point) -> $type {
-				let dp = p1 - self.s1;
-				let dot = dp.dot(self.dir);
-				let proj_p = (dot / self.mag) * (1.0 + <$type>::EPSILON);
-				let clampped = math::clamp(proj_p, 0.0, 1.0);
-				math::lerp(-1.0, 1.0, clampped)
-			}
+	}
+}
+
+impl<T: Float> Gradient<T> {
+	/// Creates a new Gradient struct with the given line segment endpoints.
+	///
+	/// # Arguments
+	///
+	/// * `s1`: The first point on the line segment.
+	/// * `s2`: The second point on the line segment.
+	pub fn new(s1: [T; MAX_GRADIENT_ENTRY], s2: [T; MAX_GRADIENT_ENTRY]) -> Self {
+		let s1 = s1.into();
+		let s2 = s2.into();
+		let (dir, mag) = Gradient::pre_calc(&s1, &s2);
+		Self {
+			s1,
+			dir,
+			mag,
 		}
-		
-		impl Noise for Gradient {
-			/// Calculates the dot product of the x value scaled to the range [-1, 1].
-			fn sample_1d(&mut self, x: $type) -> $type {
-				let delta = x - self.s1.x;
-				self.eval(Point {
-					x,
-					y: self.s1.y + delta,
-					z: self.s1.z + delta,
-				})
-			}
-		
-			/// Calculates the dot product of the x, y values scaled to the range [-1, 1].
-			fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-				let p1 = Point { x, y, z: self.s1.z };
-				let diff = p1 - self.s1;
-				let mag = diff.dot(diff);
-				let mag = match mag {
-					_ if mag <= 0.0 => 0.0,
-					x => x.sqrt(),
-				};
-				self.eval(Point {
-					x,
-					y,
-					z: self.s1.z + self.dir.z * mag,
-				})
-			}
-		
-			/// Calculates the dot product of the x, y, and z values scaled to the range [-1, 1].
-			fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-				self.eval(Point { x, y, z })
-			}
+	}
+
+	fn pre_calc(s1: &Point<T>, s2: &Point<T>) -> (Point<T>, T) {
+		let direction = *s2 - *s1;
+		let len = direction.dot(direction);
+
+		if len <= T::ZERO {
+			panic!("Gradient segment must have a greater length than 0.0");
 		}
-	};
-}
+		// sqrt + a little margin to acount for floating point error
+		let len = len.sqrt();
 
-pub mod f32 {
-	use super::MAX_GRADIENT_ENTRY;
-	use crate::source::f32::Noise;
-	use crate::math::f32 as math;
-	point!(f32);
-	gradient!(f32);
-}
+		let direction = direction / len;
+
+		(direction, len)
+	}
 
-pub mod f64 {
-	use super::MAX_GRADIENT_ENTRY;
-	use crate::source::f64::Noise;
-	use crate::math::f64 as math;
-	point!(f64);
-	gradient!(f64);
+	fn eval(&mut self, p1: Point<T>) -> T {
+		let dp = p1 - self.s1;
+		let dot = dp.dot(self.dir);
+		let proj_p = (dot / self.mag) * (T::ONE + T::EPSILON);
+		let clampped = math::clamp(proj_p, T::ZERO, T::ONE);
+		math::lerp(-T::ONE, T::ONE, clampped)
+	}
 }
 
+impl<T: Float> Noise<T> for Gradient<T> {
+	/// Calculates the dot product of the x value scaled to the range [-1, 1].
+	fn sample_1d(&mut self, x: T) -> T {
+		let delta = x - self.s1.x;
+		self.eval(Point {
+			x,
+			y: self.s1.y + delta,
+			z: self.s1.z + delta,
+		})
+	}
+
+	/// Calculates the dot product of the x, y values scaled to the range [-1, 1].
+	fn sample_2d(&mut self, x: T, y: T) -> T {
+		let p1 = Point { x, y, z: self.s1.z };
+		let diff = p1 - self.s1;
+		let mag = diff.dot(diff);
+		let mag = match mag {
+			_ if mag <= T::ZERO => T::ZERO,
+			x => x.sqrt(),
+		};
+		self.eval(Point {
+			x,
+			y,
+			z: self.s1.z + self.dir.z * mag,
+		})
+	}
+
+	/// Calculates the dot product of the x, y, and z values scaled to the range [-1, 1].
+	fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+		self.eval(Point { x, y, z })
+	}
+}
 
 #[cfg(test)]
 mod tests {
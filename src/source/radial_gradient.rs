@@ -0,0 +1,112 @@
+use super::gradient::{check_radius, falloff, Point};
+use super::Noise;
+use crate::float::Float;
+
+/// A gradient by the distance from a centre point in the xy plane, ignoring z.
+///
+/// Samples are -1 at the centre and rise to 1 at `radius`, so a 3D sample follows a cylinder
+/// along the z axis.
+///
+/// # Examples
+///
+/// ```
+/// extern crate ferro_noise;
+/// use ferro_noise::source::f64::{Noise, RadialGradient};
+///
+/// let mut grad = RadialGradient::new([0.0, 0.0, 0.0], 2.0);
+///
+/// assert_eq!(grad.sample_2d(0.0, 0.0), -1.0);
+/// assert_eq!(grad.sample_2d(0.0, 1.0), 0.0);
+/// assert_eq!(grad.sample_3d(3.0, 0.0, 5.0), 1.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct RadialGradient<T: Float> {
+	centre: Point<T>,
+	radius: T,
+}
+
+impl<T: Float> Default for RadialGradient<T> {
+	fn default() -> Self {
+		Self::new([T::ZERO; 3], T::ONE)
+	}
+}
+
+impl<T: Float> RadialGradient<T> {
+	/// Creates a new RadialGradient around `centre`.
+	///
+	/// # Arguments
+	///
+	/// * `centre`: The point sampled as -1, only its x and y are used.
+	/// * `radius`: The distance from the centre sampled as 1.
+	pub fn new(centre: [T; 3], radius: T) -> Self {
+		check_radius(radius);
+		Self {
+			centre: centre.into(),
+			radius,
+		}
+	}
+
+	fn eval(&self, x: T, y: T) -> T {
+		let d = Point {
+			x: x - self.centre.x,
+			y: y - self.centre.y,
+			z: T::ZERO,
+		};
+		falloff(d.length(), self.radius)
+	}
+}
+
+impl<T: Float> Noise<T> for RadialGradient<T> {
+	fn sample_1d(&mut self, x: T) -> T {
+		self.eval(x, self.centre.y)
+	}
+
+	fn sample_2d(&mut self, x: T, y: T) -> T {
+		self.eval(x, y)
+	}
+
+	fn sample_3d(&mut self, x: T, y: T, _: T) -> T {
+		self.eval(x, y)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	mod f32 {
+		use crate::source::f32::{Noise, RadialGradient};
+
+		#[test]
+		fn radial_gradient() {
+			let mut grad = RadialGradient::new([1.0, 1.0, 1.0], 2.0);
+			assert_eq!(grad.sample_1d(1.0), -1.0);
+			assert_eq!(grad.sample_1d(2.0), 0.0);
+			assert_eq!(grad.sample_1d(-4.0), 1.0);
+
+			assert_eq!(grad.sample_2d(1.0, 1.0), -1.0);
+			assert_eq!(grad.sample_2d(1.0, 0.0), 0.0);
+			assert_eq!(grad.sample_2d(3.0, 3.0), 1.0);
+
+			assert_eq!(grad.sample_3d(1.0, 1.0, 8.0), -1.0);
+			assert_eq!(grad.sample_3d(2.0, 1.0, -8.0), 0.0);
+		}
+	}
+
+	mod f64 {
+		use crate::source::f64::{Noise, RadialGradient};
+
+		#[test]
+		fn radial_gradient() {
+			let mut grad = RadialGradient::new([1.0, 1.0, 1.0], 2.0);
+			assert_eq!(grad.sample_1d(1.0), -1.0);
+			assert_eq!(grad.sample_1d(2.0), 0.0);
+			assert_eq!(grad.sample_1d(-4.0), 1.0);
+
+			assert_eq!(grad.sample_2d(1.0, 1.0), -1.0);
+			assert_eq!(grad.sample_2d(1.0, 0.0), 0.0);
+			assert_eq!(grad.sample_2d(3.0, 3.0), 1.0);
+
+			assert_eq!(grad.sample_3d(1.0, 1.0, 8.0), -1.0);
+			assert_eq!(grad.sample_3d(2.0, 1.0, -8.0), 0.0);
+		}
+	}
+}
@@ -0,0 +1,113 @@
+use super::gradient::{check_radius, falloff, Point};
+use super::Noise;
+use crate::float::Float;
+
+/// A gradient by the distance from a centre point in all three dimensions.
+///
+/// Samples are -1 at the centre and rise to 1 at `radius`. 1D and 2D samples lie on the line
+/// and plane through the centre, so they match a [`RadialGradient`](super::RadialGradient).
+///
+/// # Examples
+///
+/// ```
+/// extern crate ferro_noise;
+/// use ferro_noise::source::f64::{Noise, SphericalGradient};
+///
+/// let mut grad = SphericalGradient::new([0.0, 0.0, 0.0], 2.0);
+///
+/// assert_eq!(grad.sample_3d(0.0, 0.0, 0.0), -1.0);
+/// assert_eq!(grad.sample_3d(0.0, 0.0, 1.0), 0.0);
+/// assert_eq!(grad.sample_3d(0.0, 0.0, 5.0), 1.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct SphericalGradient<T: Float> {
+	centre: Point<T>,
+	radius: T,
+}
+
+impl<T: Float> Default for SphericalGradient<T> {
+	fn default() -> Self {
+		Self::new([T::ZERO; 3], T::ONE)
+	}
+}
+
+impl<T: Float> SphericalGradient<T> {
+	/// Creates a new SphericalGradient around `centre`.
+	///
+	/// # Arguments
+	///
+	/// * `centre`: The point sampled as -1.
+	/// * `radius`: The distance from the centre sampled as 1.
+	pub fn new(centre: [T; 3], radius: T) -> Self {
+		check_radius(radius);
+		Self {
+			centre: centre.into(),
+			radius,
+		}
+	}
+
+	fn eval(&self, p: Point<T>) -> T {
+		falloff((p - self.centre).length(), self.radius)
+	}
+}
+
+impl<T: Float> Noise<T> for SphericalGradient<T> {
+	fn sample_1d(&mut self, x: T) -> T {
+		self.eval(Point {
+			x,
+			y: self.centre.y,
+			z: self.centre.z,
+		})
+	}
+
+	fn sample_2d(&mut self, x: T, y: T) -> T {
+		self.eval(Point {
+			x,
+			y,
+			z: self.centre.z,
+		})
+	}
+
+	fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+		self.eval(Point { x, y, z })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	mod f32 {
+		use crate::source::f32::{Noise, SphericalGradient};
+
+		#[test]
+		fn spherical_gradient() {
+			let mut grad = SphericalGradient::new([1.0, 1.0, 1.0], 2.0);
+			assert_eq!(grad.sample_1d(1.0), -1.0);
+			assert_eq!(grad.sample_1d(0.0), 0.0);
+
+			assert_eq!(grad.sample_2d(1.0, 2.0), 0.0);
+			assert_eq!(grad.sample_2d(3.0, 3.0), 1.0);
+
+			assert_eq!(grad.sample_3d(1.0, 1.0, 1.0), -1.0);
+			assert_eq!(grad.sample_3d(1.0, 1.0, 2.0), 0.0);
+			assert_eq!(grad.sample_3d(1.0, 1.0, -8.0), 1.0);
+		}
+	}
+
+	mod f64 {
+		use crate::source::f64::{Noise, SphericalGradient};
+
+		#[test]
+		fn spherical_gradient() {
+			let mut grad = SphericalGradient::new([1.0, 1.0, 1.0], 2.0);
+			assert_eq!(grad.sample_1d(1.0), -1.0);
+			assert_eq!(grad.sample_1d(0.0), 0.0);
+
+			assert_eq!(grad.sample_2d(1.0, 2.0), 0.0);
+			assert_eq!(grad.sample_2d(3.0, 3.0), 1.0);
+
+			assert_eq!(grad.sample_3d(1.0, 1.0, 1.0), -1.0);
+			assert_eq!(grad.sample_3d(1.0, 1.0, 2.0), 0.0);
+			assert_eq!(grad.sample_3d(1.0, 1.0, -8.0), 1.0);
+		}
+	}
+}
@@ -1,59 +1,148 @@
+mod abs;
 mod aggregator;
 mod bias;
 mod cache;
+mod clamp;
+mod curve;
+mod custom;
+mod exponent;
 mod fractal;
+#[cfg(test)]
+mod golden;
 mod gradient;
+mod invert;
+mod multi_gradient;
+mod parameter;
+mod pow;
 mod scale;
 mod scale_offset;
 mod selector;
+#[allow(clippy::module_inception)]
 mod task;
 mod task_tree;
+mod terrace;
 mod transform_domain;
+mod turbulence;
 
+#[cfg(feature = "serde")]
+pub(crate) use task::{
+    domain_operation_name, format_levels, format_matrix, format_points, format_stops, fractal_name,
+    gradient_kind_name, interpolation_name, operation_name,
+};
 pub(crate) use task::{named_to_task, source_or_message};
 
-macro_rules! task_type {
-    ($type: ty) => {
-        /// Trait for generating noise values.
-        pub trait Task {
-            /// Evaluates the noise function at the given x-coordinate.
-            fn sample_1d(&mut self, x: $type) -> $type;
+pub use abs::{Abs, AbsBuilder};
+pub use aggregator::{Aggregator, AggregatorBuilder, Operation};
+pub use bias::{Bias, BiasBuilder};
+pub use cache::{Cache, CacheBuilder, CacheMode, CacheStrategy, CacheTolerance};
+pub use clamp::{Clamp, ClampBuilder};
+pub use curve::{Curve, CurveBuilder, Interpolation};
+pub use custom::CustomTask;
+pub use exponent::{Exponent, ExponentBuilder};
+pub use fractal::{Fractal, FractalBuilder, FractalType, NoiseConfig, ScaleBias};
+pub use gradient::{Gradient, GradientBuilder, GradientKind};
+pub use invert::{Invert, InvertBuilder};
+pub use multi_gradient::{GradientStop, MultiGradient, MultiGradientBuilder};
+pub use parameter::Parameter;
+pub use pow::{Pow, PowBuilder};
+pub use scale::{Scale, ScaleBuilder};
+pub use scale_offset::{ScaleOffset, ScaleOffsetBuilder};
+pub use selector::{Selector, SelectorBuilder};
+pub(crate) use task::NameOrSource;
+pub use task::TaskSource;
+pub use task_tree::TaskTree;
+pub use terrace::{Terrace, TerraceBuilder};
+pub use transform_domain::{DomainOperation, TransformDomain, TransformDomainBuilder};
+pub use turbulence::{Turbulence, TurbulenceBuilder};
 
-            /// Evaluates the noise function at the given (x, y) coordinates.
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type;
+use crate::float::Float;
 
-            /// Evaluates the noise function at the given (x, y, z) coordinates.
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type;
-        }
-    };
+/// Trait for generating noise values.
+///
+/// Implement it for `T: Float` to write a task once for both precisions.
+pub trait Task<T: Float> {
+    /// Evaluates the noise function at the given x-coordinate.
+    fn sample_1d(&mut self, x: T) -> T;
+
+    /// Evaluates the noise function at the given (x, y) coordinates.
+    fn sample_2d(&mut self, x: T, y: T) -> T;
+
+    /// Evaluates the noise function at the given (x, y, z) coordinates.
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T;
 }
 
 pub mod f32 {
+    pub use super::abs::f32::*;
     pub use super::aggregator::f32::*;
     pub use super::bias::f32::*;
     pub use super::cache::f32::*;
+    pub use super::clamp::f32::*;
+    pub use super::curve::f32::*;
+    pub use super::exponent::f32::*;
     pub use super::fractal::f32::*;
     pub use super::gradient::f32::*;
+    pub use super::invert::f32::*;
+    pub use super::multi_gradient::f32::*;
+    pub use super::parameter::f32::*;
+    pub use super::pow::f32::*;
     pub use super::scale::f32::*;
     pub use super::scale_offset::f32::*;
     pub use super::selector::f32::*;
     pub use super::task::f32::*;
     pub use super::task_tree::f32::*;
+    pub use super::terrace::f32::*;
     pub use super::transform_domain::f32::*;
-    task_type!(f32);
+    pub use super::turbulence::f32::*;
+    pub use super::{CustomTask, Task};
 }
 
 pub mod f64 {
+    pub use super::abs::f64::*;
     pub use super::aggregator::f64::*;
     pub use super::bias::f64::*;
     pub use super::cache::f64::*;
+    pub use super::clamp::f64::*;
+    pub use super::curve::f64::*;
+    pub use super::exponent::f64::*;
     pub use super::fractal::f64::*;
     pub use super::gradient::f64::*;
+    pub use super::invert::f64::*;
+    pub use super::multi_gradient::f64::*;
+    pub use super::parameter::f64::*;
+    pub use super::pow::f64::*;
     pub use super::scale::f64::*;
     pub use super::scale_offset::f64::*;
     pub use super::selector::f64::*;
     pub use super::task::f64::*;
     pub use super::task_tree::f64::*;
+    pub use super::terrace::f64::*;
     pub use super::transform_domain::f64::*;
-    task_type!(f64);
+    pub use super::turbulence::f64::*;
+    pub use super::{CustomTask, Task};
+}
+
+pub mod fixed {
+    pub use super::abs::fixed::*;
+    pub use super::aggregator::fixed::*;
+    pub use super::bias::fixed::*;
+    pub use super::cache::fixed::*;
+    pub use super::clamp::fixed::*;
+    pub use super::curve::fixed::*;
+    pub use super::exponent::fixed::*;
+    pub use super::fractal::fixed::*;
+    pub use super::gradient::fixed::*;
+    pub use super::invert::fixed::*;
+    pub use super::multi_gradient::fixed::*;
+    pub use super::parameter::fixed::*;
+    pub use super::pow::fixed::*;
+    pub use super::scale::fixed::*;
+    pub use super::scale_offset::fixed::*;
+    pub use super::selector::fixed::*;
+    pub use super::task::fixed::*;
+    pub use super::task_tree::fixed::*;
+    pub use super::terrace::fixed::*;
+    pub use super::transform_domain::fixed::*;
+    pub use super::turbulence::fixed::*;
+    pub use super::{CustomTask, Task};
+    pub use crate::float::Fixed;
 }
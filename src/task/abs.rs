@@ -0,0 +1,93 @@
+mod builder;
+
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+pub use builder::AbsBuilder;
+
+/// Task returning the absolute value of its source.
+#[derive(Clone, Debug)]
+pub struct Abs<T: Float> {
+    pub(crate) source: TaskSource<T>,
+}
+
+impl<T: Float> Abs<T> {
+    fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+        let v = sampler(&mut self.source);
+        v.abs()
+    }
+}
+
+impl<T: Float> Task<T> for Abs<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
+}
+
+pub mod f32 {
+    pub type Abs = super::Abs<f32>;
+    pub type AbsBuilder = super::AbsBuilder<f32>;
+}
+
+pub mod f64 {
+    pub type Abs = super::Abs<f64>;
+    pub type AbsBuilder = super::AbsBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Abs = super::Abs<Fixed>;
+    pub type AbsBuilder = super::AbsBuilder<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{AbsBuilder, Task};
+        #[test]
+        fn abs_negative() {
+            let mut result = AbsBuilder::new().source(-0.5).build();
+
+            assert_eq!(result.sample_1d(1.0), 0.5);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.5);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.5);
+        }
+
+        #[test]
+        fn abs_positive() {
+            let mut result = AbsBuilder::new().source(0.25).build();
+
+            assert_eq!(result.sample_1d(1.0), 0.25);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.25);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.25);
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{AbsBuilder, Task};
+        #[test]
+        fn abs_negative() {
+            let mut result = AbsBuilder::new().source(-0.5).build();
+
+            assert_eq!(result.sample_1d(1.0), 0.5);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.5);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.5);
+        }
+
+        #[test]
+        fn abs_positive() {
+            let mut result = AbsBuilder::new().source(0.25).build();
+
+            assert_eq!(result.sample_1d(1.0), 0.25);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.25);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.25);
+        }
+    }
+}
@@ -1,8 +1,14 @@
 mod builder;
 
+use alloc::vec;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+pub use builder::AggregatorBuilder;
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
 #[cfg_attr(
     feature = "serde",
@@ -20,99 +26,100 @@ pub enum Operation {
     Min,
 }
 
-macro_rules! aggregator {
-    ($type: ty) => {
-        #[derive(Clone, Debug)]
-        pub struct Aggregator {
-            pub(crate) op: Operation,
-            pub(crate) initial: $type,
-            pub(crate) sources: Vec<TaskSource>,
+#[derive(Clone, Debug)]
+pub struct Aggregator<T: Float> {
+    pub(crate) op: Operation,
+    pub(crate) initial: T,
+    pub(crate) sources: Vec<TaskSource<T>>,
+}
+
+impl<T: Float> Default for Aggregator<T> {
+    fn default() -> Self {
+        Self {
+            op: Operation::Add,
+            initial: T::ZERO,
+            sources: vec![],
         }
+    }
+}
 
-        impl Default for Aggregator {
-            fn default() -> Self {
-                Self {
-                    op: Operation::Add,
-                    initial: 0.0,
-                    sources: vec![],
-                }
-            }
+impl<T: Float> Aggregator<T> {
+    pub fn new(op: Operation, initial: T, sources: Vec<TaskSource<T>>) -> Self {
+        Self {
+            op,
+            initial,
+            sources,
         }
+    }
 
-        impl Aggregator {
-            pub fn new(op: Operation, initial: $type, sources: Vec<TaskSource>) -> Self {
-                Self {
-                    op,
-                    initial,
-                    sources,
+    fn eval<F>(&mut self, sampler: F) -> T
+    where
+        F: Fn(&mut TaskSource<T>) -> T,
+    {
+        let mut result = self.initial;
+        for source in self.sources.iter_mut() {
+            let val = sampler(source);
+            result = match self.op {
+                Operation::Add => result + val,
+                Operation::Avg => result + val,
+                Operation::Div => result / val,
+                Operation::Max => {
+                    if result < val {
+                        val
+                    } else {
+                        result
+                    }
                 }
-            }
-
-            fn eval<F>(&mut self, sampler: F) -> $type
-            where
-                F: Fn(&mut TaskSource) -> $type,
-            {
-                let mut result = self.initial;
-                for source in self.sources.iter_mut() {
-                    let val = sampler(source);
-                    result = match self.op {
-                        Operation::Add => result + val,
-                        Operation::Avg => result + val,
-                        Operation::Div => result / val,
-                        Operation::Max => {
-                            if result < val {
-                                val
-                            } else {
-                                result
-                            }
-                        }
-                        Operation::Min => {
-                            if result < val {
-                                result
-                            } else {
-                                val
-                            }
-                        }
-                        Operation::Mul => result * val,
-                        Operation::Sub => result - val,
-                    };
+                Operation::Min => {
+                    if result < val {
+                        result
+                    } else {
+                        val
+                    }
                 }
-                if self.op == Operation::Avg && self.sources.len() > 0 {
-                    result / self.sources.len() as $type
-                } else {
-                    result
-                }
-            }
+                Operation::Mul => result * val,
+                Operation::Sub => result - val,
+            };
+        }
+        if self.op == Operation::Avg && !self.sources.is_empty() {
+            result / T::from_usize(self.sources.len())
+        } else {
+            result
         }
+    }
+}
 
-        impl Task for Aggregator {
-            fn sample_1d(&mut self, x: $type) -> $type {
-                self.eval(|s| (*s).sample_1d(x))
-            }
+impl<T: Float> Task<T> for Aggregator<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|s| (*s).sample_1d(x))
+    }
 
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-                self.eval(|s| (*s).sample_2d(x, y))
-            }
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|s| (*s).sample_2d(x, y))
+    }
 
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-                self.eval(|s| (*s).sample_3d(x, y, z))
-            }
-        }
-    };
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|s| (*s).sample_3d(x, y, z))
+    }
 }
 
 pub mod f32 {
-    pub use super::builder::f32::AggregatorBuilder;
     pub use super::Operation;
-    use crate::task::f32::{Task, TaskSource};
-    aggregator!(f32);
+    pub type Aggregator = super::Aggregator<f32>;
+    pub type AggregatorBuilder = super::AggregatorBuilder<f32>;
 }
 
 pub mod f64 {
-    pub use super::builder::f64::AggregatorBuilder;
     pub use super::Operation;
-    use crate::task::f64::{Task, TaskSource};
-    aggregator!(f64);
+    pub type Aggregator = super::Aggregator<f64>;
+    pub type AggregatorBuilder = super::AggregatorBuilder<f64>;
+}
+
+pub mod fixed {
+    pub use super::Operation;
+    use crate::float::Fixed;
+    pub type Aggregator = super::Aggregator<Fixed>;
+    pub type AggregatorBuilder = super::AggregatorBuilder<Fixed>;
 }
 
 #[cfg(test)]
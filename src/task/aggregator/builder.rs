@@ -1,80 +1,79 @@
-macro_rules! aggregator_builder {
-    ($type: ty) => {
-        pub struct AggregatorBuilder {
-            op: Operation,
-            initial: Option<$type>,
-            tasks: Vec<TaskSource>,
-            refs: Vec<String>,
-        }
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
 
-        #[allow(dead_code)]
-        impl AggregatorBuilder {
-            pub fn new() -> Self {
-                Self {
-                    op: Operation::Add,
-                    initial: None,
-                    tasks: vec![],
-                    refs: vec![],
-                }
-            }
-            pub fn add_named_task<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.refs.push(name.into());
-                self
-            }
+use super::{Aggregator, Operation};
+use crate::float::Float;
+use crate::task::{TaskSource, TaskTree};
 
-            pub fn add_task<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.tasks.push(task.into());
-                self
-            }
+pub struct AggregatorBuilder<T: Float> {
+    op: Operation,
+    initial: Option<T>,
+    tasks: Vec<TaskSource<T>>,
+    refs: Vec<String>,
+}
 
-            pub fn build(&self) -> Aggregator {
-                Aggregator {
-                    op: self.op,
-                    initial: match self.initial {
-                        Some(x) => x,
-                        _ => match self.op {
-                            Operation::Div | Operation::Mul => 1.0,
-                            Operation::Min => <$type>::MAX,
-                            Operation::Max => <$type>::MIN,
-                            _ => 0.0,
-                        },
-                    },
-                    sources: self.tasks.clone(),
-                }
-            }
+impl<T: Float> Default for AggregatorBuilder<T> {
+    fn default() -> Self {
+        Self {
+            op: Operation::Add,
+            initial: None,
+            tasks: vec![],
+            refs: vec![],
+        }
+    }
+}
 
-            /// Link named tasks to their task tree values
-            pub fn link(&mut self, tree: &TaskTree) -> &mut Self {
-                for name in &self.refs {
-                    if let Some(task) = tree.get(name) {
-                        self.tasks.push(task.clone());
-                    }
-                }
-                self.refs.clear();
-                self
-            }
+#[allow(dead_code)]
+impl<T: Float> AggregatorBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-            pub fn initial<V: Into<$type>>(&mut self, value: V) -> &mut Self {
-                self.initial = Some(value.into());
-                self
-            }
+    pub fn add_named_task<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.refs.push(name.into());
+        self
+    }
 
-            pub fn operation(&mut self, op: Operation) -> &mut Self {
-                self.op = op;
-                self
+    pub fn add_task<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.tasks.push(task.into());
+        self
+    }
+
+    pub fn build(&self) -> Aggregator<T> {
+        Aggregator {
+            op: self.op,
+            initial: match self.initial {
+                Some(x) => x,
+                _ => match self.op {
+                    Operation::Div | Operation::Mul => T::ONE,
+                    Operation::Min => T::MAX,
+                    Operation::Max => T::MIN,
+                    _ => T::ZERO,
+                },
+            },
+            sources: self.tasks.clone(),
+        }
+    }
+
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        for name in &self.refs {
+            if let Some(task) = tree.get(name) {
+                self.tasks.push(task.clone());
             }
         }
-    };
-}
+        self.refs.clear();
+        self
+    }
 
-pub mod f32 {
-    use super::super::{f32::Aggregator, Operation};
-    use crate::task::f32::{TaskSource, TaskTree};
-    aggregator_builder!(f32);
-}
+    pub fn initial<V: Into<T>>(&mut self, value: V) -> &mut Self {
+        self.initial = Some(value.into());
+        self
+    }
 
-pub mod f64 {
-    use super::super::{f64::Aggregator, Operation};
-    use crate::task::f64::{TaskSource, TaskTree};
-    aggregator_builder!(f64);
+    pub fn operation(&mut self, op: Operation) -> &mut Self {
+        self.op = op;
+        self
+    }
 }
@@ -1,57 +1,59 @@
 mod builder;
 
-macro_rules! bias {
-	($type: ty) => {
-		#[derive(Clone, Debug)]
-		pub struct Bias {
-			pub(crate) bias: TaskSource,
-			pub(crate) source: TaskSource,
-			// The `min` field represents the minimum value of an exponent used
-			pub(crate) min: $type,
-			// The `max` field represents the maximum value of an exponent used
-			pub(crate) max: $type,
-		}
-		
-		impl Bias {
-			fn eval<F: Fn(&mut dyn Task) -> $type>(&mut self, sampler: F) -> $type {
-				let v = sampler(&mut self.source);
-				let b = sampler(&mut self.bias);
-				// ease in out with higher exponents will push the values further towards the extremes
-				let p = (b * self.max) + self.min;
-				math::ease_in_out(v, p)
-			}
-		}
-		
-		impl Task for Bias {
-			fn sample_1d(&mut self, x: $type) -> $type {
-				self.eval(|t| t.sample_1d(x))
-			}
-		
-			fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-				self.eval(|t| t.sample_2d(x, y))
-			}
-		
-			fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-				self.eval(|t| t.sample_3d(x, y, z))
-			}
-		}		
-	};
+use crate::float::Float;
+use crate::math;
+use crate::task::{Task, TaskSource};
+pub use builder::BiasBuilder;
+
+#[derive(Clone, Debug)]
+pub struct Bias<T: Float> {
+	pub(crate) bias: TaskSource<T>,
+	pub(crate) source: TaskSource<T>,
+	// The `min` field represents the minimum value of an exponent used
+	pub(crate) min: T,
+	// The `max` field represents the maximum value of an exponent used
+	pub(crate) max: T,
+}
+
+impl<T: Float> Bias<T> {
+	fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+		let v = sampler(&mut self.source);
+		let b = sampler(&mut self.bias);
+		// ease in out with higher exponents will push the values further towards the extremes
+		let p = (b * self.max) + self.min;
+		math::ease_in_out(v, p)
+	}
+}
+
+impl<T: Float> Task<T> for Bias<T> {
+	fn sample_1d(&mut self, x: T) -> T {
+		self.eval(|t| t.sample_1d(x))
+	}
+
+	fn sample_2d(&mut self, x: T, y: T) -> T {
+		self.eval(|t| t.sample_2d(x, y))
+	}
+
+	fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+		self.eval(|t| t.sample_3d(x, y, z))
+	}
 }
 
 pub mod f32 {
-	use crate::math::f32 as math;
-	use crate::task::f32::{TaskSource, Task};
-	pub use super::builder::f32::BiasBuilder;
-	bias!(f32);
+	pub type Bias = super::Bias<f32>;
+	pub type BiasBuilder = super::BiasBuilder<f32>;
 }
 
 pub mod f64 {
-	use crate::math::f64 as math;
-	use crate::task::f64::{TaskSource, Task};
-	pub use super::builder::f64::BiasBuilder;
-	bias!(f64);
+	pub type Bias = super::Bias<f64>;
+	pub type BiasBuilder = super::BiasBuilder<f64>;
 }
 
+pub mod fixed {
+	use crate::float::Fixed;
+	pub type Bias = super::Bias<Fixed>;
+	pub type BiasBuilder = super::BiasBuilder<Fixed>;
+}
 
 #[cfg(test)]
 mod tests {
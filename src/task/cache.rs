@@ -1,92 +1,286 @@
 mod builder;
 
+pub use builder::CacheBuilder;
 use builder::MAX_CACHE_ENTRY;
 
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+
+/// Name of a [`CacheStrategy`] in configs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize, Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CacheMode {
+    #[default]
+    Last,
+    Lru,
+    Grid,
+}
+
 const CACHE_1D: usize = 0;
 const CACHE_2D: usize = 1;
 const CACHE_3D: usize = 2;
 
-macro_rules! cache_value {
-    ($type: ty) => {
-        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
-        pub(crate) struct CacheValue {
-            pub x: $type,
-            pub y: $type,
-            pub z: $type,
-            pub value: $type,
-        }
-    };
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub(crate) struct CacheValue<T: Float> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub value: T,
 }
 
-macro_rules! cache {
-    ($type: ty) => {
-        #[derive(Clone, Debug)]
-        pub struct Cache {
-            pub(crate) store: [Option<CacheValue>; MAX_CACHE_ENTRY],
-            pub(crate) source: TaskSource,
-        }
-
-        impl Cache {
-            fn eval<F: Fn(&mut dyn Task) -> $type>(
-                &mut self,
-                key: usize,
-                coords: ($type, $type, $type),
-                sampler: F,
-            ) -> $type {
-                if let Some(v) = self.store[key] {
-                    if nearly_eq(v.x, coords.0) && nearly_eq(v.y, coords.1) && nearly_eq(v.z, coords.2)
-                    {
-                        return v.value;
-                    }
-                }
+/// Which points a [`Cache`] remembers besides the last point sampled in each dimension.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub enum CacheStrategy<T: Float> {
+    /// Only the last point
+    #[default]
+    Last,
+    /// The given number of most recently used points
+    Lru(usize),
+    /// Every point of a grid with cells of `resolution` size, up to `size` cells before the
+    /// grid is cleared. Points are sampled at the center of their cell, so the output is
+    /// quantized to the grid.
+    Grid { resolution: T, size: usize },
+}
 
-                self.store[key] = Some(CacheValue {
-                    x: coords.0,
-                    y: coords.1,
-                    z: coords.2,
-                    value: sampler(&mut self.source),
-                });
+impl<T: Float> CacheStrategy<T> {
+    /// Returns an error if the grid has cells of size 0 or less.
+    pub fn check(&self) -> Result<(), String> {
+        match *self {
+            Self::Grid { resolution, .. } if resolution > T::ZERO => Ok(()),
+            Self::Grid { resolution, .. } => Err(format!(
+                "Cache resolution must be greater than 0, got {}",
+                resolution
+            )),
+            _ => Ok(()),
+        }
+    }
+}
 
-                self.store[key].unwrap().value
+impl<T: Float> fmt::Display for CacheStrategy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Last => write!(f, "last"),
+            Self::Lru(size) => write!(f, "lru, size = {}", size),
+            Self::Grid { resolution, size } => {
+                write!(f, "grid, resolution = {}, size = {}", resolution, size)
             }
         }
+    }
+}
+
+/// How close a point has to be to a cached point for the cached value to be used.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize, Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CacheTolerance<T: Float> {
+    /// Coordinates differ by less than the value
+    Absolute(T),
+    /// Coordinates differ by less than the value times the larger of the two
+    Relative(T),
+    /// Coordinates fall in the same cell of a grid with cells of the value's size
+    Quantized(T),
+}
+
+impl<T: Float> Default for CacheTolerance<T> {
+    fn default() -> Self {
+        Self::Absolute(T::EPSILON)
+    }
+}
+
+impl<T: Float> CacheTolerance<T> {
+    pub fn matches(&self, a: T, b: T) -> bool {
+        match *self {
+            Self::Absolute(e) => (a - b).abs() < e,
+            Self::Relative(e) => a == b || (a - b).abs() < e * a.abs().max(b.abs()),
+            Self::Quantized(e) => (a / e).floor() == (b / e).floor(),
+        }
+    }
+
+    /// Returns an error if the tolerance is quantized to cells of size 0 or less.
+    pub fn check(&self) -> Result<(), String> {
+        match *self {
+            Self::Quantized(e) if e > T::ZERO => Ok(()),
+            Self::Quantized(e) => Err(format!("Cache tolerance must be greater than 0, got {}", e)),
+            _ => Ok(()),
+        }
+    }
+
+    fn matches_point(&self, v: &CacheValue<T>, coords: (T, T, T)) -> bool {
+        self.matches(v.x, coords.0) && self.matches(v.y, coords.1) && self.matches(v.z, coords.2)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Cache<T: Float> {
+    pub(crate) store: [Option<CacheValue<T>>; MAX_CACHE_ENTRY],
+    pub(crate) source: TaskSource<T>,
+    pub(crate) strategy: CacheStrategy<T>,
+    pub(crate) tolerance: CacheTolerance<T>,
+    /// Recently used points with the dimension they were sampled in, oldest first
+    pub(crate) entries: VecDeque<(usize, CacheValue<T>)>,
+    pub(crate) cells: BTreeMap<(usize, [i64; 3]), T>,
+}
+
+impl<T: Float> Cache<T> {
+    pub fn strategy(&self) -> CacheStrategy<T> {
+        self.strategy
+    }
+
+    pub fn tolerance(&self) -> CacheTolerance<T> {
+        self.tolerance
+    }
 
-        impl Task for Cache {
-            fn sample_1d(&mut self, x: $type) -> $type {
-                self.eval(CACHE_1D, (x, 0.0, 0.0), |t| t.sample_1d(x))
+    /// Forgets every cached value, so the source is sampled again.
+    pub fn invalidate(&mut self) {
+        self.store = [None; MAX_CACHE_ENTRY];
+        self.entries.clear();
+        self.cells.clear();
+    }
+
+    fn eval<F: Fn(&mut dyn Task<T>, (T, T, T)) -> T>(
+        &mut self,
+        key: usize,
+        coords: (T, T, T),
+        sampler: F,
+    ) -> T {
+        if let Some(v) = self.store[key] {
+            if self.tolerance.matches_point(&v, coords) {
+                return v.value;
             }
+        }
 
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-                self.eval(CACHE_2D, (x, y, 0.0), |t| t.sample_2d(x, y))
+        let value = match self.strategy {
+            CacheStrategy::Last => sampler(&mut self.source, coords),
+            CacheStrategy::Lru(size) => self.eval_lru(key, coords, size, sampler),
+            CacheStrategy::Grid { resolution, size } => {
+                self.eval_grid(key, coords, resolution, size, sampler)
             }
+        };
+
+        self.store[key] = Some(CacheValue {
+            x: coords.0,
+            y: coords.1,
+            z: coords.2,
+            value,
+        });
 
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-                self.eval(CACHE_3D, (x, y, z), |t| t.sample_3d(x, y, z))
+        value
+    }
+
+    fn eval_lru<F: Fn(&mut dyn Task<T>, (T, T, T)) -> T>(
+        &mut self,
+        key: usize,
+        coords: (T, T, T),
+        size: usize,
+        sampler: F,
+    ) -> T {
+        let tolerance = self.tolerance;
+        let found = self
+            .entries
+            .iter()
+            .rposition(|(k, v)| *k == key && tolerance.matches_point(v, coords));
+        let entry = match found {
+            // Searching from the most recent point keeps hits on nearby points cheap to move
+            Some(i) => {
+                let entry = self.entries[i];
+                self.entries.remove(i);
+                entry
+            }
+            None => {
+                let value = sampler(&mut self.source, coords);
+                if size == 0 {
+                    return value;
+                }
+                if self.entries.len() >= size {
+                    self.entries.pop_front();
+                }
+                let (x, y, z) = coords;
+                (key, CacheValue { x, y, z, value })
             }
+        };
+
+        self.entries.push_back(entry);
+        entry.1.value
+    }
+
+    fn eval_grid<F: Fn(&mut dyn Task<T>, (T, T, T)) -> T>(
+        &mut self,
+        key: usize,
+        coords: (T, T, T),
+        resolution: T,
+        size: usize,
+        sampler: F,
+    ) -> T {
+        let cell = |x: T| (x / resolution).round().to_i64();
+        let cell = [cell(coords.0), cell(coords.1), cell(coords.2)];
+        if let Some(value) = self.cells.get(&(key, cell)) {
+            return *value;
         }
-    };
+
+        let center = |i: i64| T::from_i64(i) * resolution;
+        let center = (center(cell[0]), center(cell[1]), center(cell[2]));
+        let value = sampler(&mut self.source, center);
+        if self.cells.len() >= size {
+            self.cells.clear();
+        }
+        self.cells.insert((key, cell), value);
+        value
+    }
+}
+
+impl<T: Float> Task<T> for Cache<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(CACHE_1D, (x, T::ZERO, T::ZERO), |t, (x, _, _)| {
+            t.sample_1d(x)
+        })
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(CACHE_2D, (x, y, T::ZERO), |t, (x, y, _)| t.sample_2d(x, y))
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(CACHE_3D, (x, y, z), |t, (x, y, z)| t.sample_3d(x, y, z))
+    }
 }
 
 pub mod f32 {
-    pub use super::builder::f32::CacheBuilder;
-    use super::{CACHE_1D, CACHE_2D, CACHE_3D, MAX_CACHE_ENTRY};
-    use crate::{
-        math::f32::nearly_eq,
-        task::f32::{Task, TaskSource},
-    };
-    cache_value!(f32);
-    cache!(f32);
+    pub use super::CacheMode;
+    pub type Cache = super::Cache<f32>;
+    pub type CacheBuilder = super::CacheBuilder<f32>;
+    pub type CacheStrategy = super::CacheStrategy<f32>;
+    pub type CacheTolerance = super::CacheTolerance<f32>;
 }
 
 pub mod f64 {
-    pub use super::builder::f64::CacheBuilder;
-    use super::{CACHE_1D, CACHE_2D, CACHE_3D, MAX_CACHE_ENTRY};
-    use crate::{
-        math::f64::nearly_eq,
-        task::f64::{Task, TaskSource},
-    };
-    cache_value!(f64);
-    cache!(f64);
+    pub use super::CacheMode;
+    pub type Cache = super::Cache<f64>;
+    pub type CacheBuilder = super::CacheBuilder<f64>;
+    pub type CacheStrategy = super::CacheStrategy<f64>;
+    pub type CacheTolerance = super::CacheTolerance<f64>;
+}
+
+pub mod fixed {
+    pub use super::CacheMode;
+    use crate::float::Fixed;
+    pub type Cache = super::Cache<Fixed>;
+    pub type CacheBuilder = super::CacheBuilder<Fixed>;
+    pub type CacheStrategy = super::CacheStrategy<Fixed>;
+    pub type CacheTolerance = super::CacheTolerance<Fixed>;
 }
 
 #[cfg(test)]
@@ -95,7 +289,16 @@ mod test {
 
     mod f32 {
         use super::{CACHE_1D, CACHE_2D, CACHE_3D};
-        use crate::task::f32::{CacheBuilder, CacheValue, Task};
+        use crate::task::cache::CacheValue;
+        use crate::task::f32::{
+            CacheBuilder, CacheStrategy, CacheTolerance, Parameter, Task, TaskSource,
+        };
+
+        fn set(source: &TaskSource, value: f32) {
+            if let TaskSource::Parameter(x) = source {
+                x.borrow_mut().set(value);
+            }
+        }
 
         #[test]
         fn value_cached() {
@@ -137,11 +340,133 @@ mod test {
             assert_eq!(result.sample_2d(1.0, 1.0), 1.0);
             assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 1.0);
         }
+
+        #[test]
+        fn lru_cached() {
+            let source: TaskSource = Parameter::new(1.0).into();
+            let mut result = CacheBuilder::new()
+                .source(source.clone())
+                .strategy(CacheStrategy::Lru(2))
+                .build();
+
+            assert_eq!(result.sample_1d(1.0), 1.0);
+            assert_eq!(result.sample_1d(2.0), 1.0);
+            set(&source, 2.0);
+            assert_eq!(result.sample_1d(1.0), 1.0);
+
+            // Sampling 3.0 evicts 2.0, the least recently used point
+            assert_eq!(result.sample_1d(3.0), 2.0);
+            assert_eq!(result.sample_1d(1.0), 1.0);
+            assert_eq!(result.sample_1d(2.0), 2.0);
+        }
+
+        #[test]
+        fn grid_cached() {
+            let source: TaskSource = Parameter::new(1.0).into();
+            let mut result = CacheBuilder::new()
+                .source(source.clone())
+                .strategy(CacheStrategy::Grid {
+                    resolution: 1.0,
+                    size: 2,
+                })
+                .build();
+
+            assert_eq!(result.sample_2d(0.1, 0.2), 1.0);
+            set(&source, 2.0);
+            assert_eq!(result.sample_2d(0.4, -0.3), 1.0);
+            assert_eq!(result.sample_2d(1.2, 0.0), 2.0);
+
+            // The grid is cleared once it holds `size` cells
+            assert_eq!(result.sample_2d(5.0, 5.0), 2.0);
+            assert_eq!(result.sample_2d(0.1, 0.2), 2.0);
+            assert_eq!(result.strategy().to_string(), "grid, resolution = 1, size = 2");
+        }
+
+        #[test]
+        fn tolerance_matches() {
+            let tolerance = CacheTolerance::default();
+            assert!(tolerance.matches(1.0, 1.0));
+            assert!(!tolerance.matches(1000000.0, 1000000.5));
+
+            let tolerance = CacheTolerance::Relative(1e-6);
+            assert!(tolerance.matches(1000000.0, 1000000.5));
+            assert!(!tolerance.matches(1.0, 1.5));
+            assert!(tolerance.matches(0.0, 0.0));
+
+            let tolerance = CacheTolerance::Quantized(0.5);
+            assert!(tolerance.matches(0.125, 0.375));
+            assert!(!tolerance.matches(0.375, 0.625));
+            assert!(tolerance.matches(-0.125, -0.375));
+        }
+
+        #[test]
+        fn check_settings() {
+            let grid = |resolution| CacheStrategy::Grid {
+                resolution,
+                size: 2,
+            };
+            assert!(grid(0.5).check().is_ok());
+            assert!(grid(0.0).check().is_err());
+            assert!(grid(-1.0).check().is_err());
+            assert!(CacheStrategy::Lru(0).check().is_ok());
+
+            assert!(CacheTolerance::Quantized(0.5).check().is_ok());
+            assert!(CacheTolerance::Quantized(0.0).check().is_err());
+            assert!(CacheTolerance::Quantized(-0.5).check().is_err());
+        }
+
+        #[test]
+        #[should_panic(expected = "Cache resolution must be greater than 0")]
+        fn invalid_resolution() {
+            CacheBuilder::new()
+                .source(1.0)
+                .strategy(CacheStrategy::Grid {
+                    resolution: 0.0,
+                    size: 2,
+                })
+                .build();
+        }
+
+        #[test]
+        #[should_panic(expected = "Cache tolerance must be greater than 0")]
+        fn invalid_tolerance() {
+            CacheBuilder::new()
+                .source(1.0)
+                .tolerance(CacheTolerance::Quantized(0.0))
+                .build();
+        }
+
+        #[test]
+        fn tolerance_cached() {
+            let source: TaskSource = Parameter::new(1.0).into();
+            let mut result = CacheBuilder::new()
+                .source(source.clone())
+                .tolerance(CacheTolerance::Quantized(0.5))
+                .build();
+
+            assert_eq!(result.sample_2d(0.125, 0.125), 1.0);
+            set(&source, 2.0);
+            assert_eq!(result.sample_2d(0.375, 0.25), 1.0);
+            assert_eq!(result.sample_2d(0.625, 0.25), 2.0);
+
+            set(&source, 3.0);
+            result.invalidate();
+            assert_eq!(result.sample_2d(0.625, 0.25), 3.0);
+        }
     }
 
     mod f64 {
         use super::{CACHE_1D, CACHE_2D, CACHE_3D};
-        use crate::task::f64::{CacheBuilder, CacheValue, Task};
+        use crate::task::cache::CacheValue;
+        use crate::task::f64::{
+            CacheBuilder, CacheStrategy, CacheTolerance, Parameter, Task, TaskSource,
+        };
+
+        fn set(source: &TaskSource, value: f64) {
+            if let TaskSource::Parameter(x) = source {
+                x.borrow_mut().set(value);
+            }
+        }
 
         #[test]
         fn value_cached() {
@@ -183,5 +508,118 @@ mod test {
             assert_eq!(result.sample_2d(1.0, 1.0), 1.0);
             assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 1.0);
         }
+
+        #[test]
+        fn lru_cached() {
+            let source: TaskSource = Parameter::new(1.0).into();
+            let mut result = CacheBuilder::new()
+                .source(source.clone())
+                .strategy(CacheStrategy::Lru(2))
+                .build();
+
+            assert_eq!(result.sample_1d(1.0), 1.0);
+            assert_eq!(result.sample_1d(2.0), 1.0);
+            set(&source, 2.0);
+            assert_eq!(result.sample_1d(1.0), 1.0);
+
+            // Sampling 3.0 evicts 2.0, the least recently used point
+            assert_eq!(result.sample_1d(3.0), 2.0);
+            assert_eq!(result.sample_1d(1.0), 1.0);
+            assert_eq!(result.sample_1d(2.0), 2.0);
+        }
+
+        #[test]
+        fn grid_cached() {
+            let source: TaskSource = Parameter::new(1.0).into();
+            let mut result = CacheBuilder::new()
+                .source(source.clone())
+                .strategy(CacheStrategy::Grid {
+                    resolution: 1.0,
+                    size: 2,
+                })
+                .build();
+
+            assert_eq!(result.sample_2d(0.1, 0.2), 1.0);
+            set(&source, 2.0);
+            assert_eq!(result.sample_2d(0.4, -0.3), 1.0);
+            assert_eq!(result.sample_2d(1.2, 0.0), 2.0);
+
+            // The grid is cleared once it holds `size` cells
+            assert_eq!(result.sample_2d(5.0, 5.0), 2.0);
+            assert_eq!(result.sample_2d(0.1, 0.2), 2.0);
+            assert_eq!(result.strategy().to_string(), "grid, resolution = 1, size = 2");
+        }
+
+        #[test]
+        fn tolerance_matches() {
+            let tolerance = CacheTolerance::default();
+            assert!(tolerance.matches(1.0, 1.0));
+            assert!(!tolerance.matches(1000000.0, 1000000.5));
+
+            let tolerance = CacheTolerance::Relative(1e-6);
+            assert!(tolerance.matches(1000000.0, 1000000.5));
+            assert!(!tolerance.matches(1.0, 1.5));
+            assert!(tolerance.matches(0.0, 0.0));
+
+            let tolerance = CacheTolerance::Quantized(0.5);
+            assert!(tolerance.matches(0.125, 0.375));
+            assert!(!tolerance.matches(0.375, 0.625));
+            assert!(tolerance.matches(-0.125, -0.375));
+        }
+
+        #[test]
+        fn check_settings() {
+            let grid = |resolution| CacheStrategy::Grid {
+                resolution,
+                size: 2,
+            };
+            assert!(grid(0.5).check().is_ok());
+            assert!(grid(0.0).check().is_err());
+            assert!(grid(-1.0).check().is_err());
+            assert!(CacheStrategy::Lru(0).check().is_ok());
+
+            assert!(CacheTolerance::Quantized(0.5).check().is_ok());
+            assert!(CacheTolerance::Quantized(0.0).check().is_err());
+            assert!(CacheTolerance::Quantized(-0.5).check().is_err());
+        }
+
+        #[test]
+        #[should_panic(expected = "Cache resolution must be greater than 0")]
+        fn invalid_resolution() {
+            CacheBuilder::new()
+                .source(1.0)
+                .strategy(CacheStrategy::Grid {
+                    resolution: 0.0,
+                    size: 2,
+                })
+                .build();
+        }
+
+        #[test]
+        #[should_panic(expected = "Cache tolerance must be greater than 0")]
+        fn invalid_tolerance() {
+            CacheBuilder::new()
+                .source(1.0)
+                .tolerance(CacheTolerance::Quantized(0.0))
+                .build();
+        }
+
+        #[test]
+        fn tolerance_cached() {
+            let source: TaskSource = Parameter::new(1.0).into();
+            let mut result = CacheBuilder::new()
+                .source(source.clone())
+                .tolerance(CacheTolerance::Quantized(0.5))
+                .build();
+
+            assert_eq!(result.sample_2d(0.125, 0.125), 1.0);
+            set(&source, 2.0);
+            assert_eq!(result.sample_2d(0.375, 0.25), 1.0);
+            assert_eq!(result.sample_2d(0.625, 0.25), 2.0);
+
+            set(&source, 3.0);
+            result.invalidate();
+            assert_eq!(result.sample_2d(0.625, 0.25), 3.0);
+        }
     }
 }
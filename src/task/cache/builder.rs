@@ -1,74 +1,78 @@
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::string::String;
+
+use super::{Cache, CacheStrategy, CacheTolerance};
+use crate::float::Float;
+use crate::task::{named_to_task, NameOrSource, TaskSource, TaskTree};
+
 pub const MAX_CACHE_ENTRY: usize = 3;
 
-macro_rules! cache_builder {
-    ($type: ty) => {
-        pub struct CacheBuilder {
-            source: NameOrSource,
-        }
+pub struct CacheBuilder<T: Float> {
+    source: NameOrSource<T>,
+    strategy: CacheStrategy<T>,
+    tolerance: CacheTolerance<T>,
+}
 
-        impl Default for CacheBuilder {
-            fn default() -> Self {
-                Self {
-                    source: NameOrSource::Source(0.0.into()),
-                }
-            }
+impl<T: Float> Default for CacheBuilder<T> {
+    fn default() -> Self {
+        Self {
+            source: NameOrSource::Source(T::ZERO.into()),
+            strategy: CacheStrategy::default(),
+            tolerance: CacheTolerance::default(),
         }
+    }
+}
 
-        impl CacheBuilder {
-            pub fn new() -> Self {
-                Self::default()
-            }
+impl<T: Float> CacheBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-            pub fn build(&self) -> Cache {
-                Cache {
-                    store: [None; MAX_CACHE_ENTRY],
-                    source: match &self.source {
-                        NameOrSource::Source(x) => x.clone(),
-                        _ => panic!(
-                            "CacheBuilder::link must be called if CacheBuilder::source is used"
-                        ),
-                    },
-                }
-            }
+    /// Panics if the strategy or the tolerance is invalid, see [`CacheStrategy::check`] and
+    /// [`CacheTolerance::check`].
+    pub fn build(&self) -> Cache<T> {
+        if let Err(x) = self.strategy.check().and(self.tolerance.check()) {
+            panic!("{}", x);
+        }
 
-            /// Link named tasks to their task tree values
-            pub fn link(&mut self, tree: &TaskTree) -> &mut Self {
-                match &self.source {
-                    NameOrSource::Named(name) => {
-                        if let Some(task) = tree.get(name) {
-                            self.source = NameOrSource::Source(task.clone());
-                        }
-                    }
-                    _ => {}
-                }
+        Cache {
+            store: [None; MAX_CACHE_ENTRY],
+            source: match &self.source {
+                NameOrSource::Source(x) => x.clone(),
+                _ => panic!("CacheBuilder::link must be called if CacheBuilder::source is used"),
+            },
+            strategy: self.strategy,
+            tolerance: self.tolerance,
+            entries: VecDeque::new(),
+            cells: BTreeMap::new(),
+        }
+    }
 
-                self
-            }
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        named_to_task!(self.source, tree);
 
-            pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.source = NameOrSource::Named(name.into());
-                self
-            }
+        self
+    }
 
-			#[allow(dead_code)]
-            pub fn source<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.source = NameOrSource::Source(task.into());
-                self
-            }
-        }
-    };
-}
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
 
-pub mod f32 {
-    use super::MAX_CACHE_ENTRY;
-    use crate::task::task::f32::{NameOrSource, TaskSource};
-    use crate::task::{cache::f32::Cache, task_tree::f32::TaskTree};
-    cache_builder!(f32);
-}
+    pub fn strategy(&mut self, strategy: CacheStrategy<T>) -> &mut Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn tolerance(&mut self, tolerance: CacheTolerance<T>) -> &mut Self {
+        self.tolerance = tolerance;
+        self
+    }
 
-pub mod f64 {
-    use super::MAX_CACHE_ENTRY;
-    use crate::task::task::f64::{NameOrSource, TaskSource};
-    use crate::task::{cache::f64::Cache, task_tree::f64::TaskTree};
-    cache_builder!(f64);
+    #[allow(dead_code)]
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
 }
@@ -0,0 +1,102 @@
+mod builder;
+
+use crate::float::Float;
+use crate::math;
+use crate::task::{Task, TaskSource};
+pub use builder::ClampBuilder;
+
+/// Task clamping its source between `min` and `max`.
+#[derive(Clone, Debug)]
+pub struct Clamp<T: Float> {
+    pub(crate) max: TaskSource<T>,
+    pub(crate) min: TaskSource<T>,
+    pub(crate) source: TaskSource<T>,
+}
+
+impl<T: Float> Clamp<T> {
+    fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+        let v = sampler(&mut self.source);
+        let min = sampler(&mut self.min);
+        let max = sampler(&mut self.max);
+        math::clamp(v, min, max)
+    }
+}
+
+impl<T: Float> Task<T> for Clamp<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
+}
+
+pub mod f32 {
+    pub type Clamp = super::Clamp<f32>;
+    pub type ClampBuilder = super::ClampBuilder<f32>;
+}
+
+pub mod f64 {
+    pub type Clamp = super::Clamp<f64>;
+    pub type ClampBuilder = super::ClampBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Clamp = super::Clamp<Fixed>;
+    pub type ClampBuilder = super::ClampBuilder<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{ClampBuilder, Task};
+        #[test]
+        fn clamp_tests() {
+            let mut result = ClampBuilder::new().min(-0.2).max(1.0).source(-0.5).build();
+            assert_eq!(result.sample_1d(1.0), -0.2);
+            assert_eq!(result.sample_2d(1.0, 1.0), -0.2);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), -0.2);
+
+            let mut result = ClampBuilder::new().min(-0.2).max(1.0).source(2.0).build();
+            assert_eq!(result.sample_1d(1.0), 1.0);
+
+            let mut result = ClampBuilder::new().min(-0.2).max(1.0).source(0.5).build();
+            assert_eq!(result.sample_1d(1.0), 0.5);
+        }
+
+        #[test]
+        fn clamp_default_range() {
+            let mut result = ClampBuilder::new().source(-3.0).build();
+            assert_eq!(result.sample_1d(1.0), -1.0);
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{ClampBuilder, Task};
+        #[test]
+        fn clamp_tests() {
+            let mut result = ClampBuilder::new().min(-0.2).max(1.0).source(-0.5).build();
+            assert_eq!(result.sample_1d(1.0), -0.2);
+            assert_eq!(result.sample_2d(1.0, 1.0), -0.2);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), -0.2);
+
+            let mut result = ClampBuilder::new().min(-0.2).max(1.0).source(2.0).build();
+            assert_eq!(result.sample_1d(1.0), 1.0);
+
+            let mut result = ClampBuilder::new().min(-0.2).max(1.0).source(0.5).build();
+            assert_eq!(result.sample_1d(1.0), 0.5);
+        }
+
+        #[test]
+        fn clamp_default_range() {
+            let mut result = ClampBuilder::new().source(-3.0).build();
+            assert_eq!(result.sample_1d(1.0), -1.0);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use alloc::string::String;
+
+use super::Clamp;
+use crate::float::Float;
+use crate::task::{named_to_task, source_or_message, NameOrSource, TaskSource, TaskTree};
+
+pub struct ClampBuilder<T: Float> {
+    max: NameOrSource<T>,
+    min: NameOrSource<T>,
+    source: NameOrSource<T>,
+}
+
+impl<T: Float> Default for ClampBuilder<T> {
+    fn default() -> Self {
+        Self {
+            max: NameOrSource::Source(T::ONE.into()),
+            min: NameOrSource::Source((-T::ONE).into()),
+            source: NameOrSource::Source(T::ZERO.into()),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> ClampBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.max = NameOrSource::Source(task.into());
+        self
+    }
+
+    pub fn min<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.min = NameOrSource::Source(task.into());
+        self
+    }
+
+    pub fn build(&self) -> Clamp<T> {
+        Clamp {
+            max: source_or_message!(self.max, ClampBuilder<T>),
+            min: source_or_message!(self.min, ClampBuilder<T>),
+            source: source_or_message!(self.source, ClampBuilder<T>),
+        }
+    }
+
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        named_to_task!(self.max, tree);
+        named_to_task!(self.min, tree);
+        named_to_task!(self.source, tree);
+
+        self
+    }
+
+    pub fn named_max<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.max = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_min<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.min = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
+}
@@ -0,0 +1,330 @@
+mod builder;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::float::Float;
+use crate::math::lerp;
+use crate::task::{Task, TaskSource};
+pub use builder::CurveBuilder;
+
+/// How a [`Curve`] interpolates between its control points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize, Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Interpolation {
+    /// Straight lines between the points
+    #[default]
+    Linear,
+    /// Smooth curve through the points, which can overshoot between them
+    CatmullRom,
+    /// Smooth curve that never overshoots, so it rises or falls wherever the points do
+    MonotoneCubic,
+}
+
+/// Task remapping its source through a piecewise curve.
+///
+/// The control points are `[in, out]` pairs sorted by input. Sources below the first or
+/// above the last point map to the output of that point, a curve without points returns
+/// its source unchanged.
+#[derive(Clone, Debug)]
+pub struct Curve<T: Float> {
+    pub(crate) interpolation: Interpolation,
+    pub(crate) points: Vec<[T; 2]>,
+    pub(crate) source: TaskSource<T>,
+    /// Tangent at every point for monotone cubic interpolation
+    tangents: Vec<T>,
+}
+
+impl<T: Float> Curve<T> {
+    pub fn new(interpolation: Interpolation, points: Vec<[T; 2]>, source: TaskSource<T>) -> Self {
+        let mut points = points;
+        points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap_or(Ordering::Equal));
+        let tangents = match interpolation {
+            Interpolation::MonotoneCubic => monotone_tangents(&points),
+            _ => vec![],
+        };
+
+        Self {
+            interpolation,
+            points,
+            source,
+            tangents,
+        }
+    }
+
+    pub(crate) fn remap(&self, x: T) -> T {
+        let points = &self.points;
+        let i = points.partition_point(|p| p[0] <= x);
+        if points.is_empty() {
+            return x;
+        } else if i == 0 {
+            return points[0][1];
+        } else if i == points.len() {
+            return points[i - 1][1];
+        }
+
+        interpolate_segment(self.interpolation, points, &self.tangents, i, x)
+    }
+
+    fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+        let v = sampler(&mut self.source);
+        self.remap(v)
+    }
+}
+
+/// Interpolates `x` between `points[i - 1]` and `points[i]`.
+///
+/// `tangents` come from [`monotone_tangents`] and are only used by monotone cubic interpolation.
+pub(crate) fn interpolate_segment<T: Float>(
+    interpolation: Interpolation,
+    points: &[[T; 2]],
+    tangents: &[T],
+    i: usize,
+    x: T,
+) -> T {
+    let [x0, y0] = points[i - 1];
+    let [x1, y1] = points[i];
+    let t = (x - x0) / (x1 - x0);
+    match interpolation {
+        Interpolation::Linear => lerp(y0, y1, t),
+        Interpolation::CatmullRom => {
+            let before = points[i.saturating_sub(2)][1];
+            let after = points[(i + 1).min(points.len() - 1)][1];
+            catmull_rom(before, y0, y1, after, t)
+        }
+        Interpolation::MonotoneCubic => {
+            let h = x1 - x0;
+            hermite(y0, tangents[i - 1] * h, y1, tangents[i] * h, t)
+        }
+    }
+}
+
+/// Uniform Catmull-Rom spline between `p1` and `p2`.
+fn catmull_rom<T: Float>(p0: T, p1: T, p2: T, p3: T, t: T) -> T {
+    let three = T::from_f64(3.0);
+    let a = p1 * T::TWO;
+    let b = p2 - p0;
+    let c = p0 * T::TWO - p1 * T::from_f64(5.0) + p2 * T::from_f64(4.0) - p3;
+    let d = (p1 - p2) * three + p3 - p0;
+    (a + (b + (c + d * t) * t) * t) * T::HALF
+}
+
+/// Cubic Hermite spline between `y0` and `y1` with tangents `m0` and `m1` scaled to `t`.
+fn hermite<T: Float>(y0: T, m0: T, y1: T, m1: T, t: T) -> T {
+    let three = T::from_f64(3.0);
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let h00 = T::TWO * t3 - three * t2 + T::ONE;
+    let h10 = t3 - T::TWO * t2 + t;
+    let h01 = three * t2 - T::TWO * t3;
+    let h11 = t3 - t2;
+    h00 * y0 + h10 * m0 + h01 * y1 + h11 * m1
+}
+
+/// Tangents keeping the curve monotone between points, using the Fritsch-Carlson method.
+pub(crate) fn monotone_tangents<T: Float>(points: &[[T; 2]]) -> Vec<T> {
+    let n = points.len();
+    if n < 2 {
+        return vec![T::ZERO; n];
+    }
+
+    let secants: Vec<T> = points
+        .windows(2)
+        .map(|w| {
+            let dx = w[1][0] - w[0][0];
+            if dx == T::ZERO {
+                T::ZERO
+            } else {
+                (w[1][1] - w[0][1]) / dx
+            }
+        })
+        .collect();
+
+    let mut tangents = vec![T::ZERO; n];
+    tangents[0] = secants[0];
+    tangents[n - 1] = secants[n - 2];
+    for i in 1..n - 1 {
+        let (a, b) = (secants[i - 1], secants[i]);
+        tangents[i] = if a * b <= T::ZERO {
+            T::ZERO
+        } else {
+            (a + b) * T::HALF
+        };
+    }
+
+    for (i, &d) in secants.iter().enumerate() {
+        if d == T::ZERO {
+            tangents[i] = T::ZERO;
+            tangents[i + 1] = T::ZERO;
+            continue;
+        }
+        let a = tangents[i] / d;
+        let b = tangents[i + 1] / d;
+        let s = a * a + b * b;
+        let nine = T::from_f64(9.0);
+        if s > nine {
+            let tau = T::from_f64(3.0) / s.sqrt();
+            tangents[i] = tau * a * d;
+            tangents[i + 1] = tau * b * d;
+        }
+    }
+    tangents
+}
+
+impl<T: Float> Task<T> for Curve<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
+}
+
+pub mod f32 {
+    pub use super::Interpolation;
+    pub type Curve = super::Curve<f32>;
+    pub type CurveBuilder = super::CurveBuilder<f32>;
+}
+
+pub mod f64 {
+    pub use super::Interpolation;
+    pub type Curve = super::Curve<f64>;
+    pub type CurveBuilder = super::CurveBuilder<f64>;
+}
+
+pub mod fixed {
+    pub use super::Interpolation;
+    use crate::float::Fixed;
+    pub type Curve = super::Curve<Fixed>;
+    pub type CurveBuilder = super::CurveBuilder<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{CurveBuilder, Interpolation, Task};
+
+        fn curve(interpolation: Interpolation, source: f32) -> f32 {
+            CurveBuilder::new()
+                .interpolation(interpolation)
+                .add_point(1.0, 1.0)
+                .add_point(-1.0, -1.0)
+                .add_point(0.0, 0.5)
+                .add_point(0.5, 0.5)
+                .source(source)
+                .build()
+                .sample_1d(0.0)
+        }
+
+        #[test]
+        fn curve_linear() {
+            assert_eq!(curve(Interpolation::Linear, -2.0), -1.0);
+            assert_eq!(curve(Interpolation::Linear, -0.5), -0.25);
+            assert_eq!(curve(Interpolation::Linear, 0.25), 0.5);
+            assert_eq!(curve(Interpolation::Linear, 0.75), 0.75);
+            assert_eq!(curve(Interpolation::Linear, 2.0), 1.0);
+        }
+
+        #[test]
+        fn curve_passes_through_points() {
+            for interpolation in [
+                Interpolation::Linear,
+                Interpolation::CatmullRom,
+                Interpolation::MonotoneCubic,
+            ] {
+                for [x, y] in [[-1.0, -1.0], [0.0, 0.5], [0.5, 0.5], [1.0, 1.0]] {
+                    assert_eq!(curve(interpolation, x), y);
+                }
+            }
+        }
+
+        #[test]
+        fn curve_monotone_cubic_does_not_overshoot() {
+            // Catmull-Rom overshoots on the flat part, monotone cubic stays flat
+            assert!(curve(Interpolation::CatmullRom, 0.25) > 0.5);
+            assert_eq!(curve(Interpolation::MonotoneCubic, 0.25), 0.5);
+
+            let mut last = -1.0;
+            for i in 0..=100 {
+                let v = curve(Interpolation::MonotoneCubic, i as f32 / 50.0 - 1.0);
+                assert!(v >= last, "{} < {}", v, last);
+                last = v;
+            }
+        }
+
+        #[test]
+        fn curve_without_points() {
+            let mut result = CurveBuilder::new().source(0.3).build();
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.3);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.3);
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{CurveBuilder, Interpolation, Task};
+
+        fn curve(interpolation: Interpolation, source: f64) -> f64 {
+            CurveBuilder::new()
+                .interpolation(interpolation)
+                .points(vec![[-1.0, -1.0], [0.0, 0.5], [0.5, 0.5], [1.0, 1.0]])
+                .source(source)
+                .build()
+                .sample_1d(0.0)
+        }
+
+        #[test]
+        fn curve_linear() {
+            assert_eq!(curve(Interpolation::Linear, -2.0), -1.0);
+            assert_eq!(curve(Interpolation::Linear, -0.5), -0.25);
+            assert_eq!(curve(Interpolation::Linear, 0.25), 0.5);
+            assert_eq!(curve(Interpolation::Linear, 0.75), 0.75);
+            assert_eq!(curve(Interpolation::Linear, 2.0), 1.0);
+        }
+
+        #[test]
+        fn curve_passes_through_points() {
+            for interpolation in [
+                Interpolation::Linear,
+                Interpolation::CatmullRom,
+                Interpolation::MonotoneCubic,
+            ] {
+                for [x, y] in [[-1.0, -1.0], [0.0, 0.5], [0.5, 0.5], [1.0, 1.0]] {
+                    assert_eq!(curve(interpolation, x), y);
+                }
+            }
+        }
+
+        #[test]
+        fn curve_monotone_cubic_does_not_overshoot() {
+            // Catmull-Rom overshoots on the flat part, monotone cubic stays flat
+            assert!(curve(Interpolation::CatmullRom, 0.25) > 0.5);
+            assert_eq!(curve(Interpolation::MonotoneCubic, 0.25), 0.5);
+
+            let mut last = -1.0;
+            for i in 0..=100 {
+                let v = curve(Interpolation::MonotoneCubic, i as f64 / 50.0 - 1.0);
+                assert!(v >= last, "{} < {}", v, last);
+                last = v;
+            }
+        }
+
+        #[test]
+        fn curve_without_points() {
+            let mut result = CurveBuilder::new().source(0.3).build();
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.3);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.3);
+        }
+    }
+}
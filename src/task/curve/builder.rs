@@ -0,0 +1,72 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Curve, Interpolation};
+use crate::float::Float;
+use crate::task::{named_to_task, source_or_message, NameOrSource, TaskSource, TaskTree};
+
+pub struct CurveBuilder<T: Float> {
+    interpolation: Interpolation,
+    points: Vec<[T; 2]>,
+    source: NameOrSource<T>,
+}
+
+impl<T: Float> Default for CurveBuilder<T> {
+    fn default() -> Self {
+        Self {
+            interpolation: Interpolation::Linear,
+            points: vec![],
+            source: NameOrSource::Source(T::ZERO.into()),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> CurveBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a control point mapping `input` to `output`, points can be added in any order.
+    pub fn add_point(&mut self, input: T, output: T) -> &mut Self {
+        self.points.push([input, output]);
+        self
+    }
+
+    pub fn build(&self) -> Curve<T> {
+        Curve::new(
+            self.interpolation,
+            self.points.clone(),
+            source_or_message!(self.source, CurveBuilder<T>),
+        )
+    }
+
+    pub fn interpolation(&mut self, interpolation: Interpolation) -> &mut Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        named_to_task!(self.source, tree);
+
+        self
+    }
+
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
+
+    /// Replaces the control points with `[in, out]` pairs.
+    pub fn points(&mut self, points: Vec<[T; 2]>) -> &mut Self {
+        self.points = points;
+        self
+    }
+
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
+}
@@ -0,0 +1,229 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+
+/// Task type defined outside the crate, added to a tree with [`TaskSource::custom`].
+///
+/// Only sampling is required, the other methods let graphs, optimizations and cache
+/// invalidation see the inputs and settings of the task.
+pub trait CustomTask<T: Float>: Task<T> + Debug {
+    /// Name of the task type, shown as its `type` in graphs.
+    fn kind(&self) -> &'static str;
+
+    /// The settings of the task that aren't inputs, formatted for display.
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+
+    /// Calls `f` with every task this task samples and the name of its field.
+    ///
+    /// Inputs that aren't visited are never simplified and their caches aren't cleared by
+    /// `TaskTree::invalidate`.
+    fn visit_inputs_mut(&mut self, f: &mut dyn FnMut(&str, &mut TaskSource<T>)) {
+        let _ = f;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::Float;
+    use crate::task::{CustomTask, Task, TaskSource};
+
+    /// Custom task returning 1 where its source is above `level` and 0 elsewhere.
+    #[derive(Debug)]
+    struct Step<T: Float> {
+        level: T,
+        source: TaskSource<T>,
+    }
+
+    impl<T: Float> Step<T> {
+        fn step(&self, value: T) -> T {
+            if value > self.level {
+                T::ONE
+            } else {
+                T::ZERO
+            }
+        }
+    }
+
+    impl<T: Float> Task<T> for Step<T> {
+        fn sample_1d(&mut self, x: T) -> T {
+            let value = self.source.sample_1d(x);
+            self.step(value)
+        }
+
+        fn sample_2d(&mut self, x: T, y: T) -> T {
+            let value = self.source.sample_2d(x, y);
+            self.step(value)
+        }
+
+        fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+            let value = self.source.sample_3d(x, y, z);
+            self.step(value)
+        }
+    }
+
+    impl<T: Float> CustomTask<T> for Step<T> {
+        fn kind(&self) -> &'static str {
+            "step"
+        }
+
+        fn params(&self) -> Vec<(&'static str, String)> {
+            vec![("level", self.level.to_string())]
+        }
+
+        fn visit_inputs_mut(&mut self, f: &mut dyn FnMut(&str, &mut TaskSource<T>)) {
+            f("source", &mut self.source);
+        }
+    }
+
+    mod f32 {
+        use super::Step;
+        use crate::task::f32::{CacheBuilder, Parameter, Task, TaskSource, TaskTree};
+
+        #[test]
+        fn custom_task_in_tree() {
+            let mut tree = TaskTree::new();
+            tree.add_task("height", Parameter::new(0.25));
+            let height = tree.get("height").unwrap().clone();
+            tree.add_task(
+                "step",
+                TaskSource::custom(Step {
+                    level: 0.5,
+                    source: height,
+                }),
+            );
+
+            assert_eq!(tree.sample_2d("step", 1.0, 1.0), 0.0);
+            tree.set_param("height", 0.75);
+            assert_eq!(tree.sample_2d("step", 1.0, 1.0), 1.0);
+            assert_eq!(tree.dependencies("step"), vec!["height".to_owned()]);
+
+            let step = tree.get("step").unwrap();
+            assert_eq!(step.kind(), "custom");
+            assert_eq!(
+                step.params(),
+                vec![("type", "step".to_owned()), ("level", "0.5".to_owned())]
+            );
+        }
+
+        #[test]
+        fn custom_task_inputs_are_invalidated() {
+            let mut tree = TaskTree::new();
+            tree.add_task("height", Parameter::new(0.25));
+            tree.add_task(
+                "cached",
+                CacheBuilder::new()
+                    .named_source("height")
+                    .link(&tree)
+                    .build(),
+            );
+            let cached = tree.get("cached").unwrap().clone();
+            tree.add_task(
+                "step",
+                TaskSource::custom(Step {
+                    level: 0.5,
+                    source: cached,
+                }),
+            );
+
+            assert_eq!(tree.sample_1d("step", 1.0), 0.0);
+            tree.set_param("height", 0.75);
+            assert_eq!(tree.sample_1d("step", 1.0), 1.0);
+        }
+
+        #[test]
+        fn custom_task_is_kept_by_optimize() {
+            let mut tree = TaskTree::new();
+            tree.add_task(
+                "step",
+                TaskSource::custom(Step {
+                    level: 0.5,
+                    source: 0.75.into(),
+                }),
+            );
+            tree.optimize();
+
+            let mut step = tree.get("step").unwrap().clone();
+            assert_eq!(step.kind(), "custom");
+            assert_eq!(step.sample_3d(1.0, 1.0, 1.0), 1.0);
+        }
+    }
+
+    mod f64 {
+        use super::Step;
+        use crate::task::f64::{CacheBuilder, Parameter, Task, TaskSource, TaskTree};
+
+        #[test]
+        fn custom_task_in_tree() {
+            let mut tree = TaskTree::new();
+            tree.add_task("height", Parameter::new(0.25));
+            let height = tree.get("height").unwrap().clone();
+            tree.add_task(
+                "step",
+                TaskSource::custom(Step {
+                    level: 0.5,
+                    source: height,
+                }),
+            );
+
+            assert_eq!(tree.sample_2d("step", 1.0, 1.0), 0.0);
+            tree.set_param("height", 0.75);
+            assert_eq!(tree.sample_2d("step", 1.0, 1.0), 1.0);
+            assert_eq!(tree.dependencies("step"), vec!["height".to_owned()]);
+
+            let step = tree.get("step").unwrap();
+            assert_eq!(step.kind(), "custom");
+            assert_eq!(
+                step.params(),
+                vec![("type", "step".to_owned()), ("level", "0.5".to_owned())]
+            );
+        }
+
+        #[test]
+        fn custom_task_inputs_are_invalidated() {
+            let mut tree = TaskTree::new();
+            tree.add_task("height", Parameter::new(0.25));
+            tree.add_task(
+                "cached",
+                CacheBuilder::new()
+                    .named_source("height")
+                    .link(&tree)
+                    .build(),
+            );
+            let cached = tree.get("cached").unwrap().clone();
+            tree.add_task(
+                "step",
+                TaskSource::custom(Step {
+                    level: 0.5,
+                    source: cached,
+                }),
+            );
+
+            assert_eq!(tree.sample_1d("step", 1.0), 0.0);
+            tree.set_param("height", 0.75);
+            assert_eq!(tree.sample_1d("step", 1.0), 1.0);
+        }
+
+        #[test]
+        fn custom_task_is_kept_by_optimize() {
+            let mut tree = TaskTree::new();
+            tree.add_task(
+                "step",
+                TaskSource::custom(Step {
+                    level: 0.5,
+                    source: 0.75.into(),
+                }),
+            );
+            tree.optimize();
+
+            let mut step = tree.get("step").unwrap().clone();
+            assert_eq!(step.kind(), "custom");
+            assert_eq!(step.sample_3d(1.0, 1.0, 1.0), 1.0);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+mod builder;
+
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+pub use builder::ExponentBuilder;
+
+/// Task raising its source to the power of `exponent` in `[0, 1]`.
+///
+/// The source is mapped from `[-1, 1]` to `[0, 1]` first and back afterwards, so the result
+/// keeps the range of the noise.
+#[derive(Clone, Debug)]
+pub struct Exponent<T: Float> {
+    pub(crate) exponent: TaskSource<T>,
+    pub(crate) source: TaskSource<T>,
+}
+
+impl<T: Float> Exponent<T> {
+    fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+        let v = sampler(&mut self.source);
+        let e = sampler(&mut self.exponent);
+        ((v + T::ONE) * T::HALF).abs().powf(e) * T::TWO - T::ONE
+    }
+}
+
+impl<T: Float> Task<T> for Exponent<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
+}
+
+pub mod f32 {
+    pub type Exponent = super::Exponent<f32>;
+    pub type ExponentBuilder = super::ExponentBuilder<f32>;
+}
+
+pub mod f64 {
+    pub type Exponent = super::Exponent<f64>;
+    pub type ExponentBuilder = super::ExponentBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Exponent = super::Exponent<Fixed>;
+    pub type ExponentBuilder = super::ExponentBuilder<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{ExponentBuilder, Task};
+        #[test]
+        fn exponent_square() {
+            let mut result = ExponentBuilder::new().exponent(2.0).source(0.0).build();
+            assert_eq!(result.sample_1d(1.0), -0.5);
+            assert_eq!(result.sample_2d(1.0, 1.0), -0.5);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), -0.5);
+
+            let mut result = ExponentBuilder::new().exponent(2.0).source(1.0).build();
+            assert_eq!(result.sample_1d(1.0), 1.0);
+
+            let mut result = ExponentBuilder::new().exponent(2.0).source(-1.0).build();
+            assert_eq!(result.sample_1d(1.0), -1.0);
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{ExponentBuilder, Task};
+        #[test]
+        fn exponent_square() {
+            let mut result = ExponentBuilder::new().exponent(2.0).source(0.0).build();
+            assert_eq!(result.sample_1d(1.0), -0.5);
+            assert_eq!(result.sample_2d(1.0, 1.0), -0.5);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), -0.5);
+
+            let mut result = ExponentBuilder::new().exponent(2.0).source(1.0).build();
+            assert_eq!(result.sample_1d(1.0), 1.0);
+
+            let mut result = ExponentBuilder::new().exponent(2.0).source(-1.0).build();
+            assert_eq!(result.sample_1d(1.0), -1.0);
+        }
+    }
+}
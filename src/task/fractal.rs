@@ -3,9 +3,16 @@ mod builder;
 mod fbm;
 mod ridged_multi;
 
+use alloc::boxed::Box;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::float::Float;
+use crate::source::Noise;
+use crate::task::Task;
+pub use builder::FractalBuilder;
+use ridged_multi::PreCalc;
+
 #[allow(dead_code)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(
@@ -24,134 +31,99 @@ pub enum FractalType {
     RidgedMulti,
 }
 
-macro_rules! scale_bias_type {
-    ($type: ty) => {
-        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Default)]
-        pub struct ScaleBias {
-            pub scale: $type,
-            pub bias: $type,
-        }
-    };
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Default)]
+pub struct ScaleBias<T: Float> {
+    pub scale: T,
+    pub bias: T,
 }
 
-macro_rules! noise_config_type {
-    ($type: ty) => {
-        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
-        pub struct NoiseConfig {
-            pub octaves: u16,
-            pub lacunarity: $type,
-            pub gain: $type,
-            pub frequency: $type,
-            pub amplitude: $type,
-        }
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct NoiseConfig<T: Float> {
+    pub octaves: u16,
+    pub lacunarity: T,
+    pub gain: T,
+    pub frequency: T,
+    pub amplitude: T,
+}
 
-        impl Default for NoiseConfig {
-            fn default() -> Self {
-                NoiseConfig {
-                    octaves: 6,
-                    lacunarity: 2.0,
-                    gain: 0.5,
-                    frequency: 1.0,
-                    amplitude: 1.0,
-                }
-            }
+impl<T: Float> Default for NoiseConfig<T> {
+    fn default() -> Self {
+        NoiseConfig {
+            octaves: 6,
+            lacunarity: T::TWO,
+            gain: T::HALF,
+            frequency: T::ONE,
+            amplitude: T::ONE,
         }
-    };
+    }
 }
 
-macro_rules! fractal_type {
-    ($type: ty) => {
-        #[derive(Debug)]
-        pub struct Fractal {
-            pub(crate) config: NoiseConfig,
-            pub(crate) noise: Box<dyn Noise>,
-            pub(crate) fractal: FractalType,
-            /// Only used in Ridged Multi
-            pub(crate) pre_calc: PreCalc,
-        }
+#[derive(Debug)]
+pub struct Fractal<T: Float> {
+    pub(crate) config: NoiseConfig<T>,
+    pub(crate) noise: Box<dyn Noise<T>>,
+    pub(crate) fractal: FractalType,
+    /// Only used in Ridged Multi
+    pub(crate) pre_calc: PreCalc<T>,
+}
 
-        impl Task for Fractal {
-            fn sample_1d(&mut self, x: $type) -> $type {
-                match self.fractal {
-                    FractalType::Brownian => fbm::sample_1d(&self.config, self.noise.as_mut(), x),
-                    FractalType::Billowing => {
-                        billowing::sample_1d(&self.config, self.noise.as_mut(), x)
-                    }
-                    FractalType::RidgedMulti => ridged_multi::sample_1d(
-                        &self.config,
-                        &self.pre_calc,
-                        self.noise.as_mut(),
-                        x,
-                    ),
-                }
+impl<T: Float> Task<T> for Fractal<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        match self.fractal {
+            FractalType::Brownian => fbm::sample_1d(&self.config, self.noise.as_mut(), x),
+            FractalType::Billowing => billowing::sample_1d(&self.config, self.noise.as_mut(), x),
+            FractalType::RidgedMulti => {
+                ridged_multi::sample_1d(&self.config, &self.pre_calc, self.noise.as_mut(), x)
             }
+        }
+    }
 
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-                match self.fractal {
-                    FractalType::Brownian => {
-                        fbm::sample_2d(&self.config, self.noise.as_mut(), x, y)
-                    }
-                    FractalType::Billowing => {
-                        billowing::sample_2d(&self.config, self.noise.as_mut(), x, y)
-                    }
-                    FractalType::RidgedMulti => ridged_multi::sample_2d(
-                        &self.config,
-                        &self.pre_calc,
-                        self.noise.as_mut(),
-                        x,
-                        y,
-                    ),
-                }
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        match self.fractal {
+            FractalType::Brownian => fbm::sample_2d(&self.config, self.noise.as_mut(), x, y),
+            FractalType::Billowing => billowing::sample_2d(&self.config, self.noise.as_mut(), x, y),
+            FractalType::RidgedMulti => {
+                ridged_multi::sample_2d(&self.config, &self.pre_calc, self.noise.as_mut(), x, y)
             }
+        }
+    }
 
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-                match self.fractal {
-                    FractalType::Brownian => {
-                        fbm::sample_3d(&self.config, self.noise.as_mut(), x, y, z)
-                    }
-                    FractalType::Billowing => {
-                        billowing::sample_3d(&self.config, self.noise.as_mut(), x, y, z)
-                    }
-                    FractalType::RidgedMulti => ridged_multi::sample_3d(
-                        &self.config,
-                        &self.pre_calc,
-                        self.noise.as_mut(),
-                        x,
-                        y,
-                        z,
-                    ),
-                }
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        match self.fractal {
+            FractalType::Brownian => fbm::sample_3d(&self.config, self.noise.as_mut(), x, y, z),
+            FractalType::Billowing => {
+                billowing::sample_3d(&self.config, self.noise.as_mut(), x, y, z)
+            }
+            FractalType::RidgedMulti => {
+                ridged_multi::sample_3d(&self.config, &self.pre_calc, self.noise.as_mut(), x, y, z)
             }
         }
-    };
+    }
 }
 
 pub mod f32 {
-    use super::billowing::f32 as billowing;
-    pub use super::builder::f32::FractalBuilder;
-    use super::fbm::f32 as fbm;
-    use super::ridged_multi::f32 as ridged_multi;
     pub use super::FractalType;
-    use crate::source::f32::Noise;
-    use crate::task::f32::Task;
-    use ridged_multi::PreCalc;
-    scale_bias_type!(f32);
-    noise_config_type!(f32);
-    fractal_type!(f32);
+    pub type Fractal = super::Fractal<f32>;
+    pub type FractalBuilder = super::FractalBuilder<f32>;
+    pub type NoiseConfig = super::NoiseConfig<f32>;
+    pub type ScaleBias = super::ScaleBias<f32>;
 }
 
 pub mod f64 {
-    use super::billowing::f64 as billowing;
-    pub use super::builder::f64::FractalBuilder;
-    use super::fbm::f64 as fbm;
-    use super::ridged_multi::f64 as ridged_multi;
     pub use super::FractalType;
-    use crate::source::f64::Noise;
-    use crate::task::f64::Task;
-    use ridged_multi::PreCalc;
-    scale_bias_type!(f64);
-    noise_config_type!(f64);
-    fractal_type!(f64);
+    pub type Fractal = super::Fractal<f64>;
+    pub type FractalBuilder = super::FractalBuilder<f64>;
+    pub type NoiseConfig = super::NoiseConfig<f64>;
+    pub type ScaleBias = super::ScaleBias<f64>;
+}
+
+pub mod fixed {
+    pub use super::FractalType;
+    use crate::float::Fixed;
+    pub type Fractal = super::Fractal<Fixed>;
+    pub type FractalBuilder = super::FractalBuilder<Fixed>;
+    pub type NoiseConfig = super::NoiseConfig<Fixed>;
+    pub type ScaleBias = super::ScaleBias<Fixed>;
 }
 
 #[cfg(test)]
@@ -1,88 +1,58 @@
-/// Fractal Brownian Motion
+//! Fractal Brownian Motion
 
-macro_rules! eval {
-    ($type: ty) => {
-        fn eval<F: Fn(&mut dyn Noise, $type, u16) -> $type>(
-            config: &NoiseConfig,
-            noise: &mut dyn Noise,
-            sampler: F,
-        ) -> $type {
-            let mut result = 0.0;
-            let mut amp = config.amplitude;
-            let mut freq = config.frequency;
+use super::NoiseConfig;
+use crate::float::Float;
+use crate::source::Noise;
 
-            let mut weight = 0.0;
+fn eval<T: Float, F: Fn(&mut dyn Noise<T>, T, u16) -> T>(
+    config: &NoiseConfig<T>,
+    noise: &mut dyn Noise<T>,
+    sampler: F,
+) -> T {
+    let mut result = T::ZERO;
+    let mut amp = config.amplitude;
+    let mut freq = config.frequency;
 
-            for octave in 0..config.octaves {
-                let tmp = sampler(noise, freq, octave);
-                result += tmp * amp;
+    let mut weight = T::ZERO;
 
-                // used to normalize values generated.
-                weight += amp;
+    for octave in 0..config.octaves {
+        let tmp = sampler(noise, freq, octave);
+        result += tmp * amp;
 
-                freq *= config.lacunarity;
-                amp *= config.gain;
-            }
-            result /= weight;
+        // used to normalize values generated.
+        weight += amp;
 
-            result
-        }
-    };
-}
-
-macro_rules! sample_1d {
-    ($type: ty) => {
-        pub fn sample_1d(config: &NoiseConfig, noise: &mut dyn Noise, x: $type) -> $type {
-            eval(config, noise, |s, f, o| {
-                let o: $type = o as $type;
-                (*s).sample_1d(x * f + o)
-            })
-        }
-    };
-}
+        freq *= config.lacunarity;
+        amp *= config.gain;
+    }
+    result /= weight;
 
-macro_rules! sample_2d {
-    ($type: ty) => {
-        pub fn sample_2d(config: &NoiseConfig, noise: &mut dyn Noise, x: $type, y: $type) -> $type {
-            eval(config, noise, |s, f, o| {
-                let o: $type = o as $type;
-                (*s).sample_2d(x * f + o, y * f + o)
-            })
-        }
-    };
+    result
 }
 
-macro_rules! sample_3d {
-    ($type: ty) => {
-        pub fn sample_3d(
-            config: &NoiseConfig,
-            noise: &mut dyn Noise,
-            x: $type,
-            y: $type,
-            z: $type,
-        ) -> $type {
-            eval(config, noise, |s, f, o| {
-                let o: $type = o as $type;
-                (*s).sample_3d(x * f + o, y * f + o, z * f + o)
-            })
-        }
-    };
+pub fn sample_1d<T: Float>(config: &NoiseConfig<T>, noise: &mut dyn Noise<T>, x: T) -> T {
+    eval(config, noise, |s, f, o| {
+        let o = T::from_usize(o as usize);
+        (*s).sample_1d(x * f + o)
+    })
 }
 
-pub mod f32 {
-    use super::super::f32::NoiseConfig;
-    use crate::source::f32::Noise;
-    eval!(f32);
-    sample_1d!(f32);
-    sample_2d!(f32);
-    sample_3d!(f32);
+pub fn sample_2d<T: Float>(config: &NoiseConfig<T>, noise: &mut dyn Noise<T>, x: T, y: T) -> T {
+    eval(config, noise, |s, f, o| {
+        let o = T::from_usize(o as usize);
+        (*s).sample_2d(x * f + o, y * f + o)
+    })
 }
 
-pub mod f64 {
-    use super::super::f64::NoiseConfig;
-    use crate::source::f64::Noise;
-    eval!(f64);
-    sample_1d!(f64);
-    sample_2d!(f64);
-    sample_3d!(f64);
+pub fn sample_3d<T: Float>(
+    config: &NoiseConfig<T>,
+    noise: &mut dyn Noise<T>,
+    x: T,
+    y: T,
+    z: T,
+) -> T {
+    eval(config, noise, |s, f, o| {
+        let o = T::from_usize(o as usize);
+        (*s).sample_3d(x * f + o, y * f + o, z * f + o)
+    })
 }
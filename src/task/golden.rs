@@ -0,0 +1,569 @@
+//! Golden values sampled from every task type in both precisions and in fixed point.
+//!
+//! Fixed point values must always match bit for bit. Float values must match with the
+//! `deterministic` feature, otherwise `powf` from the platform may move the tasks using it by
+//! a few bits. After an intended change, rewrite the tables below with the new values with
+//! `UPDATE_GOLDEN=1 cargo test --lib --features deterministic golden` and review the diff.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::sync::Mutex;
+
+use crate::float::Float;
+use crate::math::{cubic_curve, quintic_curve};
+use crate::source::Perlin;
+use crate::task::{
+    AbsBuilder, AggregatorBuilder, BiasBuilder, CacheBuilder, CacheStrategy, ClampBuilder,
+    CurveBuilder, DomainOperation, ExponentBuilder, FractalBuilder, FractalType, GradientBuilder,
+    GradientKind, Interpolation, InvertBuilder, MultiGradientBuilder, Operation, Parameter,
+    PowBuilder, ScaleBuilder, ScaleOffsetBuilder, SelectorBuilder, Task, TaskSource,
+    TerraceBuilder, TransformDomainBuilder, TurbulenceBuilder,
+};
+
+/// Points sampled by every case, each in 1D, 2D and 3D.
+const POINTS: [[f64; 3]; 2] = [[0.3, -1.7, 2.9], [13.25, 7.5, -4.125]];
+
+fn value<T: Float>(v: f64) -> T {
+    T::from_f64(v)
+}
+
+fn fractal<T: Float>(fractal: FractalType) -> TaskSource<T> {
+    FractalBuilder::new()
+        .fractal(fractal)
+        .frequency(value(0.35))
+        .source(Box::new(Perlin::new_from_seed(quintic_curve, 42)))
+        .build()
+        .into()
+}
+
+fn gradient<T: Float>() -> TaskSource<T> {
+    GradientBuilder::new()
+        .s1([value(-20.0), value(-15.0), value(-25.0)])
+        .s2([value(20.0), value(25.0), value(15.0)])
+        .build()
+        .into()
+}
+
+/// Gradient centred among the sampled points so none of them saturates.
+fn shaped_gradient<T: Float>(kind: GradientKind) -> TaskSource<T> {
+    GradientBuilder::new()
+        .kind(kind)
+        .radius(value(20.0))
+        .s1([value(2.0), value(1.0), value(-1.0)])
+        .s2([value(6.0), value(9.0), value(7.0)])
+        .build()
+        .into()
+}
+
+/// Displacement noise for turbulence, seeded apart from the other fractals.
+fn warp<T: Float>(seed: u64) -> TaskSource<T> {
+    FractalBuilder::new()
+        .octaves(2)
+        .frequency(value(0.5))
+        .source(Box::new(Perlin::new_from_seed(quintic_curve, seed)))
+        .build()
+        .into()
+}
+
+fn aggregate<T: Float>(op: Operation) -> TaskSource<T> {
+    AggregatorBuilder::new()
+        .operation(op)
+        .initial(value::<T>(0.25))
+        .add_task(fractal(FractalType::Brownian))
+        .add_task(gradient())
+        .build()
+        .into()
+}
+
+fn domain<T: Float>(operation: DomainOperation) -> TaskSource<T> {
+    TransformDomainBuilder::new()
+        .operation(operation)
+        .value_x(value::<T>(1.5))
+        .value_y(gradient())
+        .value_z(value::<T>(-0.75))
+        .source(fractal(FractalType::Brownian))
+        .build()
+        .into()
+}
+
+/// One task of every type, named after its type and settings.
+fn cases<T: Float>() -> Vec<(&'static str, TaskSource<T>)> {
+    let cache = |strategy| {
+        CacheBuilder::new()
+            .strategy(strategy)
+            .source(fractal(FractalType::Brownian))
+            .build()
+            .into()
+    };
+
+    Vec::from([
+        (
+            "abs",
+            AbsBuilder::new()
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+        ("aggregate_add", aggregate(Operation::Add)),
+        ("aggregate_avg", aggregate(Operation::Avg)),
+        ("aggregate_div", aggregate(Operation::Div)),
+        ("aggregate_max", aggregate(Operation::Max)),
+        ("aggregate_min", aggregate(Operation::Min)),
+        ("aggregate_mul", aggregate(Operation::Mul)),
+        ("aggregate_sub", aggregate(Operation::Sub)),
+        (
+            "bias",
+            BiasBuilder::new()
+                .bias(value::<T>(0.7))
+                .min(value(0.5))
+                .max(value(3.0))
+                .source(
+                    ScaleOffsetBuilder::new()
+                        .scale(value::<T>(0.5))
+                        .offset(value::<T>(0.5))
+                        .source(gradient())
+                        .build(),
+                )
+                .build()
+                .into(),
+        ),
+        ("cache_last", cache(CacheStrategy::Last)),
+        ("cache_lru", cache(CacheStrategy::Lru(4))),
+        (
+            "cache_grid",
+            cache(CacheStrategy::Grid {
+                resolution: value(0.5),
+                size: 16,
+            }),
+        ),
+        (
+            "clamp",
+            ClampBuilder::new()
+                .min(value::<T>(-0.1))
+                .max(gradient())
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+        ("constant", TaskSource::Constant(value(0.625))),
+        (
+            "curve",
+            CurveBuilder::new()
+                .interpolation(Interpolation::MonotoneCubic)
+                .add_point(value(-1.0), value(-1.0))
+                .add_point(value(-0.1), value(0.2))
+                .add_point(value(0.2), value(0.3))
+                .add_point(value(1.0), value(1.0))
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+        (
+            "exponent",
+            ExponentBuilder::new()
+                .exponent(value::<T>(2.5))
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+        ("fractal_billowing", fractal(FractalType::Billowing)),
+        ("fractal_brownian", fractal(FractalType::Brownian)),
+        ("fractal_ridged_multi", fractal(FractalType::RidgedMulti)),
+        ("gradient", gradient()),
+        (
+            "gradient_cylindrical",
+            shaped_gradient(GradientKind::Cylindrical),
+        ),
+        ("gradient_radial", shaped_gradient(GradientKind::Radial)),
+        (
+            "gradient_spherical",
+            shaped_gradient(GradientKind::Spherical),
+        ),
+        (
+            "invert",
+            InvertBuilder::new().source(gradient()).build().into(),
+        ),
+        (
+            "multi_gradient",
+            MultiGradientBuilder::new()
+                .kind(GradientKind::Spherical)
+                .radius(value(20.0))
+                .s1([value(2.0), value(1.0), value(-1.0)])
+                .add_stop(value(0.0), value(-1.0), Interpolation::Linear)
+                .add_stop(value(0.3), value(-0.2), Interpolation::MonotoneCubic)
+                .add_stop(value(0.35), value(0.1), Interpolation::CatmullRom)
+                .add_stop(value(0.7), value(0.6), Interpolation::Linear)
+                .add_stop(value(1.0), value(1.0), Interpolation::Linear)
+                .build()
+                .into(),
+        ),
+        ("parameter", Parameter::new(value(-0.375)).into()),
+        (
+            "perlin_unseeded",
+            FractalBuilder::new()
+                .octaves(1)
+                .source(Box::new(Perlin::new(cubic_curve)))
+                .build()
+                .into(),
+        ),
+        (
+            "pow",
+            PowBuilder::new()
+                .exponent(value::<T>(1.5))
+                .source(
+                    ScaleOffsetBuilder::new()
+                        .scale(value::<T>(0.5))
+                        .offset(value::<T>(0.5))
+                        .source(gradient())
+                        .build(),
+                )
+                .build()
+                .into(),
+        ),
+        (
+            "scale",
+            ScaleBuilder::new()
+                .scale(gradient())
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+        (
+            "scale_offset",
+            ScaleOffsetBuilder::new()
+                .scale(value::<T>(1.75))
+                .offset(gradient())
+                .source(fractal(FractalType::Billowing))
+                .build()
+                .into(),
+        ),
+        (
+            "selector",
+            SelectorBuilder::new()
+                .condition(gradient())
+                .lower(fractal(FractalType::Brownian))
+                .upper(fractal(FractalType::RidgedMulti))
+                .falloff(value::<T>(0.2))
+                .threshold(value::<T>(0.45))
+                .build()
+                .into(),
+        ),
+        (
+            "terrace",
+            TerraceBuilder::new()
+                .levels(vec![
+                    value(-1.0),
+                    value(-0.2),
+                    value(0.1),
+                    value(0.5),
+                    value(1.0),
+                ])
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+        (
+            "terrace_sharp_inverted",
+            TerraceBuilder::new()
+                .levels(vec![
+                    value(-1.0),
+                    value(-0.2),
+                    value(0.1),
+                    value(0.5),
+                    value(1.0),
+                ])
+                .smooth(false)
+                .invert(true)
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+        (
+            "transform_domain_affine",
+            TransformDomainBuilder::new()
+                .operation(DomainOperation::Affine)
+                .matrix([
+                    [value(0.8), value(-0.6), value(0.0)],
+                    [value(0.6), value(0.8), value(0.25)],
+                    [value(0.0), value(0.5), value(1.5)],
+                ])
+                .value_x(value::<T>(1.5))
+                .value_y(gradient())
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+        (
+            "transform_domain_operations",
+            TransformDomainBuilder::new()
+                .operation(DomainOperation::Scale)
+                .value_x(value::<T>(1.5))
+                .value_y(value::<T>(0.5))
+                .value_z(value::<T>(1.0))
+                .add_operation(DomainOperation::Rotate)
+                .value_z(value::<T>(30.0))
+                .add_operation(DomainOperation::Warp)
+                .value_x(warp(4))
+                .value_y(warp(5))
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+        ("transform_domain_rotate", domain(DomainOperation::Rotate)),
+        ("transform_domain_scale", domain(DomainOperation::Scale)),
+        (
+            "transform_domain_translate",
+            domain(DomainOperation::Translate),
+        ),
+        (
+            "turbulence",
+            TurbulenceBuilder::new()
+                .dx(warp(1))
+                .dy(warp(2))
+                .dz(warp(3))
+                .power(value::<T>(0.75))
+                .iterations(2)
+                .source(fractal(FractalType::Brownian))
+                .build()
+                .into(),
+        ),
+    ])
+}
+
+/// Samples `task` at every point in 1D, 2D and 3D.
+fn samples<T: Float>(task: &mut TaskSource<T>) -> Vec<T> {
+    let mut result = Vec::new();
+    for [x, y, z] in POINTS.map(|p| p.map(value::<T>)) {
+        result.push(task.sample_1d(x));
+        result.push(task.sample_2d(x, y));
+        result.push(task.sample_3d(x, y, z));
+    }
+    result
+}
+
+/// Checks every case against the samples in `golden`, bit for bit if `exact`.
+fn check<T: Float>(golden: &[(&str, [T; 6])], exact: bool) {
+    let cases = cases::<T>();
+    assert_eq!(cases.len(), golden.len());
+
+    for ((name, mut task), (golden_name, expected)) in cases.into_iter().zip(golden) {
+        assert_eq!(name, *golden_name);
+        for (i, (actual, expected)) in samples(&mut task).into_iter().zip(expected).enumerate() {
+            let expected = *expected;
+            if exact {
+                assert!(
+                    actual.to_f64().to_bits() == expected.to_f64().to_bits(),
+                    "{} sample {}: {} != {}",
+                    name,
+                    i,
+                    actual,
+                    expected
+                );
+            } else {
+                let tolerance = T::EPSILON.sqrt() * T::ONE.max(expected.abs());
+                assert!(
+                    (actual - expected).abs() <= tolerance,
+                    "{} sample {}: {} != {}",
+                    name,
+                    i,
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+}
+
+/// Serializes the updates of the tables, which all live in this file.
+static SOURCE: Mutex<()> = Mutex::new(());
+
+/// Checks every case against `golden` as [`check`] does, unless `UPDATE_GOLDEN` is set, in
+/// which case the table of `type_name` in this file is rewritten with the current samples
+/// written by `write`.
+fn check_or_update<T: Float>(
+    golden: &[(&str, [T; 6])],
+    exact: bool,
+    type_name: &str,
+    write: fn(T) -> String,
+) {
+    if std::env::var_os("UPDATE_GOLDEN").is_none() {
+        check(golden, exact);
+        return;
+    }
+
+    let rows: Vec<String> = cases::<T>()
+        .into_iter()
+        .map(|(name, mut task)| {
+            let values: Vec<_> = samples(&mut task).into_iter().map(write).collect();
+            format!("        (\"{}\", [{}]),", name, values.join(", "))
+        })
+        .collect();
+
+    let _lock = SOURCE.lock().unwrap_or_else(|x| x.into_inner());
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(file!());
+    let source = std::fs::read_to_string(&path).unwrap();
+    let header = format!("    const GOLDEN: [(&str, [{}; 6]); ", type_name);
+    let start = source.find(&header).unwrap();
+    let end = start + source[start..].find("\n    ];\n").unwrap();
+    let table = format!("{}{}] = [\n{}", header, rows.len(), rows.join("\n"));
+    let source = format!("{}{}{}", &source[..start], table, &source[end..]);
+    std::fs::write(&path, source).unwrap();
+}
+
+mod f32 {
+    #[test]
+    fn golden_values() {
+        let exact = cfg!(feature = "deterministic");
+        super::check_or_update::<f32>(&GOLDEN, exact, "f32", |x| std::format!("{:?}", x));
+    }
+
+    #[rustfmt::skip]
+    const GOLDEN: [(&str, [f32; 6]); 39] = [
+        ("abs", [0.015168998, 0.163604, 0.16050893, 0.0070817946, 0.25173005, 0.36902755]),
+        ("aggregate_add", [0.24983099, -0.120076194, 0.114491165, 0.91958183, 0.8172152, 0.15805584]),
+        ("aggregate_avg", [0.124915496, -0.060038097, 0.057245582, 0.45979092, 0.4086076, 0.07902792]),
+        ("aggregate_div", [-1098.7332, 7.4009, -62.301594, 53.285717, 3.1479373, -2.4449542]),
+        ("aggregate_max", [0.25, 0.25, 0.25, 0.6625, 0.31548512, 0.2770834]),
+        ("aggregate_min", [-0.015168998, -0.20647219, -0.16050893, 0.0070817946, 0.25, -0.36902755]),
+        ("aggregate_mul", [-5.688369e-5, 0.008444919, -0.0010031846, 0.0011729223, 0.019854272, -0.025562853]),
+        ("aggregate_sub", [0.250169, 0.6200762, 0.38550884, -0.41958183, -0.31721517, 0.34194416]),
+        ("bias", [0.5279069, 0.20763771, 0.54586244, 0.9919386, 0.88158, 0.8542847]),
+        ("cache_last", [-0.015168998, -0.163604, -0.16050893, 0.0070817946, 0.25173005, -0.36902755]),
+        ("cache_lru", [-0.015168998, -0.163604, -0.16050893, 0.0070817946, 0.25173005, -0.36902755]),
+        ("cache_grid", [0.02352936, -0.14943796, -0.1676908, 0.0570617, 0.280109, -0.29555756]),
+        ("clamp", [-0.015168998, -0.1, -0.1, 0.0070817946, 0.25173005, -0.1]),
+        ("constant", [0.625, 0.625, 0.625, 0.625, 0.625, 0.625]),
+        ("curve", [0.24469742, 0.1439637, 0.14689343, 0.25055003, 0.3322285, -0.08945107]),
+        ("exponent", [-0.6597021, -0.77380407, -0.7717056, -0.6401539, -0.38022828, -0.8881899]),
+        ("fractal_billowing", [-0.35527462, -0.11386949, -0.12933308, -0.18221194, 0.0034601092, 0.30568123]),
+        ("fractal_brownian", [-0.015168998, -0.163604, -0.16050893, 0.0070817946, 0.25173005, -0.36902755]),
+        ("fractal_ridged_multi", [0.71869254, 0.35194695, 0.34644032, 0.4233811, 0.1670003, -0.24545532]),
+        ("gradient", [0.014999986, -0.20647219, 0.025000095, 0.6625, 0.31548512, 0.2770834]),
+        ("gradient_cylindrical", [-0.8397224, -0.7860166, -0.49665558, 0.060660124, 0.017212987, 0.1940608]),
+        ("gradient_radial", [-0.83000004, -0.68093884, -0.68093884, 0.125, 0.29927862, 0.29927862]),
+        ("gradient_spherical", [-0.83000004, -0.68093884, -0.4961151, 0.125, 0.29927862, 0.33633125]),
+        ("invert", [-0.014999986, 0.20647219, -0.025000095, -0.6625, -0.31548512, -0.2770834]),
+        ("multi_gradient", [-0.77333343, -0.57458514, -0.3281535, 0.4014418, 0.5315567, 0.55752844]),
+        ("parameter", [-0.375, -0.375, -0.375, -0.375, -0.375, -0.375]),
+        ("perlin_unseeded", [0.12180001, 0.28533113, -0.27693102, 0.06738281, -0.6171875, -0.19528198]),
+        ("pow", [0.3615381, 0.24991842, 0.36689422, 0.75787485, 0.53343797, 0.51025105]),
+        ("scale", [-0.00022753477, 0.033779677, -0.0040127384, 0.0046916893, 0.07941709, -0.10225141]),
+        ("scale_offset", [-0.6067306, -0.40574378, -0.2013328, 0.34362912, 0.3215403, 0.81202555]),
+        ("selector", [-0.015168998, -0.163604, -0.16050893, 0.4233811, 0.23785871, -0.36066064]),
+        ("terrace", [-0.086125, -0.19558445, -0.19480152, -0.057057105, 0.15755503, -0.5023422]),
+        ("terrace_sharp_inverted", [0.1, 0.1, 0.1, 0.1, 0.5, -0.2]),
+        ("transform_domain_affine", [-0.105436645, 0.02631758, -0.25949067, -0.11366693, -0.17919223, 0.27195266]),
+        ("transform_domain_operations", [0.017470306, -0.15596929, -0.19061342, -0.05094668, -0.16747804, -0.009303366]),
+        ("transform_domain_rotate", [-0.015174794, -0.17009737, -0.12963772, 0.0064816824, 0.21182911, -0.25293332]),
+        ("transform_domain_scale", [0.01554733, 0.020291623, 0.01789151, -0.03922516, -0.07416809, -0.012717812]),
+        ("transform_domain_translate", [-0.12783287, -0.0817212, -0.25472066, -0.063455485, 0.1377758, -0.20762727]),
+        ("turbulence", [-0.01378186, -0.16079353, -0.12836112, -0.0033851573, 0.2246011, -0.36768562]),
+    ];
+}
+
+mod f64 {
+    #[test]
+    fn golden_values() {
+        let exact = cfg!(feature = "deterministic");
+        super::check_or_update::<f64>(&GOLDEN, exact, "f64", |x| std::format!("{:?}", x));
+    }
+
+    #[rustfmt::skip]
+    const GOLDEN: [(&str, [f64; 6]); 39] = [
+        ("abs", [0.015169014358216278, 0.16360408181486075, 0.1605089255246795, 0.007081964792402327, 0.2517304303642752, 0.36902741173856496]),
+        ("aggregate_add", [0.24983098564178385, -0.12007638498815609, 0.11449107447532064, 0.9195819647924024, 0.8172154866374272, 0.1580559215947684]),
+        ("aggregate_avg", [0.12491549282089193, -0.060038192494078046, 0.05724553723766032, 0.4597909823962012, 0.4086077433187136, 0.0790279607973842]),
+        ("aggregate_div", [-1098.7310231952579, 7.400892082856167, -62.301831298860506, 53.28443470530598, 3.147933126091789, -2.444955605999647]),
+        ("aggregate_max", [0.25, 0.25, 0.25, 0.6625000000000001, 0.31548505627315193, 0.27708333333333335]),
+        ("aggregate_min", [-0.015169014358216278, -0.20647230317329535, -0.1605089255246795, 0.007081964792402327, 0.25, -0.36902741173856496]),
+        ("aggregate_mul", [-5.688380384331151e-5, 0.008444927895216637, -0.0010031807845292521, 0.0011729504187416356, 0.01985429724728453, -0.02556283633397351]),
+        ("aggregate_sub", [0.25016901435821615, 0.6200763849881561, 0.38550892552467936, -0.4195819647924024, -0.31721548663742716, 0.34194407840523167]),
+        ("bias", [0.5279068703307753, 0.20763757363617438, 0.5458623126871844, 0.991938602105859, 0.8815799739108178, 0.8542846490856364]),
+        ("cache_last", [-0.015169014358216278, -0.16360408181486075, -0.1605089255246795, 0.007081964792402327, 0.2517304303642752, -0.36902741173856496]),
+        ("cache_lru", [-0.015169014358216278, -0.16360408181486075, -0.1605089255246795, 0.007081964792402327, 0.2517304303642752, -0.36902741173856496]),
+        ("cache_grid", [0.02352934830729168, -0.14943800337868407, -0.16769090399674477, 0.05706183103918599, 0.28010912038697083, -0.29555755241347753]),
+        ("clamp", [-0.015169014358216278, -0.1, -0.1, 0.007081964792402327, 0.2517304303642752, -0.1]),
+        ("constant", [0.625, 0.625, 0.625, 0.625, 0.625, 0.625]),
+        ("curve", [0.24469740050434527, 0.14396359517254345, 0.14689342464599384, 0.25055005677913467, 0.3322287473082054, -0.08945093907270085]),
+        ("exponent", [-0.6597021013968618, -0.7738040794090268, -0.77170562332025, -0.6401536906687029, -0.38022788510454464, -0.888189848143009]),
+        ("fractal_billowing", [-0.35527454822756754, -0.11386942137967593, -0.12933313468727925, -0.18221124369691177, 0.0034608607285505077, 0.30568106851585913]),
+        ("fractal_brownian", [-0.015169014358216278, -0.16360408181486075, -0.1605089255246795, 0.007081964792402327, 0.2517304303642752, -0.36902741173856496]),
+        ("fractal_ridged_multi", [0.7186926464930765, 0.35194686712031764, 0.34644026379643855, 0.4233800385816022, 0.1669991840623657, -0.24545521109570534]),
+        ("gradient", [0.015000000000000124, -0.20647230317329535, 0.025000000000000133, 0.6625000000000001, 0.31548505627315193, 0.27708333333333335]),
+        ("gradient_cylindrical", [-0.8397224629310491, -0.786016615390613, -0.4966556292600904, 0.060660171779821415, 0.01721296797780858, 0.19406082340892494]),
+        ("gradient_radial", [-0.8300000000000001, -0.6809388773291236, -0.6809388773291236, 0.125, 0.2992786460186283, 0.2992786460186283]),
+        ("gradient_spherical", [-0.8300000000000001, -0.6809388773291236, -0.49611509250623514, 0.125, 0.2992786460186283, 0.33633126506865807]),
+        ("invert", [-0.015000000000000124, 0.20647230317329535, -0.025000000000000133, -0.6625000000000001, -0.31548505627315193, -0.27708333333333335]),
+        ("multi_gradient", [-0.7733333333333334, -0.5745851697721648, -0.32815345667498025, 0.40144178206997094, 0.5315566771179305, 0.5575283936493827]),
+        ("parameter", [-0.375, -0.375, -0.375, -0.375, -0.375, -0.375]),
+        ("perlin_unseeded", [0.1218, 0.2853312000000001, -0.27693096959999997, 0.0673828125, -0.6171875, -0.195281982421875]),
+        ("pow", [0.36153809878766585, 0.24991834778532898, 0.36689416474645664, 0.7578748363536851, 0.5334379201924441, 0.5102509972864758]),
+        ("scale", [-0.00022753521537324605, 0.033779711580866546, -0.0040127231381170084, 0.0046918016749665425, 0.07941718898913812, -0.10225134533589404]),
+        ("scale_offset", [-0.6067304593982431, -0.40574379058772825, -0.20133298570273855, 0.34363032353040446, 0.32154156254811533, 0.8120252032360868]),
+        ("selector", [-0.015169014358216278, -0.16360408181486075, -0.1605089255246795, 0.4233800385816022, 0.2378588542838381, -0.36066054398670466]),
+        ("terrace", [-0.08612502248895582, -0.19558445713153552, -0.19480151678928231, -0.057056866192394154, 0.15755530874632043, -0.502341991078332]),
+        ("terrace_sharp_inverted", [0.1, 0.1, 0.1, 0.1, 0.5, -0.2]),
+        ("transform_domain_affine", [-0.10543666046779522, 0.026317982683608545, -0.25949086781269187, -0.1136668756807501, -0.17919220225863258, 0.2719527720771332]),
+        ("transform_domain_operations", [0.017470302043195347, -0.15596933378930797, -0.19061327823492308, -0.05094656133779381, -0.16747849772705942, -0.009303776148688775]),
+        ("transform_domain_rotate", [-0.015174807470025798, -0.17009734900819348, -0.1296376417286156, 0.00648171247726282, 0.211829140276724, -0.2529333141548399]),
+        ("transform_domain_scale", [0.015547305110593943, 0.02029155482313582, 0.017891568078809764, -0.0392249765533643, -0.07416834184811344, -0.012717927913158096]),
+        ("transform_domain_translate", [-0.1278328936369523, -0.08172111711981699, -0.2547207686116976, -0.06345540555729973, 0.1377754413079024, -0.20762722011625287]),
+        ("turbulence", [-0.013781857130459573, -0.16079351418009127, -0.12836113001827676, -0.003385082626600275, 0.22460110855781296, -0.3676855844049946]),
+    ];
+}
+
+mod fixed {
+    use crate::float::Fixed;
+
+    #[test]
+    fn golden_values() {
+        let golden = GOLDEN.map(|(name, bits)| (name, bits.map(Fixed::from_bits)));
+        super::check_or_update::<Fixed>(&golden, true, "i32", |x| std::format!("{}", x.to_bits()));
+    }
+
+    /// Raw bits of the samples.
+    #[rustfmt::skip]
+    const GOLDEN: [(&str, [i32; 6]); 39] = [
+        ("abs", [993, 10721, 10518, 468, 16511, 24186]),
+        ("aggregate_add", [16375, -7869, 7504, 60270, 53571, 10358]),
+        ("aggregate_avg", [8187, -3934, 3752, 30135, 26785, 5179]),
+        ("aggregate_div", [-72017070, 485044, -4084437, 3463092, 206126, -160213]),
+        ("aggregate_max", [16384, 16384, 16384, 43418, 20676, 18160]),
+        ("aggregate_min", [-993, -13532, -10518, 468, 16384, -24186]),
+        ("aggregate_mul", [-4, 553, -66, 78, 1302, -1675]),
+        ("aggregate_sub", [16393, 40637, 25264, -27502, -20803, 22410]),
+        ("bias", [34600, 13608, 35770, 65007, 57778, 55988]),
+        ("cache_last", [-993, -10721, -10518, 468, 16511, -24186]),
+        ("cache_lru", [-993, -10721, -10518, 468, 16511, -24186]),
+        ("cache_grid", [1543, -9794, -10990, 3748, 18366, -19377]),
+        ("clamp", [-993, -6554, -6554, 468, 16511, -6554]),
+        ("constant", [40960, 40960, 40960, 40960, 40960, 40960]),
+        ("curve", [16036, 9435, 9627, 16421, 21781, -5865]),
+        ("exponent", [-43232, -50710, -50574, -41950, -24900, -58210]),
+        ("fractal_billowing", [-23283, -7463, -8477, -11922, 255, 20049]),
+        ("fractal_brownian", [-993, -10721, -10518, 468, 16511, -24186]),
+        ("fractal_ridged_multi", [47100, 23066, 22706, 27712, 10894, -16108]),
+        ("gradient", [984, -13532, 1638, 43418, 20676, 18160]),
+        ("gradient_cylindrical", [-55034, -51514, -32550, 3974, 1128, 12716]),
+        ("gradient_radial", [-54396, -44628, -44628, 8192, 19612, 19612]),
+        ("gradient_spherical", [-54396, -44628, -32514, 8192, 19612, 22040]),
+        ("invert", [-984, 13532, -1638, -43418, -20676, -18160]),
+        ("multi_gradient", [-50683, -37659, -21507, 26309, 34836, 36537]),
+        ("parameter", [-24576, -24576, -24576, -24576, -24576, -24576]),
+        ("perlin_unseeded", [7981, 18700, -18150, 4416, -40448, -12798]),
+        ("pow", [23694, 16378, 24045, 49668, 34960, 33440]),
+        ("scale", [-15, 2214, -263, 310, 5209, -6702]),
+        ("scale_offset", [-39761, -26592, -13197, 22555, 21122, 53246]),
+        ("selector", [-993, -10721, -10518, 27712, 15592, -23638]),
+        ("terrace", [-5643, -12817, -12766, -3735, 10336, -32924]),
+        ("terrace_sharp_inverted", [6554, 6554, 6554, 6554, 32768, -13107]),
+        ("transform_domain_affine", [-6909, 1722, -17001, -7450, -11737, 17812]),
+        ("transform_domain_operations", [1145, -10221, -12494, -3339, -10968, -597]),
+        ("transform_domain_rotate", [-994, -11147, -8494, 429, 13892, -16590]),
+        ("transform_domain_scale", [1019, 1327, 1171, -2564, -4871, -845]),
+        ("transform_domain_translate", [-8378, -5355, -16695, -4154, 9006, -13602]),
+        ("turbulence", [-902, -10536, -8412, -218, 14726, -24099]),
+    ];
+}
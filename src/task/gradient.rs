@@ -1,59 +1,116 @@
 mod builder;
 
-macro_rules! gradient_type {
-    ($type: ty) => {
-        #[derive(Debug, PartialEq, PartialOrd)]
-        pub struct Gradient {
-            pub(crate) noise: source::Gradient,
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::float::Float;
+use crate::source::{self, Noise};
+use crate::task::Task;
+pub use builder::GradientBuilder;
+
+/// Shape of a [`Gradient`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize, Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum GradientKind {
+    /// Along the segment from `s1` to `s2`
+    #[default]
+    Linear,
+    /// By the distance from `s1` in the xy plane
+    Radial,
+    /// By the distance from `s1`
+    Spherical,
+    /// By the distance from the axis through `s1` and `s2`
+    Cylindrical,
+}
+
+#[derive(Debug, PartialEq, PartialOrd)]
+pub(crate) enum GradientNoise<T: Float> {
+    Linear(source::Gradient<T>),
+    Radial(source::RadialGradient<T>),
+    Spherical(source::SphericalGradient<T>),
+    Cylindrical(source::CylindricalGradient<T>),
+}
+
+impl<T: Float> GradientNoise<T> {
+    fn noise(&mut self) -> &mut dyn Noise<T> {
+        match self {
+            Self::Linear(x) => x,
+            Self::Radial(x) => x,
+            Self::Spherical(x) => x,
+            Self::Cylindrical(x) => x,
         }
+    }
+}
 
-        impl Default for Gradient {
-            fn default() -> Self {
-                Self {
-                    noise: source::Gradient::default(),
-                }
-            }
+/// Task sampling a gradient in the range [-1, 1], see [`GradientKind`] for its shapes.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct Gradient<T: Float> {
+    pub(crate) noise: GradientNoise<T>,
+}
+
+impl<T: Float> Default for Gradient<T> {
+    fn default() -> Self {
+        Self {
+            noise: GradientNoise::Linear(source::Gradient::default()),
         }
+    }
+}
 
-        impl Task for Gradient {
-            /// Calculates the dot product of the x value scaled to the range [-1, 1].
-            fn sample_1d(&mut self, x: $type) -> $type {
-                self.noise.sample_1d(x)
-            }
-
-            /// Calculates the dot product of the x, y values scaled to the range [-1, 1].
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-                self.noise.sample_2d(x, y)
-            }
-
-            /// Calculates the dot product of the x, y, and z values scaled to the range [-1, 1].
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-                self.noise.sample_3d(x, y, z)
-            }
+impl<T: Float> Gradient<T> {
+    pub(crate) fn kind(&self) -> GradientKind {
+        match self.noise {
+            GradientNoise::Linear(_) => GradientKind::Linear,
+            GradientNoise::Radial(_) => GradientKind::Radial,
+            GradientNoise::Spherical(_) => GradientKind::Spherical,
+            GradientNoise::Cylindrical(_) => GradientKind::Cylindrical,
         }
-    };
+    }
 }
 
-pub mod f32 {
-    pub use super::builder::f32::GradientBuilder;
-    use crate::{source::f32 as source, task::f32::Task};
-	use crate::source::f32::Noise;
+impl<T: Float> Task<T> for Gradient<T> {
+    /// Samples the gradient at the x-coordinate.
+    fn sample_1d(&mut self, x: T) -> T {
+        self.noise.noise().sample_1d(x)
+    }
+
+    /// Samples the gradient at the (x, y) coordinates.
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.noise.noise().sample_2d(x, y)
+    }
+
+    /// Samples the gradient at the (x, y, z) coordinates.
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.noise.noise().sample_3d(x, y, z)
+    }
+}
 
-    gradient_type!(f32);
+pub mod f32 {
+    pub use super::GradientKind;
+    pub type Gradient = super::Gradient<f32>;
+    pub type GradientBuilder = super::GradientBuilder<f32>;
 }
 
 pub mod f64 {
-    pub use super::builder::f64::GradientBuilder;
-    use crate::{source::f64 as source, task::f64::Task};
-	use crate::source::f64::Noise;
+    pub use super::GradientKind;
+    pub type Gradient = super::Gradient<f64>;
+    pub type GradientBuilder = super::GradientBuilder<f64>;
+}
 
-    gradient_type!(f64);
+pub mod fixed {
+    pub use super::GradientKind;
+    use crate::float::Fixed;
+    pub type Gradient = super::Gradient<Fixed>;
+    pub type GradientBuilder = super::GradientBuilder<Fixed>;
 }
 
 #[cfg(test)]
 mod tests {
 	mod f32 {
-		use crate::task::f32::{Task, GradientBuilder};
+		use crate::task::f32::{Task, GradientBuilder, GradientKind};
 		#[test]
 		fn task_type_gradient_tests() {
 			let mut result = GradientBuilder::new().build();
@@ -62,10 +119,28 @@ mod tests {
 			let mut result = GradientBuilder::default().s2([1.0, 0.0, 0.0]).build();
 			assert_eq!(result.sample_1d(1.0), 1.0);
 		}
+
+		#[test]
+		fn gradient_kinds() {
+			let mut builder = GradientBuilder::new();
+			builder.s2([0.0, 0.0, 1.0]).radius(2.0);
+
+			let mut result = builder.kind(GradientKind::Radial).build();
+			assert_eq!(result.sample_2d(0.0, 1.0), 0.0);
+			assert_eq!(result.sample_3d(0.0, 1.0, 4.0), 0.0);
+
+			let mut result = builder.kind(GradientKind::Spherical).build();
+			assert_eq!(result.sample_2d(0.0, 1.0), 0.0);
+			assert_eq!(result.sample_3d(0.0, 1.0, 4.0), 1.0);
+
+			let mut result = builder.kind(GradientKind::Cylindrical).build();
+			assert_eq!(result.sample_3d(0.0, 0.0, 4.0), -1.0);
+			assert_eq!(result.sample_3d(1.0, 0.0, 4.0), 0.0);
+		}
 	}
 
 	mod f64 {
-		use crate::task::f64::{Task, GradientBuilder};
+		use crate::task::f64::{Task, GradientBuilder, GradientKind};
 		#[test]
 		fn task_type_gradient_tests() {
 			let mut result = GradientBuilder::new().build();
@@ -74,5 +149,23 @@ mod tests {
 			let mut result = GradientBuilder::default().s2([1.0, 0.0, 0.0]).build();
 			assert_eq!(result.sample_1d(1.0), 1.0);
 		}
+
+		#[test]
+		fn gradient_kinds() {
+			let mut builder = GradientBuilder::new();
+			builder.s2([0.0, 0.0, 1.0]).radius(2.0);
+
+			let mut result = builder.kind(GradientKind::Radial).build();
+			assert_eq!(result.sample_2d(0.0, 1.0), 0.0);
+			assert_eq!(result.sample_3d(0.0, 1.0, 4.0), 0.0);
+
+			let mut result = builder.kind(GradientKind::Spherical).build();
+			assert_eq!(result.sample_2d(0.0, 1.0), 0.0);
+			assert_eq!(result.sample_3d(0.0, 1.0, 4.0), 1.0);
+
+			let mut result = builder.kind(GradientKind::Cylindrical).build();
+			assert_eq!(result.sample_3d(0.0, 0.0, 4.0), -1.0);
+			assert_eq!(result.sample_3d(1.0, 0.0, 4.0), 0.0);
+		}
 	}
 }
@@ -1,52 +1,93 @@
-macro_rules! gradient_builder_type {
-    ($type: ty) => {
-        pub struct GradientBuilder {
-            s1: [$type; 3],
-            s2: [$type; 3],
-        }
+use alloc::format;
+use alloc::string::String;
 
-        impl Default for GradientBuilder {
-            fn default() -> Self {
-                Self {
-                    s1: [0.0; 3],
-                    s2: [1.0, 1.0, 0.0],
-                }
-            }
+use super::{Gradient, GradientKind, GradientNoise};
+use crate::float::Float;
+use crate::source;
+
+pub struct GradientBuilder<T: Float> {
+    kind: GradientKind,
+    radius: T,
+    s1: [T; 3],
+    s2: [T; 3],
+}
+
+impl<T: Float> Default for GradientBuilder<T> {
+    fn default() -> Self {
+        Self {
+            kind: GradientKind::default(),
+            radius: T::ONE,
+            s1: [T::ZERO; 3],
+            s2: [T::ONE, T::ONE, T::ZERO],
         }
+    }
+}
 
-        #[allow(dead_code)]
-        impl GradientBuilder {
-            pub fn new() -> Self {
-                Self::default()
-            }
+#[allow(dead_code)]
+impl<T: Float> GradientBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-            pub fn build(&self) -> Gradient {
-                Gradient {
-                    noise: source::Gradient::new(self.s1, self.s2),
-                }
+    pub fn build(&self) -> Gradient<T> {
+        let noise = match self.kind {
+            GradientKind::Linear => GradientNoise::Linear(source::Gradient::new(self.s1, self.s2)),
+            GradientKind::Radial => {
+                GradientNoise::Radial(source::RadialGradient::new(self.s1, self.radius))
             }
-
-            pub fn s1(&mut self, point: [$type; 3]) -> &mut Self {
-                self.s1 = point;
-                self
+            GradientKind::Spherical => {
+                GradientNoise::Spherical(source::SphericalGradient::new(self.s1, self.radius))
             }
+            GradientKind::Cylindrical => GradientNoise::Cylindrical(
+                source::CylindricalGradient::new(self.s1, self.s2, self.radius),
+            ),
+        };
+        Gradient { noise }
+    }
 
-            pub fn s2(&mut self, point: [$type; 3]) -> &mut Self {
-                self.s2 = point;
-                self
+    /// Returns an error if [`build`](Self::build) would panic: a radius of 0 or less for
+    /// shapes other than linear, or equal points for linear and cylindrical gradients.
+    pub fn check(&self) -> Result<(), String> {
+        if self.kind != GradientKind::Linear && self.radius <= T::ZERO {
+            return Err(format!(
+                "Gradient radius must be greater than 0, got {}",
+                self.radius
+            ));
+        }
+        if matches!(self.kind, GradientKind::Linear | GradientKind::Cylindrical) {
+            let len = self
+                .s1
+                .iter()
+                .zip(self.s2.iter())
+                .fold(T::ZERO, |len, (&a, &b)| len + (b - a) * (b - a));
+            if len <= T::ZERO {
+                return Err(String::from("Gradient points must be different"));
             }
         }
-    };
-}
+        Ok(())
+    }
 
-pub mod f32 {
-    use super::super::f32::Gradient;
-    use crate::source::f32 as source;
-    gradient_builder_type!(f32);
-}
+    /// Sets the shape of the gradient, linear by default.
+    pub fn kind(&mut self, kind: GradientKind) -> &mut Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the distance from the centre or axis sampled as 1, unused by linear gradients.
+    pub fn radius(&mut self, radius: T) -> &mut Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the start of the segment, or the centre of radial and spherical gradients.
+    pub fn s1(&mut self, point: [T; 3]) -> &mut Self {
+        self.s1 = point;
+        self
+    }
 
-pub mod f64 {
-    use super::super::f64::Gradient;
-    use crate::source::f64 as source;
-    gradient_builder_type!(f64);
+    /// Sets the end of the segment, unused by radial and spherical gradients.
+    pub fn s2(&mut self, point: [T; 3]) -> &mut Self {
+        self.s2 = point;
+        self
+    }
 }
@@ -0,0 +1,74 @@
+mod builder;
+
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+pub use builder::InvertBuilder;
+
+/// Task returning its source negated.
+#[derive(Clone, Debug)]
+pub struct Invert<T: Float> {
+    pub(crate) source: TaskSource<T>,
+}
+
+impl<T: Float> Invert<T> {
+    fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+        -sampler(&mut self.source)
+    }
+}
+
+impl<T: Float> Task<T> for Invert<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
+}
+
+pub mod f32 {
+    pub type Invert = super::Invert<f32>;
+    pub type InvertBuilder = super::InvertBuilder<f32>;
+}
+
+pub mod f64 {
+    pub type Invert = super::Invert<f64>;
+    pub type InvertBuilder = super::InvertBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Invert = super::Invert<Fixed>;
+    pub type InvertBuilder = super::InvertBuilder<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{InvertBuilder, Task};
+        #[test]
+        fn invert_tests() {
+            let mut result = InvertBuilder::new().source(0.25).build();
+
+            assert_eq!(result.sample_1d(1.0), -0.25);
+            assert_eq!(result.sample_2d(1.0, 1.0), -0.25);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), -0.25);
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{InvertBuilder, Task};
+        #[test]
+        fn invert_tests() {
+            let mut result = InvertBuilder::new().source(0.25).build();
+
+            assert_eq!(result.sample_1d(1.0), -0.25);
+            assert_eq!(result.sample_2d(1.0, 1.0), -0.25);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), -0.25);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use alloc::string::String;
+
+use super::Invert;
+use crate::float::Float;
+use crate::task::{named_to_task, source_or_message, NameOrSource, TaskSource, TaskTree};
+
+pub struct InvertBuilder<T: Float> {
+    source: NameOrSource<T>,
+}
+
+impl<T: Float> Default for InvertBuilder<T> {
+    fn default() -> Self {
+        Self {
+            source: NameOrSource::Source(T::ZERO.into()),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> InvertBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build(&self) -> Invert<T> {
+        Invert {
+            source: source_or_message!(self.source, InvertBuilder<T>),
+        }
+    }
+
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        named_to_task!(self.source, tree);
+
+        self
+    }
+
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
+}
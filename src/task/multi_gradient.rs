@@ -0,0 +1,209 @@
+mod builder;
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::float::Float;
+use crate::task::curve::{interpolate_segment, monotone_tangents};
+use crate::task::{Gradient, Interpolation, Task};
+pub use builder::MultiGradientBuilder;
+
+/// A stop of a [`MultiGradient`].
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct GradientStop<T: Float> {
+    /// Position along the gradient, from 0 at its start to 1 at its end
+    pub position: T,
+    pub value: T,
+    /// How the gradient interpolates from this stop to the next one
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub interpolation: Interpolation,
+}
+
+/// Task sampling a gradient through stops of arbitrary values.
+///
+/// The shape of the gradient is the same as a [`Gradient`] of the same kind, its position runs
+/// from 0 at `s1` to 1 at `s2`, or at the radius of radial, spherical and cylindrical gradients.
+/// Positions before the first or after the last stop take the value of that stop, a gradient
+/// without stops samples from -1 to 1 like a plain [`Gradient`].
+#[derive(Debug)]
+pub struct MultiGradient<T: Float> {
+    pub(crate) gradient: Gradient<T>,
+    pub(crate) stops: Vec<GradientStop<T>>,
+    /// Stops as `[position, value]` pairs
+    points: Vec<[T; 2]>,
+    /// Tangent at every stop for monotone cubic segments
+    tangents: Vec<T>,
+}
+
+impl<T: Float> MultiGradient<T> {
+    pub fn new(gradient: Gradient<T>, stops: Vec<GradientStop<T>>) -> Self {
+        let mut stops = stops;
+        stops.sort_by(|a, b| {
+            a.position
+                .partial_cmp(&b.position)
+                .unwrap_or(Ordering::Equal)
+        });
+        let points: Vec<[T; 2]> = stops.iter().map(|s| [s.position, s.value]).collect();
+        let tangents = monotone_tangents(&points);
+
+        Self {
+            gradient,
+            stops,
+            points,
+            tangents,
+        }
+    }
+
+    pub(crate) fn remap(&self, v: T) -> T {
+        let points = &self.points;
+        if points.is_empty() {
+            return v;
+        }
+
+        let x = (v + T::ONE) * T::HALF;
+        let i = points.partition_point(|p| p[0] <= x);
+        if i == 0 {
+            return points[0][1];
+        } else if i == points.len() {
+            return points[i - 1][1];
+        }
+        let interpolation = self.stops[i - 1].interpolation;
+        interpolate_segment(interpolation, points, &self.tangents, i, x)
+    }
+
+    fn eval<F: Fn(&mut Gradient<T>) -> T>(&mut self, sampler: F) -> T {
+        let v = sampler(&mut self.gradient);
+        self.remap(v)
+    }
+}
+
+impl<T: Float> Task<T> for MultiGradient<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
+}
+
+pub mod f32 {
+    pub type GradientStop = super::GradientStop<f32>;
+    pub type MultiGradient = super::MultiGradient<f32>;
+    pub type MultiGradientBuilder = super::MultiGradientBuilder<f32>;
+}
+
+pub mod f64 {
+    pub type GradientStop = super::GradientStop<f64>;
+    pub type MultiGradient = super::MultiGradient<f64>;
+    pub type MultiGradientBuilder = super::MultiGradientBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type GradientStop = super::GradientStop<Fixed>;
+    pub type MultiGradient = super::MultiGradient<Fixed>;
+    pub type MultiGradientBuilder = super::MultiGradientBuilder<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{GradientKind, Interpolation, MultiGradientBuilder, Task};
+
+        #[test]
+        fn multi_gradient() {
+            let mut task = MultiGradientBuilder::new()
+                .s2([4.0, 0.0, 0.0])
+                .add_stop(0.75, 1.0, Interpolation::Linear)
+                .add_stop(0.0, -1.0, Interpolation::Linear)
+                .add_stop(0.25, 0.5, Interpolation::Linear)
+                .build();
+            let mut sample = |x| (task.sample_1d(x) * 1e4).round() / 1e4;
+
+            assert_eq!(sample(-2.0), -1.0);
+            assert_eq!(sample(0.5), -0.25);
+            assert_eq!(sample(1.0), 0.5);
+            assert_eq!(sample(2.0), 0.75);
+            assert_eq!(sample(3.5), 1.0);
+        }
+
+        #[test]
+        fn multi_gradient_segments() {
+            let mut task = MultiGradientBuilder::new()
+                .kind(GradientKind::Radial)
+                .radius(4.0)
+                .add_stop(0.0, 0.0, Interpolation::Linear)
+                .add_stop(0.5, 1.0, Interpolation::MonotoneCubic)
+                .add_stop(1.0, 0.0, Interpolation::Linear)
+                .build();
+
+            assert_eq!(task.sample_2d(0.0, 1.0), 0.5);
+            assert_eq!(task.sample_2d(0.0, -2.0), 1.0);
+            let v = task.sample_2d(3.0, 0.0);
+            assert!(v > 0.5 && v < 1.0, "{}", v);
+            assert_eq!(task.sample_2d(8.0, 0.0), 0.0);
+        }
+
+        #[test]
+        fn multi_gradient_without_stops() {
+            let mut task = MultiGradientBuilder::new().s2([1.0, 0.0, 0.0]).build();
+            let mut sample = |x| (task.sample_1d(x) * 1e4).round() / 1e4;
+            assert_eq!(sample(0.5), 0.0);
+            assert_eq!(sample(1.0), 1.0);
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{GradientKind, Interpolation, MultiGradientBuilder, Task};
+
+        #[test]
+        fn multi_gradient() {
+            let mut task = MultiGradientBuilder::new()
+                .s2([4.0, 0.0, 0.0])
+                .add_stop(0.75, 1.0, Interpolation::Linear)
+                .add_stop(0.0, -1.0, Interpolation::Linear)
+                .add_stop(0.25, 0.5, Interpolation::Linear)
+                .build();
+            let mut sample = |x| (task.sample_1d(x) * 1e8).round() / 1e8;
+
+            assert_eq!(sample(-2.0), -1.0);
+            assert_eq!(sample(0.5), -0.25);
+            assert_eq!(sample(1.0), 0.5);
+            assert_eq!(sample(2.0), 0.75);
+            assert_eq!(sample(3.5), 1.0);
+        }
+
+        #[test]
+        fn multi_gradient_segments() {
+            let mut task = MultiGradientBuilder::new()
+                .kind(GradientKind::Radial)
+                .radius(4.0)
+                .add_stop(0.0, 0.0, Interpolation::Linear)
+                .add_stop(0.5, 1.0, Interpolation::MonotoneCubic)
+                .add_stop(1.0, 0.0, Interpolation::Linear)
+                .build();
+
+            assert_eq!(task.sample_2d(0.0, 1.0), 0.5);
+            assert_eq!(task.sample_2d(0.0, -2.0), 1.0);
+            let v = task.sample_2d(3.0, 0.0);
+            assert!(v > 0.5 && v < 1.0, "{}", v);
+            assert_eq!(task.sample_2d(8.0, 0.0), 0.0);
+        }
+
+        #[test]
+        fn multi_gradient_without_stops() {
+            let mut task = MultiGradientBuilder::new().s2([1.0, 0.0, 0.0]).build();
+            let mut sample = |x| (task.sample_1d(x) * 1e8).round() / 1e8;
+            assert_eq!(sample(0.5), 0.0);
+            assert_eq!(sample(1.0), 1.0);
+        }
+    }
+}
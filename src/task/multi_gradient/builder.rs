@@ -0,0 +1,78 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{GradientStop, MultiGradient};
+use crate::float::Float;
+use crate::task::{GradientBuilder, GradientKind, Interpolation};
+
+pub struct MultiGradientBuilder<T: Float> {
+    gradient: GradientBuilder<T>,
+    stops: Vec<GradientStop<T>>,
+}
+
+impl<T: Float> Default for MultiGradientBuilder<T> {
+    fn default() -> Self {
+        Self {
+            gradient: GradientBuilder::default(),
+            stops: vec![],
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> MultiGradientBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stop interpolated to the next one by `interpolation`, stops can be added in any
+    /// order.
+    pub fn add_stop(&mut self, position: T, value: T, interpolation: Interpolation) -> &mut Self {
+        self.stops.push(GradientStop {
+            position,
+            value,
+            interpolation,
+        });
+        self
+    }
+
+    pub fn build(&self) -> MultiGradient<T> {
+        MultiGradient::new(self.gradient.build(), self.stops.clone())
+    }
+
+    /// Returns an error if [`build`](Self::build) would panic, see [`GradientBuilder::check`].
+    pub fn check(&self) -> Result<(), String> {
+        self.gradient.check()
+    }
+
+    /// Sets the shape of the gradient, linear by default.
+    pub fn kind(&mut self, kind: GradientKind) -> &mut Self {
+        self.gradient.kind(kind);
+        self
+    }
+
+    /// Sets the distance from the centre or axis at position 1, unused by linear gradients.
+    pub fn radius(&mut self, radius: T) -> &mut Self {
+        self.gradient.radius(radius);
+        self
+    }
+
+    /// Sets the start of the segment, or the centre of radial and spherical gradients.
+    pub fn s1(&mut self, point: [T; 3]) -> &mut Self {
+        self.gradient.s1(point);
+        self
+    }
+
+    /// Sets the end of the segment, unused by radial and spherical gradients.
+    pub fn s2(&mut self, point: [T; 3]) -> &mut Self {
+        self.gradient.s2(point);
+        self
+    }
+
+    /// Replaces the stops.
+    pub fn stops(&mut self, stops: Vec<GradientStop<T>>) -> &mut Self {
+        self.stops = stops;
+        self
+    }
+}
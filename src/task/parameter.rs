@@ -0,0 +1,93 @@
+use crate::float::Float;
+use crate::task::Task;
+
+/// Named value that can be changed after the tree is built.
+///
+/// Every task linked to a parameter shares it, so a change made through
+/// `TaskTree::set_param` is seen by all of them without rebuilding the tree.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Parameter<T: Float> {
+    pub(crate) value: T,
+}
+
+impl<T: Float> Parameter<T> {
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Changes the value, caches sampling the parameter keep their values until they
+    /// are invalidated, see `TaskTree::set_param`.
+    pub fn set(&mut self, value: T) {
+        self.value = value;
+    }
+}
+
+impl<T: Float> Task<T> for Parameter<T> {
+    fn sample_1d(&mut self, _: T) -> T {
+        self.value
+    }
+
+    fn sample_2d(&mut self, _: T, _: T) -> T {
+        self.value
+    }
+
+    fn sample_3d(&mut self, _: T, _: T, _: T) -> T {
+        self.value
+    }
+}
+
+pub mod f32 {
+    pub type Parameter = super::Parameter<f32>;
+}
+
+pub mod f64 {
+    pub type Parameter = super::Parameter<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Parameter = super::Parameter<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{Parameter, Task};
+
+        #[test]
+        fn parameter_value() {
+            let mut result = Parameter::new(0.5);
+            assert_eq!(result.sample_1d(1.0), 0.5);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.5);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.5);
+
+            result.set(0.25);
+            assert_eq!(result.value(), 0.25);
+            assert_eq!(result.sample_1d(1.0), 0.25);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.25);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.25);
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{Parameter, Task};
+
+        #[test]
+        fn parameter_value() {
+            let mut result = Parameter::new(0.5);
+            assert_eq!(result.sample_1d(1.0), 0.5);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.5);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.5);
+
+            result.set(0.25);
+            assert_eq!(result.value(), 0.25);
+            assert_eq!(result.sample_1d(1.0), 0.25);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.25);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.25);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+mod builder;
+
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+pub use builder::PowBuilder;
+
+/// Task raising its source to the power of `exponent`.
+///
+/// Negative sources with a fractional exponent have no real result, they give NaN with floats
+/// and 0 with `Fixed`.
+#[derive(Clone, Debug)]
+pub struct Pow<T: Float> {
+    pub(crate) exponent: TaskSource<T>,
+    pub(crate) source: TaskSource<T>,
+}
+
+impl<T: Float> Pow<T> {
+    fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+        let v = sampler(&mut self.source);
+        let e = sampler(&mut self.exponent);
+        v.powf(e)
+    }
+}
+
+impl<T: Float> Task<T> for Pow<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
+}
+
+pub mod f32 {
+    pub type Pow = super::Pow<f32>;
+    pub type PowBuilder = super::PowBuilder<f32>;
+}
+
+pub mod f64 {
+    pub type Pow = super::Pow<f64>;
+    pub type PowBuilder = super::PowBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Pow = super::Pow<Fixed>;
+    pub type PowBuilder = super::PowBuilder<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{PowBuilder, Task};
+        #[test]
+        fn pow_tests() {
+            let mut result = PowBuilder::new().exponent(2.0).source(0.5).build();
+            assert_eq!(result.sample_1d(1.0), 0.25);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.25);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.25);
+
+            let mut result = PowBuilder::new().exponent(0.5).source(4.0).build();
+            assert_eq!(result.sample_1d(1.0), 2.0);
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{PowBuilder, Task};
+        #[test]
+        fn pow_tests() {
+            let mut result = PowBuilder::new().exponent(2.0).source(0.5).build();
+            assert_eq!(result.sample_1d(1.0), 0.25);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.25);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.25);
+
+            let mut result = PowBuilder::new().exponent(0.5).source(4.0).build();
+            assert_eq!(result.sample_1d(1.0), 2.0);
+        }
+    }
+}
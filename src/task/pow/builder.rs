@@ -0,0 +1,61 @@
+use alloc::string::String;
+
+use super::Pow;
+use crate::float::Float;
+use crate::task::{named_to_task, source_or_message, NameOrSource, TaskSource, TaskTree};
+
+pub struct PowBuilder<T: Float> {
+    exponent: NameOrSource<T>,
+    source: NameOrSource<T>,
+}
+
+impl<T: Float> Default for PowBuilder<T> {
+    fn default() -> Self {
+        Self {
+            exponent: NameOrSource::Source(T::ONE.into()),
+            source: NameOrSource::Source(T::ZERO.into()),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> PowBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn exponent<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.exponent = NameOrSource::Source(task.into());
+        self
+    }
+
+    pub fn build(&self) -> Pow<T> {
+        Pow {
+            exponent: source_or_message!(self.exponent, PowBuilder<T>),
+            source: source_or_message!(self.source, PowBuilder<T>),
+        }
+    }
+
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        named_to_task!(self.exponent, tree);
+        named_to_task!(self.source, tree);
+
+        self
+    }
+
+    pub fn named_exponent<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.exponent = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
+}
@@ -1,47 +1,51 @@
 mod builder;
 
-macro_rules! scale {
-    ($type: ty) => {
-        #[derive(Clone, Debug)]
-        pub struct Scale {
-            pub(crate) scale: TaskSource,
-            pub(crate) source: TaskSource,
-        }
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+pub use builder::ScaleBuilder;
+
+#[derive(Clone, Debug)]
+pub struct Scale<T: Float> {
+    pub(crate) scale: TaskSource<T>,
+    pub(crate) source: TaskSource<T>,
+}
 
-        impl Scale {
-            fn eval<F: Fn(&mut dyn Task) -> $type>(&mut self, sampler: F) -> $type {
-                let v = sampler(&mut self.source);
-                let s = sampler(&mut self.scale);
-                v * s
-            }
-        }
+impl<T: Float> Scale<T> {
+    fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+        let v = sampler(&mut self.source);
+        let s = sampler(&mut self.scale);
+        v * s
+    }
+}
 
-        impl Task for Scale {
-            fn sample_1d(&mut self, x: $type) -> $type {
-                self.eval(|t| t.sample_1d(x))
-            }
+impl<T: Float> Task<T> for Scale<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
 
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-                self.eval(|t| t.sample_2d(x, y))
-            }
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
 
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-                self.eval(|t| t.sample_3d(x, y, z))
-            }
-        }
-    };
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
 }
 
 pub mod f32 {
-    pub use super::builder::f32::ScaleBuilder;
-    use crate::task::f32::{Task, TaskSource};
-    scale!(f32);
+    pub type Scale = super::Scale<f32>;
+    pub type ScaleBuilder = super::ScaleBuilder<f32>;
 }
 
 pub mod f64 {
-    pub use super::builder::f64::ScaleBuilder;
-    use crate::task::f64::{Task, TaskSource};
-    scale!(f64);
+    pub type Scale = super::Scale<f64>;
+    pub type ScaleBuilder = super::ScaleBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Scale = super::Scale<Fixed>;
+    pub type ScaleBuilder = super::ScaleBuilder<Fixed>;
 }
 
 #[cfg(test)]
@@ -1,86 +1,61 @@
-macro_rules! scale_builder {
-    ($type: ty) => {
-        pub struct ScaleBuilder {
-            scale: NameOrSource,
-            source: NameOrSource,
-        }
-
-        impl Default for ScaleBuilder {
-            fn default() -> Self {
-                Self {
-                    scale: NameOrSource::Source(0.0.into()),
-                    source: NameOrSource::Source(0.0.into()),
-                }
-            }
-        }
-
-        #[allow(dead_code)]
-        impl ScaleBuilder {
-            pub fn new() -> Self {
-                Self::default()
-            }
-
-            pub fn scale<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.scale = NameOrSource::Source(task.into());
-                self
-            }
-
-            pub fn build(&self) -> Scale {
-                Scale {
-                    scale: source_or_message!(self.scale, ScaleBuilder),
-                    source: source_or_message!(self.source, ScaleBuilder),
-                }
-            }
-
-            /// Link named tasks to their task tree values
-            pub fn link(&mut self, tree: &TaskTree) -> &mut Self {
-                match &self.scale {
-                    NameOrSource::Named(name) => {
-                        if let Some(task) = tree.get(name) {
-                            self.scale = NameOrSource::Source(task.clone());
-                        }
-                    }
-                    _ => {}
-                }
+use alloc::string::String;
 
-                match &self.source {
-                    NameOrSource::Named(name) => {
-                        if let Some(task) = tree.get(name) {
-                            self.source = NameOrSource::Source(task.clone());
-                        }
-                    }
-                    _ => {}
-                }
+use super::Scale;
+use crate::float::Float;
+use crate::task::{named_to_task, source_or_message, NameOrSource, TaskSource, TaskTree};
 
-                self
-            }
-
-            pub fn named_scale<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.scale = NameOrSource::Named(name.into());
-                self
-            }
-
-            pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.source = NameOrSource::Named(name.into());
-                self
-            }
-
-            pub fn source<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.source = NameOrSource::Source(task.into());
-                self
-            }
-        }
-    };
+pub struct ScaleBuilder<T: Float> {
+    scale: NameOrSource<T>,
+    source: NameOrSource<T>,
 }
 
-pub mod f32 {
-    use crate::task::f32::{NameOrSource, Scale, TaskSource, TaskTree};
-    use crate::task::task::source_or_message;
-    scale_builder!(f32);
+impl<T: Float> Default for ScaleBuilder<T> {
+    fn default() -> Self {
+        Self {
+            scale: NameOrSource::Source(T::ZERO.into()),
+            source: NameOrSource::Source(T::ZERO.into()),
+        }
+    }
 }
 
-pub mod f64 {
-    use crate::task::f64::{NameOrSource, Scale, TaskSource, TaskTree};
-    use crate::task::task::source_or_message;
-    scale_builder!(f64);
+#[allow(dead_code)]
+impl<T: Float> ScaleBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn scale<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.scale = NameOrSource::Source(task.into());
+        self
+    }
+
+    pub fn build(&self) -> Scale<T> {
+        Scale {
+            scale: source_or_message!(self.scale, ScaleBuilder<T>),
+            source: source_or_message!(self.source, ScaleBuilder<T>),
+        }
+    }
+
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        named_to_task!(self.scale, tree);
+        named_to_task!(self.source, tree);
+
+        self
+    }
+
+    pub fn named_scale<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.scale = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
 }
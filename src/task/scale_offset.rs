@@ -1,49 +1,53 @@
 mod builder;
 
-macro_rules! scale_offset {
-    ($type: ty) => {
-        #[derive(Clone, Debug)]
-        pub struct ScaleOffset {
-            pub(crate) offset: TaskSource,
-            pub(crate) scale: TaskSource,
-            pub(crate) source: TaskSource,
-        }
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+pub use builder::ScaleOffsetBuilder;
+
+#[derive(Clone, Debug)]
+pub struct ScaleOffset<T: Float> {
+    pub(crate) offset: TaskSource<T>,
+    pub(crate) scale: TaskSource<T>,
+    pub(crate) source: TaskSource<T>,
+}
 
-        impl ScaleOffset {
-            fn eval<F: Fn(&mut dyn Task) -> $type>(&mut self, sampler: F) -> $type {
-                let v = sampler(&mut self.source);
-                let s = sampler(&mut self.scale);
-                let o = sampler(&mut self.offset);
-                v * s + o
-            }
-        }
+impl<T: Float> ScaleOffset<T> {
+    fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+        let v = sampler(&mut self.source);
+        let s = sampler(&mut self.scale);
+        let o = sampler(&mut self.offset);
+        v * s + o
+    }
+}
 
-        impl Task for ScaleOffset {
-            fn sample_1d(&mut self, x: $type) -> $type {
-                self.eval(|t| t.sample_1d(x))
-            }
+impl<T: Float> Task<T> for ScaleOffset<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
 
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-                self.eval(|t| t.sample_2d(x, y))
-            }
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
 
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-                self.eval(|t| t.sample_3d(x, y, z))
-            }
-        }
-    };
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
 }
 
 pub mod f32 {
-    pub use super::builder::f32::ScaleOffsetBuilder;
-    use crate::task::f32::{Task, TaskSource};
-    scale_offset!(f32);
+    pub type ScaleOffset = super::ScaleOffset<f32>;
+    pub type ScaleOffsetBuilder = super::ScaleOffsetBuilder<f32>;
 }
 
 pub mod f64 {
-    pub use super::builder::f64::ScaleOffsetBuilder;
-    use crate::task::f64::{Task, TaskSource};
-    scale_offset!(f64);
+    pub type ScaleOffset = super::ScaleOffset<f64>;
+    pub type ScaleOffsetBuilder = super::ScaleOffsetBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type ScaleOffset = super::ScaleOffset<Fixed>;
+    pub type ScaleOffsetBuilder = super::ScaleOffsetBuilder<Fixed>;
 }
 
 #[cfg(test)]
@@ -1,85 +1,75 @@
-macro_rules! scale_offset_builder {
-    ($type: ty) => {
-        pub struct ScaleOffsetBuilder {
-            offset: NameOrSource,
-            scale: NameOrSource,
-            source: NameOrSource,
-        }
+use alloc::string::String;
 
-        impl Default for ScaleOffsetBuilder {
-            fn default() -> Self {
-                Self {
-                    offset: NameOrSource::Source(0.0.into()),
-                    scale: NameOrSource::Source(1.0.into()),
-                    source: NameOrSource::Source(0.0.into()),
-                }
-            }
-        }
+use super::ScaleOffset;
+use crate::float::Float;
+use crate::task::{named_to_task, source_or_message, NameOrSource, TaskSource, TaskTree};
 
-        #[allow(dead_code)]
-        impl ScaleOffsetBuilder {
-            pub fn new() -> Self {
-                Self::default()
-            }
+pub struct ScaleOffsetBuilder<T: Float> {
+    offset: NameOrSource<T>,
+    scale: NameOrSource<T>,
+    source: NameOrSource<T>,
+}
 
-            pub fn offset<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.offset = NameOrSource::Source(task.into());
-                self
-            }
+impl<T: Float> Default for ScaleOffsetBuilder<T> {
+    fn default() -> Self {
+        Self {
+            offset: NameOrSource::Source(T::ZERO.into()),
+            scale: NameOrSource::Source(T::ONE.into()),
+            source: NameOrSource::Source(T::ZERO.into()),
+        }
+    }
+}
 
-            pub fn scale<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.scale = NameOrSource::Source(task.into());
-                self
-            }
+#[allow(dead_code)]
+impl<T: Float> ScaleOffsetBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-            pub fn build(&self) -> ScaleOffset {
-                ScaleOffset {
-                    offset: source_or_message!(self.offset, ScaleOffsetBuilder),
-                    scale: source_or_message!(self.scale, ScaleOffsetBuilder),
-                    source: source_or_message!(self.source, ScaleOffsetBuilder),
-                }
-            }
+    pub fn offset<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.offset = NameOrSource::Source(task.into());
+        self
+    }
 
-            /// Link named tasks to their task tree values
-            pub fn link(&mut self, tree: &TaskTree) -> &mut Self {
-                named_to_task!(self.offset, tree);
-                named_to_task!(self.scale, tree);
-                named_to_task!(self.source, tree);
+    pub fn scale<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.scale = NameOrSource::Source(task.into());
+        self
+    }
 
-                self
-            }
+    pub fn build(&self) -> ScaleOffset<T> {
+        ScaleOffset {
+            offset: source_or_message!(self.offset, ScaleOffsetBuilder<T>),
+            scale: source_or_message!(self.scale, ScaleOffsetBuilder<T>),
+            source: source_or_message!(self.source, ScaleOffsetBuilder<T>),
+        }
+    }
 
-            pub fn named_offset<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.offset = NameOrSource::Named(name.into());
-                self
-            }
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        named_to_task!(self.offset, tree);
+        named_to_task!(self.scale, tree);
+        named_to_task!(self.source, tree);
 
-            pub fn named_scale<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.scale = NameOrSource::Named(name.into());
-                self
-            }
+        self
+    }
 
-            pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.source = NameOrSource::Named(name.into());
-                self
-            }
+    pub fn named_offset<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.offset = NameOrSource::Named(name.into());
+        self
+    }
 
-            pub fn source<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.source = NameOrSource::Source(task.into());
-                self
-            }
-        }
-    };
-}
+    pub fn named_scale<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.scale = NameOrSource::Named(name.into());
+        self
+    }
 
-pub mod f32 {
-    use crate::task::f32::{NameOrSource, ScaleOffset, TaskSource, TaskTree};
-    use crate::task::task::{named_to_task, source_or_message};
-    scale_offset_builder!(f32);
-}
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
 
-pub mod f64 {
-    use crate::task::f64::{NameOrSource, ScaleOffset, TaskSource, TaskTree};
-    use crate::task::task::{named_to_task, source_or_message};
-    scale_offset_builder!(f64);
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
 }
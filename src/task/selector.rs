@@ -1,85 +1,88 @@
 mod builder;
 
-macro_rules! selector_type {
-    ($type: ty) => {
-        #[derive(Clone, Debug)]
-        pub struct Selector {
-            pub(crate) blender: Blender,
-            pub(crate) condition: TaskSource,
-            pub(crate) lower: TaskSource,
-            pub(crate) upper: TaskSource,
-            pub(crate) falloff: TaskSource,
-            /// threshold/pivot/boundry to determine when lower or upper is used
-            pub(crate) threshold: TaskSource,
-        }
+use crate::float::Float;
+use crate::math::lerp;
+use crate::source::Blender;
+use crate::task::{Task, TaskSource};
+pub use builder::SelectorBuilder;
+
+#[derive(Clone, Debug)]
+pub struct Selector<T: Float> {
+    pub(crate) blender: Blender<T>,
+    pub(crate) condition: TaskSource<T>,
+    pub(crate) lower: TaskSource<T>,
+    pub(crate) upper: TaskSource<T>,
+    pub(crate) falloff: TaskSource<T>,
+    /// threshold/pivot/boundry to determine when lower or upper is used
+    pub(crate) threshold: TaskSource<T>,
+}
 
-        impl Selector {
-            fn eval<F>(&mut self, sampler: F) -> $type
-            where
-                F: Fn(&mut TaskSource) -> $type,
-            {
-                let c = sampler(&mut self.condition);
-                let f = sampler(&mut self.falloff);
-                let t = sampler(&mut self.threshold);
-                if f > 0.0 {
-                    if c < t - f {
-                        // outside of the threshold on the lower side
-                        sampler(&mut self.lower)
-                    } else if c > t + f {
-                        // outside of the threshold on the upper side
-                        sampler(&mut self.upper)
-                    } else {
-                        // lower bound
-                        let l = t - f;
-                        // upper bound
-                        let u = t + f;
-                        let a = (c - l) / (u - l);
-                        let b = (self.blender)(a);
-                        let lower = sampler(&mut self.lower);
-                        let upper = sampler(&mut self.upper);
-                        lerp(lower, upper, b)
-                    }
-                } else {
-                    if c < t {
-                        // outside of the threshold on the lower side
-                        sampler(&mut self.lower)
-                    } else {
-                        // outside of the threshold on the upper side
-                        sampler(&mut self.upper)
-                    }
-                }
+impl<T: Float> Selector<T> {
+    fn eval<F>(&mut self, sampler: F) -> T
+    where
+        F: Fn(&mut TaskSource<T>) -> T,
+    {
+        let c = sampler(&mut self.condition);
+        let f = sampler(&mut self.falloff);
+        let t = sampler(&mut self.threshold);
+        if f > T::ZERO {
+            if c < t - f {
+                // outside of the threshold on the lower side
+                sampler(&mut self.lower)
+            } else if c > t + f {
+                // outside of the threshold on the upper side
+                sampler(&mut self.upper)
+            } else {
+                // lower bound
+                let l = t - f;
+                // upper bound
+                let u = t + f;
+                let a = (c - l) / (u - l);
+                let b = (self.blender)(a);
+                let lower = sampler(&mut self.lower);
+                let upper = sampler(&mut self.upper);
+                lerp(lower, upper, b)
+            }
+        } else {
+            if c < t {
+                // outside of the threshold on the lower side
+                sampler(&mut self.lower)
+            } else {
+                // outside of the threshold on the upper side
+                sampler(&mut self.upper)
             }
         }
+    }
+}
 
-        impl Task for Selector {
-            fn sample_1d(&mut self, x: $type) -> $type {
-                self.eval(|s| (*s).sample_1d(x))
-            }
+impl<T: Float> Task<T> for Selector<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|s| (*s).sample_1d(x))
+    }
 
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-                self.eval(|s| (*s).sample_2d(x, y))
-            }
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|s| (*s).sample_2d(x, y))
+    }
 
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-                self.eval(|s| (*s).sample_3d(x, y, z))
-            }
-        }
-    };
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|s| (*s).sample_3d(x, y, z))
+    }
 }
 
 pub mod f32 {
-    use super::super::f32::*;
-    pub use super::builder::f32::SelectorBuilder;
-    use crate::math::f32::lerp;
-    use crate::source::f32::Blender;
-    selector_type!(f32);
+    pub type Selector = super::Selector<f32>;
+    pub type SelectorBuilder = super::SelectorBuilder<f32>;
 }
+
 pub mod f64 {
-    use super::super::f64::*;
-    pub use super::builder::f64::SelectorBuilder;
-    use crate::math::f64::lerp;
-    use crate::source::f64::Blender;
-    selector_type!(f64);
+    pub type Selector = super::Selector<f64>;
+    pub type SelectorBuilder = super::SelectorBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Selector = super::Selector<Fixed>;
+    pub type SelectorBuilder = super::SelectorBuilder<Fixed>;
 }
 
 #[cfg(test)]
@@ -1,158 +1,304 @@
+mod inspect;
 mod named_or_source;
+mod simplify;
+#[cfg(feature = "serde")]
+pub(crate) use inspect::{
+    domain_operation_name, format_levels, format_matrix, format_points, format_stops, fractal_name,
+    gradient_kind_name, interpolation_name, operation_name,
+};
 pub(crate) use named_or_source::*;
 
-macro_rules! task_source {
-    ($type: ty) => {
-        #[allow(dead_code)]
-        #[derive(Clone, Debug)]
-        pub enum TaskSource {
-            Aggregate(Aggregator),
-            Bias(Rc<RefCell<Bias>>),
-            Cache(Rc<RefCell<Cache>>),
-            Constant($type),
-            Fractal(Rc<RefCell<Fractal>>),
-            Gradient(Rc<RefCell<Gradient>>),
-            Scale(Rc<RefCell<Scale>>),
-            ScaleOffset(Rc<RefCell<ScaleOffset>>),
-            Selector(Rc<RefCell<Selector>>),
-            Domain(Rc<RefCell<TransformDomain>>),
-        }
+use alloc::rc::Rc;
+use core::cell::RefCell;
 
-        impl From<Aggregator> for TaskSource {
-            fn from(value: Aggregator) -> Self {
-                Self::Aggregate(value)
-            }
-        }
+use crate::float::Float;
+use crate::task::{
+    Abs, Aggregator, Bias, Cache, Clamp, Curve, CustomTask, Exponent, Fractal, Gradient, Invert,
+    MultiGradient, Parameter, Pow, Scale, ScaleOffset, Selector, Task, Terrace, TransformDomain,
+    Turbulence,
+};
 
-        impl From<Bias> for TaskSource {
-            fn from(value: Bias) -> Self {
-                Self::Bias(Rc::new(RefCell::new(value)))
-            }
-        }
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub enum TaskSource<T: Float> {
+    Abs(Rc<RefCell<Abs<T>>>),
+    Aggregate(Rc<RefCell<Aggregator<T>>>),
+    Bias(Rc<RefCell<Bias<T>>>),
+    Cache(Rc<RefCell<Cache<T>>>),
+    Clamp(Rc<RefCell<Clamp<T>>>),
+    Constant(T),
+    Curve(Rc<RefCell<Curve<T>>>),
+    /// Task type defined outside the crate, see [`TaskSource::custom`]
+    Custom(Rc<RefCell<dyn CustomTask<T>>>),
+    Exponent(Rc<RefCell<Exponent<T>>>),
+    Fractal(Rc<RefCell<Fractal<T>>>),
+    Gradient(Rc<RefCell<Gradient<T>>>),
+    Invert(Rc<RefCell<Invert<T>>>),
+    MultiGradient(Rc<RefCell<MultiGradient<T>>>),
+    Parameter(Rc<RefCell<Parameter<T>>>),
+    Pow(Rc<RefCell<Pow<T>>>),
+    Scale(Rc<RefCell<Scale<T>>>),
+    ScaleOffset(Rc<RefCell<ScaleOffset<T>>>),
+    Selector(Rc<RefCell<Selector<T>>>),
+    Terrace(Rc<RefCell<Terrace<T>>>),
+    Domain(Rc<RefCell<TransformDomain<T>>>),
+    Turbulence(Rc<RefCell<Turbulence<T>>>),
+}
 
-        impl From<Cache> for TaskSource {
-            fn from(value: Cache) -> Self {
-                Self::Cache(Rc::new(RefCell::new(value)))
-            }
-        }
+impl<T: Float> From<Abs<T>> for TaskSource<T> {
+    fn from(value: Abs<T>) -> Self {
+        Self::Abs(Rc::new(RefCell::new(value)))
+    }
+}
 
-        impl From<$type> for TaskSource {
-            fn from(value: $type) -> Self {
-                Self::Constant(value)
-            }
-        }
+impl<T: Float> From<Aggregator<T>> for TaskSource<T> {
+    fn from(value: Aggregator<T>) -> Self {
+        Self::Aggregate(Rc::new(RefCell::new(value)))
+    }
+}
 
-        impl From<Fractal> for TaskSource {
-            fn from(value: Fractal) -> Self {
-                Self::Fractal(Rc::new(RefCell::new(value)))
-            }
-        }
+impl<T: Float> From<Bias<T>> for TaskSource<T> {
+    fn from(value: Bias<T>) -> Self {
+        Self::Bias(Rc::new(RefCell::new(value)))
+    }
+}
 
-        impl From<Gradient> for TaskSource {
-            fn from(value: Gradient) -> Self {
-                Self::Gradient(Rc::new(RefCell::new(value)))
-            }
-        }
+impl<T: Float> From<Cache<T>> for TaskSource<T> {
+    fn from(value: Cache<T>) -> Self {
+        Self::Cache(Rc::new(RefCell::new(value)))
+    }
+}
 
-        impl From<Scale> for TaskSource {
-            fn from(value: Scale) -> Self {
-                Self::Scale(Rc::new(RefCell::new(value)))
-            }
-        }
+impl<T: Float> From<Clamp<T>> for TaskSource<T> {
+    fn from(value: Clamp<T>) -> Self {
+        Self::Clamp(Rc::new(RefCell::new(value)))
+    }
+}
 
-        impl From<ScaleOffset> for TaskSource {
-            fn from(value: ScaleOffset) -> Self {
-                Self::ScaleOffset(Rc::new(RefCell::new(value)))
-            }
-        }
+impl<T: Float> From<T> for TaskSource<T> {
+    fn from(value: T) -> Self {
+        Self::Constant(value)
+    }
+}
+
+impl<T: Float> From<Exponent<T>> for TaskSource<T> {
+    fn from(value: Exponent<T>) -> Self {
+        Self::Exponent(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<Curve<T>> for TaskSource<T> {
+    fn from(value: Curve<T>) -> Self {
+        Self::Curve(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<Fractal<T>> for TaskSource<T> {
+    fn from(value: Fractal<T>) -> Self {
+        Self::Fractal(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<Gradient<T>> for TaskSource<T> {
+    fn from(value: Gradient<T>) -> Self {
+        Self::Gradient(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<Invert<T>> for TaskSource<T> {
+    fn from(value: Invert<T>) -> Self {
+        Self::Invert(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<MultiGradient<T>> for TaskSource<T> {
+    fn from(value: MultiGradient<T>) -> Self {
+        Self::MultiGradient(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<Parameter<T>> for TaskSource<T> {
+    fn from(value: Parameter<T>) -> Self {
+        Self::Parameter(Rc::new(RefCell::new(value)))
+    }
+}
 
-        impl From<Selector> for TaskSource {
-            fn from(value: Selector) -> Self {
-                Self::Selector(Rc::new(RefCell::new(value)))
-            }
+impl<T: Float> From<Pow<T>> for TaskSource<T> {
+    fn from(value: Pow<T>) -> Self {
+        Self::Pow(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<Scale<T>> for TaskSource<T> {
+    fn from(value: Scale<T>) -> Self {
+        Self::Scale(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<ScaleOffset<T>> for TaskSource<T> {
+    fn from(value: ScaleOffset<T>) -> Self {
+        Self::ScaleOffset(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<Selector<T>> for TaskSource<T> {
+    fn from(value: Selector<T>) -> Self {
+        Self::Selector(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<Terrace<T>> for TaskSource<T> {
+    fn from(value: Terrace<T>) -> Self {
+        Self::Terrace(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<TransformDomain<T>> for TaskSource<T> {
+    fn from(value: TransformDomain<T>) -> Self {
+        Self::Domain(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> From<Turbulence<T>> for TaskSource<T> {
+    fn from(value: Turbulence<T>) -> Self {
+        Self::Turbulence(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<T: Float> TaskSource<T> {
+    /// Wraps a task type defined outside the crate so it can be added to a tree.
+    pub fn custom<C: CustomTask<T> + 'static>(task: C) -> Self {
+        Self::Custom(Rc::new(RefCell::new(task)))
+    }
+}
+
+impl<T: Float> Task<T> for TaskSource<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        match self {
+            Self::Abs(t) => t.borrow_mut().sample_1d(x),
+            Self::Aggregate(t) => t.borrow_mut().sample_1d(x),
+            Self::Bias(t) => t.borrow_mut().sample_1d(x),
+            Self::Cache(t) => t.borrow_mut().sample_1d(x),
+            Self::Clamp(t) => t.borrow_mut().sample_1d(x),
+            Self::Constant(v) => *v,
+            Self::Curve(t) => t.borrow_mut().sample_1d(x),
+            Self::Custom(t) => t.borrow_mut().sample_1d(x),
+            Self::Exponent(t) => t.borrow_mut().sample_1d(x),
+            Self::Fractal(t) => t.borrow_mut().sample_1d(x),
+            Self::Gradient(t) => t.borrow_mut().sample_1d(x),
+            Self::Invert(t) => t.borrow_mut().sample_1d(x),
+            Self::MultiGradient(t) => t.borrow_mut().sample_1d(x),
+            Self::Parameter(t) => t.borrow_mut().sample_1d(x),
+            Self::Pow(t) => t.borrow_mut().sample_1d(x),
+            Self::Scale(t) => t.borrow_mut().sample_1d(x),
+            Self::ScaleOffset(t) => t.borrow_mut().sample_1d(x),
+            Self::Selector(t) => t.borrow_mut().sample_1d(x),
+            Self::Terrace(t) => t.borrow_mut().sample_1d(x),
+            Self::Domain(t) => t.borrow_mut().sample_1d(x),
+            Self::Turbulence(t) => t.borrow_mut().sample_1d(x),
         }
+    }
 
-        impl From<TransformDomain> for TaskSource {
-            fn from(value: TransformDomain) -> Self {
-                Self::Domain(Rc::new(RefCell::new(value)))
-            }
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        match self {
+            Self::Abs(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Aggregate(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Bias(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Cache(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Clamp(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Constant(v) => *v,
+            Self::Curve(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Custom(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Exponent(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Fractal(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Gradient(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Invert(t) => t.borrow_mut().sample_2d(x, y),
+            Self::MultiGradient(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Parameter(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Pow(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Scale(t) => t.borrow_mut().sample_2d(x, y),
+            Self::ScaleOffset(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Selector(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Terrace(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Domain(t) => t.borrow_mut().sample_2d(x, y),
+            Self::Turbulence(t) => t.borrow_mut().sample_2d(x, y),
         }
+    }
 
-        impl Task for TaskSource {
-            fn sample_1d(&mut self, x: $type) -> $type {
-                match self {
-                    Self::Aggregate(t) => t.sample_1d(x),
-                    Self::Bias(t) => t.borrow_mut().sample_1d(x),
-                    Self::Cache(t) => t.borrow_mut().sample_1d(x),
-                    Self::Constant(v) => v.clone(),
-                    Self::Fractal(t) => t.borrow_mut().sample_1d(x),
-                    Self::Gradient(t) => t.borrow_mut().sample_1d(x),
-                    Self::Scale(t) => t.borrow_mut().sample_1d(x),
-                    Self::ScaleOffset(t) => t.borrow_mut().sample_1d(x),
-                    Self::Selector(t) => t.borrow_mut().sample_1d(x),
-                    Self::Domain(t) => t.borrow_mut().sample_1d(x),
-                }
-            }
-
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-                match self {
-                    Self::Aggregate(t) => t.sample_2d(x, y),
-                    Self::Bias(t) => t.borrow_mut().sample_2d(x, y),
-                    Self::Cache(t) => t.borrow_mut().sample_2d(x, y),
-                    Self::Constant(v) => v.clone(),
-                    Self::Fractal(t) => t.borrow_mut().sample_2d(x, y),
-                    Self::Gradient(t) => t.borrow_mut().sample_2d(x, y),
-                    Self::Scale(t) => t.borrow_mut().sample_2d(x, y),
-                    Self::ScaleOffset(t) => t.borrow_mut().sample_2d(x, y),
-                    Self::Selector(t) => t.borrow_mut().sample_2d(x, y),
-                    Self::Domain(t) => t.borrow_mut().sample_2d(x, y),
-                }
-            }
-
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-                match self {
-                    Self::Aggregate(t) => t.sample_3d(x, y, z),
-                    Self::Bias(t) => t.borrow_mut().sample_3d(x, y, z),
-                    Self::Cache(t) => t.borrow_mut().sample_3d(x, y, z),
-                    Self::Constant(v) => v.clone(),
-                    Self::Fractal(t) => t.borrow_mut().sample_3d(x, y, z),
-                    Self::Gradient(t) => t.borrow_mut().sample_3d(x, y, z),
-                    Self::Scale(t) => t.borrow_mut().sample_3d(x, y, z),
-                    Self::ScaleOffset(t) => t.borrow_mut().sample_3d(x, y, z),
-                    Self::Selector(t) => t.borrow_mut().sample_3d(x, y, z),
-                    Self::Domain(t) => t.borrow_mut().sample_3d(x, y, z),
-                }
-            }
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        match self {
+            Self::Abs(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Aggregate(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Bias(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Cache(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Clamp(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Constant(v) => *v,
+            Self::Curve(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Custom(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Exponent(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Fractal(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Gradient(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Invert(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::MultiGradient(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Parameter(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Pow(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Scale(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::ScaleOffset(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Selector(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Terrace(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Domain(t) => t.borrow_mut().sample_3d(x, y, z),
+            Self::Turbulence(t) => t.borrow_mut().sample_3d(x, y, z),
         }
-    };
+    }
 }
 
 pub mod f32 {
-    pub(crate) use super::named_or_source::f32::NameOrSource;
-    use crate::task::f32::{
-        Aggregator, Bias, Cache, Fractal, Gradient, Scale, ScaleOffset, Selector, Task,
-        TransformDomain,
-    };
-    use std::{cell::RefCell, rc::Rc};
-    task_source!(f32);
+    pub type TaskSource = super::TaskSource<f32>;
 }
 
 pub mod f64 {
-    pub(crate) use super::named_or_source::f64::NameOrSource;
-    use crate::task::f64::{
-        Aggregator, Bias, Cache, Fractal, Gradient, Scale, ScaleOffset, Selector, Task,
-        TransformDomain,
-    };
-    use std::{cell::RefCell, rc::Rc};
-    task_source!(f64);
+    pub type TaskSource = super::TaskSource<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type TaskSource = super::TaskSource<Fixed>;
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::float::Float;
+    use crate::task::{Task, TaskSource};
+
+    /// Custom task written once for both precisions.
+    struct Halve<T: Float>(TaskSource<T>);
+
+    impl<T: Float> Task<T> for Halve<T> {
+        fn sample_1d(&mut self, x: T) -> T {
+            self.0.sample_1d(x) * T::HALF
+        }
+
+        fn sample_2d(&mut self, x: T, y: T) -> T {
+            self.0.sample_2d(x, y) * T::HALF
+        }
+
+        fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+            self.0.sample_3d(x, y, z) * T::HALF
+        }
+    }
 
     mod f32 {
+        use super::Halve;
         use crate::task::f32::{CacheBuilder, Task, TaskSource};
 
+        #[test]
+        fn generic_task() {
+            let mut result = Halve(TaskSource::Constant(0.5));
+            assert_eq!(result.sample_1d(1.0), 0.25_f32);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.25_f32);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.25_f32);
+        }
+
         #[test]
         fn task_type_constant_tests() {
             let mut result = TaskSource::Constant(0.5);
@@ -187,8 +333,17 @@ mod tests {
     }
 
     mod f64 {
+        use super::Halve;
         use crate::task::f64::{CacheBuilder, Task, TaskSource};
 
+        #[test]
+        fn generic_task() {
+            let mut result = Halve(TaskSource::Constant(0.5));
+            assert_eq!(result.sample_1d(1.0), 0.25_f64);
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.25_f64);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.25_f64);
+        }
+
         #[test]
         fn task_type_constant_tests() {
             let mut result = TaskSource::Constant(0.5);
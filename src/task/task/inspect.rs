@@ -0,0 +1,329 @@
+use alloc::borrow::ToOwned;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use crate::float::Float;
+use crate::task::{
+    DomainOperation, FractalType, GradientKind, GradientStop, Interpolation, Operation, TaskSource,
+};
+
+pub(crate) fn operation_name(op: Operation) -> &'static str {
+    match op {
+        Operation::Add => "add",
+        Operation::Avg => "avg",
+        Operation::Sub => "sub",
+        Operation::Mul => "mul",
+        Operation::Div => "div",
+        Operation::Max => "max",
+        Operation::Min => "min",
+    }
+}
+
+pub(crate) fn fractal_name(fractal: FractalType) -> &'static str {
+    match fractal {
+        FractalType::Billowing => "billowing",
+        FractalType::Brownian => "brownian",
+        FractalType::RidgedMulti => "ridged_multi",
+    }
+}
+
+pub(crate) fn domain_operation_name(op: DomainOperation) -> &'static str {
+    match op {
+        DomainOperation::Translate => "translate",
+        DomainOperation::Scale => "scale",
+        DomainOperation::Rotate => "rotate",
+        DomainOperation::Affine => "affine",
+        DomainOperation::Warp => "warp",
+    }
+}
+
+pub(crate) fn gradient_kind_name(kind: GradientKind) -> &'static str {
+    match kind {
+        GradientKind::Linear => "linear",
+        GradientKind::Radial => "radial",
+        GradientKind::Spherical => "spherical",
+        GradientKind::Cylindrical => "cylindrical",
+    }
+}
+
+pub(crate) fn interpolation_name(interpolation: Interpolation) -> &'static str {
+    match interpolation {
+        Interpolation::Linear => "linear",
+        Interpolation::CatmullRom => "catmull_rom",
+        Interpolation::MonotoneCubic => "monotone_cubic",
+    }
+}
+
+/// Formats control points as `[in, out]` pairs.
+pub(crate) fn format_points<T: core::fmt::Display>(points: &[[T; 2]]) -> String {
+    let points: Vec<String> = points
+        .iter()
+        .map(|[x, y]| format!("[{}, {}]", x, y))
+        .collect();
+    format!("[{}]", points.join(", "))
+}
+
+/// Formats gradient stops as `[position, value, interpolation]` lists.
+pub(crate) fn format_stops<T: Float>(stops: &[GradientStop<T>]) -> String {
+    let stops: Vec<String> = stops
+        .iter()
+        .map(|s| {
+            let interpolation = interpolation_name(s.interpolation);
+            format!("[{}, {}, {}]", s.position, s.value, interpolation)
+        })
+        .collect();
+    format!("[{}]", stops.join(", "))
+}
+
+/// Formats the rows of a matrix as nested lists.
+pub(crate) fn format_matrix<T: core::fmt::Display>(matrix: &[[T; 3]; 3]) -> String {
+    let rows: Vec<String> = matrix.iter().map(|row| format_levels(row)).collect();
+    format!("[{}]", rows.join(", "))
+}
+
+/// Formats terrace levels as a list.
+pub(crate) fn format_levels<T: core::fmt::Display>(levels: &[T]) -> String {
+    let levels: Vec<String> = levels.iter().map(|x| x.to_string()).collect();
+    format!("[{}]", levels.join(", "))
+}
+
+impl<T: Float> TaskSource<T> {
+    /// Name of the task type, matching its name in configs.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Abs(_) => "abs",
+            Self::Aggregate(_) => "aggregate",
+            Self::Bias(_) => "bias",
+            Self::Cache(_) => "cache",
+            Self::Clamp(_) => "clamp",
+            Self::Constant(_) => "constant",
+            Self::Curve(_) => "curve",
+            Self::Custom(_) => "custom",
+            Self::Exponent(_) => "exponent",
+            Self::Fractal(_) => "fractal",
+            Self::Gradient(_) => "gradient",
+            Self::Invert(_) => "invert",
+            Self::MultiGradient(_) => "multi_gradient",
+            Self::Parameter(_) => "parameter",
+            Self::Pow(_) => "pow",
+            Self::Scale(_) => "scale",
+            Self::ScaleOffset(_) => "scale_offset",
+            Self::Selector(_) => "selector",
+            Self::Terrace(_) => "terrace",
+            Self::Domain(_) => "transform_domain",
+            Self::Turbulence(_) => "turbulence",
+        }
+    }
+
+    /// The settings of the task that aren't inputs, formatted for display.
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::Aggregate(t) => {
+                let t = t.borrow();
+                vec![
+                    ("operator", operation_name(t.op).to_owned()),
+                    ("initial", t.initial.to_string()),
+                ]
+            }
+            Self::Bias(t) => {
+                let t = t.borrow();
+                vec![("min", t.min.to_string()), ("max", t.max.to_string())]
+            }
+            Self::Constant(v) => vec![("value", v.to_string())],
+            Self::Curve(t) => {
+                let t = t.borrow();
+                vec![
+                    (
+                        "interpolation",
+                        interpolation_name(t.interpolation).to_owned(),
+                    ),
+                    ("points", format_points(&t.points)),
+                ]
+            }
+            Self::Custom(t) => {
+                let t = t.borrow();
+                let mut r = vec![("type", t.kind().to_owned())];
+                r.extend(t.params());
+                r
+            }
+            Self::Fractal(t) => {
+                let t = t.borrow();
+                vec![
+                    ("fractal", fractal_name(t.fractal).to_owned()),
+                    ("octaves", t.config.octaves.to_string()),
+                    ("frequency", t.config.frequency.to_string()),
+                    ("amplitude", t.config.amplitude.to_string()),
+                    ("lacunarity", t.config.lacunarity.to_string()),
+                    ("gain", t.config.gain.to_string()),
+                ]
+            }
+            Self::Parameter(t) => vec![("value", t.borrow().value.to_string())],
+            Self::Domain(t) => {
+                let t = t.borrow();
+                let operations: Vec<&str> = t
+                    .steps
+                    .iter()
+                    .map(|x| domain_operation_name(x.operation))
+                    .collect();
+                let mut r = vec![("operation", operations.join(", "))];
+                for step in t.steps.iter() {
+                    if step.operation == DomainOperation::Affine {
+                        r.push(("matrix", format_matrix(&step.matrix)));
+                    }
+                }
+                r
+            }
+            Self::Cache(t) => vec![("strategy", t.borrow().strategy.to_string())],
+            Self::Gradient(t) => vec![("kind", gradient_kind_name(t.borrow().kind()).to_owned())],
+            Self::MultiGradient(t) => {
+                let t = t.borrow();
+                vec![
+                    ("kind", gradient_kind_name(t.gradient.kind()).to_owned()),
+                    ("stops", format_stops(&t.stops)),
+                ]
+            }
+            Self::Turbulence(t) => vec![("iterations", t.borrow().iterations.to_string())],
+            Self::Terrace(t) => {
+                let t = t.borrow();
+                vec![
+                    ("levels", format_levels(&t.levels)),
+                    ("smooth", t.smooth.to_string()),
+                    ("invert", t.invert.to_string()),
+                ]
+            }
+            Self::Abs(_)
+            | Self::Clamp(_)
+            | Self::Exponent(_)
+            | Self::Invert(_)
+            | Self::Pow(_)
+            | Self::Scale(_)
+            | Self::ScaleOffset(_)
+            | Self::Selector(_) => vec![],
+        }
+    }
+
+    /// The tasks this task samples, named after the config field they come from.
+    pub fn inputs(&self) -> Vec<(String, TaskSource<T>)> {
+        let mut result = vec![];
+        self.clone()
+            .visit_inputs_mut(&mut |name, x| result.push((name.to_owned(), x.clone())));
+        result
+    }
+
+    /// Calls `f` with every input of the task and the name of its field.
+    pub(crate) fn visit_inputs_mut(&mut self, f: &mut dyn FnMut(&str, &mut TaskSource<T>)) {
+        match self {
+            Self::Abs(t) => f("source", &mut t.borrow_mut().source),
+            Self::Aggregate(t) => {
+                for (i, x) in t.borrow_mut().sources.iter_mut().enumerate() {
+                    f(&format!("source.{}", i), x);
+                }
+            }
+            Self::Bias(t) => {
+                let mut t = t.borrow_mut();
+                f("bias", &mut t.bias);
+                f("source", &mut t.source);
+            }
+            Self::Cache(t) => f("source", &mut t.borrow_mut().source),
+            Self::Clamp(t) => {
+                let mut t = t.borrow_mut();
+                f("max", &mut t.max);
+                f("min", &mut t.min);
+                f("source", &mut t.source);
+            }
+            Self::Curve(t) => f("source", &mut t.borrow_mut().source),
+            Self::Custom(t) => t.borrow_mut().visit_inputs_mut(f),
+            Self::Exponent(t) => {
+                let mut t = t.borrow_mut();
+                f("exponent", &mut t.exponent);
+                f("source", &mut t.source);
+            }
+            Self::Invert(t) => f("source", &mut t.borrow_mut().source),
+            Self::Pow(t) => {
+                let mut t = t.borrow_mut();
+                f("exponent", &mut t.exponent);
+                f("source", &mut t.source);
+            }
+            Self::Scale(t) => {
+                let mut t = t.borrow_mut();
+                f("scale", &mut t.scale);
+                f("source", &mut t.source);
+            }
+            Self::ScaleOffset(t) => {
+                let mut t = t.borrow_mut();
+                f("offset", &mut t.offset);
+                f("scale", &mut t.scale);
+                f("source", &mut t.source);
+            }
+            Self::Selector(t) => {
+                let mut t = t.borrow_mut();
+                f("condition", &mut t.condition);
+                f("lower", &mut t.lower);
+                f("upper", &mut t.upper);
+                f("falloff", &mut t.falloff);
+                f("threshold", &mut t.threshold);
+            }
+            Self::Terrace(t) => f("source", &mut t.borrow_mut().source),
+            Self::Domain(t) => {
+                let mut t = t.borrow_mut();
+                let single = t.steps.len() == 1;
+                for (i, step) in t.steps.iter_mut().enumerate() {
+                    let prefix = if single {
+                        String::new()
+                    } else {
+                        format!("operations.{}.", i)
+                    };
+                    let [dx, dy, dz] = &mut step.value;
+                    f(&format!("{}dx", prefix), dx);
+                    f(&format!("{}dy", prefix), dy);
+                    f(&format!("{}dz", prefix), dz);
+                }
+                f("source", &mut t.source);
+            }
+            Self::Turbulence(t) => {
+                let mut t = t.borrow_mut();
+                f("dx", &mut t.dx);
+                f("dy", &mut t.dy);
+                f("dz", &mut t.dz);
+                f("power", &mut t.power);
+                f("source", &mut t.source);
+            }
+            Self::Constant(_)
+            | Self::Fractal(_)
+            | Self::Gradient(_)
+            | Self::MultiGradient(_)
+            | Self::Parameter(_) => {}
+        }
+    }
+
+    /// Returns true if both sources are the same task.
+    ///
+    /// Constants are copied into the tasks using them, so they are never the same task.
+    pub fn ptr_eq(&self, other: &TaskSource<T>) -> bool {
+        match (self, other) {
+            (Self::Abs(a), Self::Abs(b)) => Rc::ptr_eq(a, b),
+            (Self::Aggregate(a), Self::Aggregate(b)) => Rc::ptr_eq(a, b),
+            (Self::Bias(a), Self::Bias(b)) => Rc::ptr_eq(a, b),
+            (Self::Cache(a), Self::Cache(b)) => Rc::ptr_eq(a, b),
+            (Self::Clamp(a), Self::Clamp(b)) => Rc::ptr_eq(a, b),
+            (Self::Curve(a), Self::Curve(b)) => Rc::ptr_eq(a, b),
+            (Self::Custom(a), Self::Custom(b)) => Rc::ptr_eq(a, b),
+            (Self::Exponent(a), Self::Exponent(b)) => Rc::ptr_eq(a, b),
+            (Self::Fractal(a), Self::Fractal(b)) => Rc::ptr_eq(a, b),
+            (Self::Gradient(a), Self::Gradient(b)) => Rc::ptr_eq(a, b),
+            (Self::Invert(a), Self::Invert(b)) => Rc::ptr_eq(a, b),
+            (Self::MultiGradient(a), Self::MultiGradient(b)) => Rc::ptr_eq(a, b),
+            (Self::Parameter(a), Self::Parameter(b)) => Rc::ptr_eq(a, b),
+            (Self::Pow(a), Self::Pow(b)) => Rc::ptr_eq(a, b),
+            (Self::Scale(a), Self::Scale(b)) => Rc::ptr_eq(a, b),
+            (Self::ScaleOffset(a), Self::ScaleOffset(b)) => Rc::ptr_eq(a, b),
+            (Self::Selector(a), Self::Selector(b)) => Rc::ptr_eq(a, b),
+            (Self::Terrace(a), Self::Terrace(b)) => Rc::ptr_eq(a, b),
+            (Self::Domain(a), Self::Domain(b)) => Rc::ptr_eq(a, b),
+            (Self::Turbulence(a), Self::Turbulence(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
@@ -3,7 +3,7 @@ macro_rules! source_or_message {
         match &$value {
             NameOrSource::Source(x) => x.clone(),
             _ => {
-                let class_name = std::any::type_name::<$class>();
+                let class_name = core::any::type_name::<$class>();
                 let func = module_path!();
                 panic!("{}::link must be called if {} is used", class_name, func);
             }
@@ -27,21 +27,12 @@ macro_rules! named_to_task {
 pub(crate) use named_to_task;
 pub(crate) use source_or_message;
 
-macro_rules! name_or_source_type {
-	() => {
-		pub(crate) enum NameOrSource {
-			Named(String),
-			Source(TaskSource),
-		}
-	};
-}
+use alloc::string::String;
 
-pub mod f32 {
-	use super::super::f32::TaskSource;
-	name_or_source_type!();
-}
+use super::TaskSource;
+use crate::float::Float;
 
-pub mod f64 {
-	use super::super::f64::TaskSource;
-	name_or_source_type!();
-}
\ No newline at end of file
+pub(crate) enum NameOrSource<T: Float> {
+	Named(String),
+	Source(TaskSource<T>),
+}
@@ -0,0 +1,204 @@
+use alloc::vec::Vec;
+
+use crate::float::Float;
+use crate::task::{Aggregator, Operation, Scale, ScaleOffset, Task, TaskSource, TransformDomain};
+
+impl<T: Float> TaskSource<T> {
+    /// Returns a task sampling the same values as this one with less work.
+    ///
+    /// Inputs are simplified first, in place, so every task sharing them benefits. Tasks
+    /// whose inputs are all constants are folded into a constant, operations that
+    /// return their input unchanged are skipped and consecutive inversions and scales by
+    /// constants are merged. Merging reorders the arithmetic, so results may differ in the
+    /// last bits.
+    ///
+    /// `named` tasks are kept as they are: they are never merged into the tasks
+    /// sampling them and a named task is never replaced by another named task. A named task
+    /// simplified into a new task is added to `named`, so its dependents don't merge it.
+    /// `done` maps the tasks already simplified to their result.
+    pub(crate) fn simplify(
+        &self,
+        named: &mut Vec<TaskSource<T>>,
+        done: &mut Vec<(TaskSource<T>, TaskSource<T>)>,
+    ) -> TaskSource<T> {
+        if let Self::Constant(_) = self {
+            return self.clone();
+        }
+        if let Some((_, result)) = done.iter().find(|(x, _)| x.ptr_eq(self)) {
+            return result.clone();
+        }
+
+        self.clone()
+            .visit_inputs_mut(&mut |_, x| *x = x.simplify(named, done));
+
+        let is_named = |x: &TaskSource<T>| named.iter().any(|y| y.ptr_eq(x));
+        let mut result = self.fold(&is_named);
+        if is_named(self) && !result.ptr_eq(self) {
+            if is_named(&result) {
+                result = self.clone();
+            } else {
+                named.push(result.clone());
+            }
+        }
+
+        done.push((self.clone(), result.clone()));
+        result
+    }
+
+    /// Simplifies this task alone, assuming its inputs are already simplified.
+    fn fold(&self, is_named: &dyn Fn(&TaskSource<T>) -> bool) -> TaskSource<T> {
+        let constant = |x: &TaskSource<T>| match x {
+            Self::Constant(v) => Some(*v),
+            _ => None,
+        };
+
+        let mut all_constant = true;
+        self.clone()
+            .visit_inputs_mut(&mut |_, x| all_constant &= constant(x).is_some());
+
+        match self {
+            Self::Abs(_)
+            | Self::Aggregate(_)
+            | Self::Bias(_)
+            | Self::Cache(_)
+            | Self::Clamp(_)
+            | Self::Curve(_)
+            | Self::Exponent(_)
+            | Self::Invert(_)
+            | Self::Pow(_)
+            | Self::Scale(_)
+            | Self::ScaleOffset(_)
+            | Self::Selector(_)
+            | Self::Terrace(_)
+                if all_constant =>
+            {
+                Self::Constant(self.clone().sample_1d(T::ZERO))
+            }
+            Self::Aggregate(t) => self.fold_aggregate(&t.borrow()),
+            Self::Invert(_) | Self::Scale(_) | Self::ScaleOffset(_) => {
+                let Some((source, scale, offset)) = self.affine() else {
+                    return self.clone();
+                };
+                match source.affine() {
+                    Some((inner, inner_scale, inner_offset)) if !is_named(&source) => {
+                        Self::from_affine(inner, inner_scale * scale, inner_offset * scale + offset)
+                    }
+                    _ if scale == T::ONE && offset == T::ZERO => source,
+                    _ => self.clone(),
+                }
+            }
+            Self::Domain(t) => {
+                let t = t.borrow();
+                let steps: Vec<_> = t
+                    .steps
+                    .iter()
+                    .filter(|step| {
+                        let identity = step.identity_value();
+                        identity.is_none() || step.value.iter().any(|x| constant(x) != identity)
+                    })
+                    .cloned()
+                    .collect();
+                if constant(&t.source).is_some() || steps.is_empty() {
+                    t.source.clone()
+                } else if steps.len() < t.steps.len() {
+                    TransformDomain {
+                        source: t.source.clone(),
+                        steps,
+                    }
+                    .into()
+                } else {
+                    self.clone()
+                }
+            }
+            Self::Turbulence(t) => {
+                let t = t.borrow();
+                if constant(&t.source).is_some()
+                    || t.iterations == 0
+                    || constant(&t.power) == Some(T::ZERO)
+                {
+                    t.source.clone()
+                } else {
+                    self.clone()
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Folds the constant sources of an aggregate into its initial value and skips it
+    /// when only one source is left and the initial value doesn't change it.
+    fn fold_aggregate(&self, t: &Aggregator<T>) -> TaskSource<T> {
+        // The average depends on the number of sources, so they all have to stay
+        if t.op == Operation::Avg {
+            if t.sources.len() == 1 && t.initial == T::ZERO {
+                return t.sources[0].clone();
+            }
+            return self.clone();
+        }
+
+        let (constants, sources): (Vec<TaskSource<T>>, Vec<TaskSource<T>>) = t
+            .sources
+            .iter()
+            .cloned()
+            .partition(|x| matches!(x, Self::Constant(_)));
+        let initial = Aggregator::new(t.op, t.initial, constants).sample_1d(T::ZERO);
+
+        let identity = match t.op {
+            Operation::Add => Some(T::ZERO),
+            Operation::Mul => Some(T::ONE),
+            Operation::Max => Some(T::NEG_INFINITY),
+            Operation::Min => Some(T::INFINITY),
+            Operation::Avg | Operation::Div | Operation::Sub => None,
+        };
+        match sources.len() {
+            0 => Self::Constant(initial),
+            1 if identity == Some(initial) => sources[0].clone(),
+            n if n == t.sources.len() => self.clone(),
+            _ => Aggregator::new(t.op, initial, sources).into(),
+        }
+    }
+
+    /// Returns the source, scale and offset of an inversion or a scale whose scale and
+    /// offset are constants.
+    fn affine(&self) -> Option<(TaskSource<T>, T, T)> {
+        match self {
+            Self::Invert(t) => Some((t.borrow().source.clone(), -T::ONE, T::ZERO)),
+            Self::Scale(t) => {
+                let t = t.borrow();
+                match t.scale {
+                    Self::Constant(scale) => Some((t.source.clone(), scale, T::ZERO)),
+                    _ => None,
+                }
+            }
+            Self::ScaleOffset(t) => {
+                let t = t.borrow();
+                match (&t.scale, &t.offset) {
+                    (Self::Constant(scale), Self::Constant(offset)) => {
+                        Some((t.source.clone(), *scale, *offset))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn from_affine(source: TaskSource<T>, scale: T, offset: T) -> TaskSource<T> {
+        if scale == T::ONE && offset == T::ZERO {
+            source
+        } else if offset == T::ZERO {
+            Scale {
+                scale: scale.into(),
+                source,
+            }
+            .into()
+        } else {
+            ScaleOffset {
+                offset: offset.into(),
+                scale: scale.into(),
+                source,
+            }
+            .into()
+        }
+    }
+}
@@ -1,70 +1,361 @@
-macro_rules! task_tree {
-    ($type: ty) => {
-        pub struct TaskTree {
-            tasks: HashMap<String, TaskSource>,
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use crate::float::Float;
+use crate::graph::{Edge, Graph, Node};
+use crate::task::{Task, TaskSource};
+
+pub struct TaskTree<T: Float> {
+    tasks: BTreeMap<String, TaskSource<T>>,
+    generation: u64,
+}
+
+impl<T: Float> Default for TaskTree<T> {
+    fn default() -> Self {
+        Self {
+            tasks: BTreeMap::new(),
+            generation: 0,
+        }
+    }
+}
+
+impl<T: Float> TaskTree<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_task<S: Into<String>, V: Into<TaskSource<T>>>(&mut self, name: S, task: V) {
+        self.tasks.insert(name.into(), task.into());
+    }
+
+    pub fn get<S: Into<String>>(&self, name: S) -> Option<&TaskSource<T>> {
+        self.tasks.get(&name.into())
+    }
+
+    pub fn get_mut<S: Into<String>>(&mut self, name: S) -> Option<&mut TaskSource<T>> {
+        self.tasks.get_mut(&name.into())
+    }
+
+    /// Names of every task in the tree, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tasks.keys().map(|x| x.as_str())
+    }
+
+    /// Returns the name under which `task` was added to the tree, the first one in order if
+    /// it was added under several names.
+    pub fn name_of(&self, task: &TaskSource<T>) -> Option<&str> {
+        self.tasks
+            .iter()
+            .find(|(_, x)| x.ptr_eq(task))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Every name the task `name` was added under, including `name`, in order.
+    ///
+    /// A task added under several names is a single task: sampling one of the names samples
+    /// all of them and replacing one replaces all of them.
+    pub fn aliases<S: Into<String>>(&self, name: S) -> Vec<String> {
+        match self.tasks.get(&name.into()) {
+            Some(task) => self.names_of(task),
+            None => vec![],
         }
+    }
+
+    fn names_of(&self, task: &TaskSource<T>) -> Vec<String> {
+        self.tasks
+            .iter()
+            .filter(|(_, x)| x.ptr_eq(task))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Names of the tasks `name` samples directly, looking through the anonymous tasks
+    /// in between. A task added under several names is listed under each of them.
+    pub fn dependencies<S: Into<String>>(&self, name: S) -> Vec<String> {
+        let mut result = vec![];
+        if let Some(task) = self.tasks.get(&name.into()) {
+            self.named_inputs(task, &mut result);
+        }
+        result
+    }
 
-        impl TaskTree {
-            pub fn new() -> Self {
-                Self {
-                    tasks: HashMap::new(),
+    fn named_inputs(&self, task: &TaskSource<T>, result: &mut Vec<String>) {
+        for (_, input) in task.inputs() {
+            let names = self.names_of(&input);
+            if names.is_empty() {
+                self.named_inputs(&input, result);
+            }
+            for name in names {
+                if !result.contains(&name) {
+                    result.push(name);
                 }
             }
+        }
+    }
 
-            pub fn add_task<S: Into<String>, V: Into<TaskSource>>(&mut self, name: S, task: V) {
-                self.tasks.insert(name.into(), task.into());
+    /// Names of the tasks sampling `name` directly.
+    pub fn dependents<S: Into<String>>(&self, name: S) -> Vec<String> {
+        let name: String = name.into();
+        let mut result: Vec<String> = self
+            .names()
+            .filter(|x| self.dependencies(*x).contains(&name))
+            .map(|x| x.to_owned())
+            .collect();
+        result.sort();
+        result
+    }
+
+    /// Returns true if `name` samples `target`, directly or through other tasks.
+    pub fn depends_on(&self, name: &str, target: &str) -> bool {
+        let mut pending = vec![name.to_owned()];
+        let mut visited = vec![];
+        while let Some(name) = pending.pop() {
+            for dependency in self.dependencies(&name) {
+                if dependency == target {
+                    return true;
+                }
+                if !visited.contains(&dependency) {
+                    visited.push(dependency.clone());
+                    pending.push(dependency);
+                }
             }
+        }
+        false
+    }
+
+    /// Removes the task `name`, failing if other tasks still sample it and it has no other
+    /// name.
+    pub fn remove<S: Into<String>>(&mut self, name: S) -> Result<TaskSource<T>, String> {
+        let name: String = name.into();
+        let dependents = self.dependents(&name);
+        if !dependents.is_empty() && self.aliases(&name).len() < 2 {
+            return Err(format!(
+                "Task '{}' is used by '{}'",
+                name,
+                dependents.join("', '")
+            ));
+        }
+
+        self.tasks
+            .remove(&name)
+            .ok_or_else(|| format!("Task '{}' doesn't exist", name))
+    }
 
-            pub fn get<S: Into<String>>(&self, name: S) -> Option<&TaskSource> {
-                self.tasks.get(&name.into())
+    /// Replaces the task `name`, making every task that sampled it sample `task` instead.
+    ///
+    /// Every other name of the task is replaced too, see [`TaskTree::aliases`].
+    /// `task` may sample the task it replaces, but none of its dependents.
+    /// Returns the previous task.
+    pub fn replace<S: Into<String>, V: Into<TaskSource<T>>>(
+        &mut self,
+        name: S,
+        task: V,
+    ) -> Result<TaskSource<T>, String> {
+        let name: String = name.into();
+        let task: TaskSource<T> = task.into();
+        let old = match self.tasks.get(&name) {
+            Some(x) => x.clone(),
+            None => return Err(format!("Task '{}' doesn't exist", name)),
+        };
+
+        let aliases = self.names_of(&old);
+        let mut inputs = vec![];
+        self.named_inputs(&task, &mut inputs);
+        if let Some(x) = inputs
+            .iter()
+            .find(|x| !aliases.contains(x) && self.depends_on(x, &name))
+        {
+            return Err(format!(
+                "Replacing '{}' would create a cycle through '{}'",
+                name, x
+            ));
+        }
+
+        for (x, source) in self.tasks.iter_mut() {
+            if aliases.contains(x) {
+                *source = task.clone();
+            } else {
+                Self::replace_input(source, &old, &task);
             }
+        }
+        Ok(old)
+    }
 
-            pub fn get_mut<S: Into<String>>(&mut self, name: S) -> Option<&mut TaskSource> {
-                self.tasks.get_mut(&name.into())
+    fn replace_input(source: &mut TaskSource<T>, old: &TaskSource<T>, new: &TaskSource<T>) {
+        source.visit_inputs_mut(&mut |_, input| {
+            if input.ptr_eq(old) {
+                *input = new.clone();
+            } else {
+                Self::replace_input(input, old, new);
             }
+        });
+    }
 
-            pub fn sample_1d<S: Into<String>>(&mut self, name: S, x: $type) -> $type {
-                if let Some(task) = &mut self.tasks.get_mut(&name.into()) {
-                    task.sample_1d(x)
-                } else {
-                    0.0
-                }
+    /// Folds constant tasks, skips operations that return their input unchanged and
+    /// merges consecutive scales by constants.
+    ///
+    /// Named tasks keep their name and stay separate tasks unless they fold into a
+    /// constant, so [`TaskTree::replace`] and [`TaskTree::set_param`] keep working.
+    pub fn optimize(&mut self) {
+        let mut named: Vec<TaskSource<T>> = self.tasks.values().cloned().collect();
+        let mut done = vec![];
+        for task in self.tasks.values_mut() {
+            *task = task.simplify(&mut named, &mut done);
+        }
+    }
+
+    /// Describes the tasks of the tree and the tasks they sample, see [`Graph::to_dot`] and
+    /// [`Graph::to_mermaid`].
+    ///
+    /// Anonymous inputs are added as `<parent>.<field>` and constant inputs are listed in
+    /// the settings of the task using them. A task added under several names is added once,
+    /// under its first name, with the other names listed in its `aliases` setting.
+    pub fn graph(&self) -> Graph {
+        let mut graph = Graph::default();
+        for (name, task) in self.tasks.iter() {
+            if self.name_of(task) == Some(name) {
+                self.add_to_graph(&mut graph, name, task);
             }
-            pub fn sample_2d<S: Into<String>>(&mut self, name: S, x: $type, y: $type) -> $type {
-                if let Some(task) = &mut self.tasks.get_mut(&name.into()) {
-                    task.sample_2d(x, y)
-                } else {
-                    0.0
-                }
+        }
+        graph.sort();
+        graph
+    }
+
+    fn add_to_graph(&self, graph: &mut Graph, name: &str, task: &TaskSource<T>) {
+        let mut node = Node {
+            name: name.to_owned(),
+            kind: task.kind().to_owned(),
+            params: task
+                .params()
+                .into_iter()
+                .map(|(k, v)| (k.to_owned(), v))
+                .collect(),
+        };
+        let aliases: Vec<String> = self
+            .names_of(task)
+            .into_iter()
+            .filter(|x| x != name)
+            .collect();
+        if !aliases.is_empty() {
+            node.params.push(("aliases".to_owned(), aliases.join(", ")));
+        }
+
+        for (field, input) in task.inputs() {
+            if let TaskSource::Constant(x) = input {
+                node.params.push((field, x.to_string()));
+                continue;
             }
 
-            pub fn sample_3d<S: Into<String>>(
-                &mut self,
-                name: S,
-                x: $type,
-                y: $type,
-                z: $type,
-            ) -> $type {
-                if let Some(task) = &mut self.tasks.get_mut(&name.into()) {
-                    task.sample_3d(x, y, z)
-                } else {
-                    0.0
+            let from = match self.name_of(&input) {
+                Some(x) => x.to_owned(),
+                None => {
+                    let anonymous = format!("{}.{}", name, field);
+                    self.add_to_graph(graph, &anonymous, &input);
+                    anonymous
                 }
+            };
+            graph.edges.push(Edge {
+                from,
+                to: name.to_owned(),
+                label: Some(field),
+            });
+        }
+
+        graph.nodes.push(node);
+    }
+
+    /// Returns the current value of the parameter `name`.
+    pub fn param<S: Into<String>>(&self, name: S) -> Option<T> {
+        match self.tasks.get(&name.into()) {
+            Some(TaskSource::Parameter(x)) => Some(x.borrow().value()),
+            _ => None,
+        }
+    }
+
+    /// Changes the value of the parameter `name` for every task using it and clears
+    /// every cache, see [`TaskTree::invalidate`].
+    ///
+    /// Returns `false` if there is no parameter with that name.
+    pub fn set_param<S: Into<String>>(&mut self, name: S, value: T) -> bool {
+        match self.tasks.get(&name.into()) {
+            Some(TaskSource::Parameter(x)) => {
+                x.borrow_mut().set(value);
+                self.invalidate();
+                true
             }
+            _ => false,
+        }
+    }
+
+    /// Number of times the caches of the tree have been cleared.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Clears every cache in the tree, so values cached before a task was changed in
+    /// place aren't used anymore.
+    pub fn invalidate(&mut self) {
+        self.generation += 1;
+        let mut visited = vec![];
+        for task in self.tasks.values() {
+            Self::invalidate_caches(task, &mut visited);
+        }
+    }
+
+    fn invalidate_caches(task: &TaskSource<T>, visited: &mut Vec<TaskSource<T>>) {
+        if let TaskSource::Constant(_) = task {
+            return;
+        }
+        if visited.iter().any(|x| x.ptr_eq(task)) {
+            return;
+        }
+        visited.push(task.clone());
+
+        if let TaskSource::Cache(x) = task {
+            x.borrow_mut().invalidate();
+        }
+        task.clone()
+            .visit_inputs_mut(&mut |_, x| Self::invalidate_caches(x, visited));
+    }
+
+    pub fn sample_1d<S: Into<String>>(&mut self, name: S, x: T) -> T {
+        if let Some(task) = &mut self.tasks.get_mut(&name.into()) {
+            task.sample_1d(x)
+        } else {
+            T::ZERO
+        }
+    }
+    pub fn sample_2d<S: Into<String>>(&mut self, name: S, x: T, y: T) -> T {
+        if let Some(task) = &mut self.tasks.get_mut(&name.into()) {
+            task.sample_2d(x, y)
+        } else {
+            T::ZERO
+        }
+    }
+
+    pub fn sample_3d<S: Into<String>>(&mut self, name: S, x: T, y: T, z: T) -> T {
+        if let Some(task) = &mut self.tasks.get_mut(&name.into()) {
+            task.sample_3d(x, y, z)
+        } else {
+            T::ZERO
         }
-    };
+    }
 }
 
 pub mod f32 {
-    use super::super::f32::{Task, TaskSource};
-    use std::collections::HashMap;
-    task_tree!(f32);
+    pub type TaskTree = super::TaskTree<f32>;
 }
 
 pub mod f64 {
-    use super::super::f64::{Task, TaskSource};
-    use std::collections::HashMap;
-    task_tree!(f64);
+    pub type TaskTree = super::TaskTree<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type TaskTree = super::TaskTree<Fixed>;
 }
 
 #[cfg(test)]
@@ -72,7 +363,9 @@ mod tests {
 
     mod f32 {
         use crate::task::f32::{
-            AggregatorBuilder, BiasBuilder, CacheBuilder, Operation, SelectorBuilder, TaskTree,
+            AbsBuilder, AggregatorBuilder, BiasBuilder, CacheBuilder, ClampBuilder,
+            GradientBuilder, InvertBuilder, Operation, Parameter, ScaleBuilder, ScaleOffsetBuilder,
+            SelectorBuilder, TaskSource, TaskTree,
         };
 
         #[test]
@@ -163,11 +456,436 @@ mod tests {
 
             assert_eq!(tree.sample_1d("task 6", 1.0), 1.0);
         }
+
+        #[test]
+        fn param_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            tree.add_task("task 2", 2.0);
+
+            tree.add_task(
+                "task 3",
+                ScaleBuilder::new()
+                    .named_source("task 1")
+                    .named_scale("task 2")
+                    .link(&tree)
+                    .build(),
+            );
+
+            assert_eq!(tree.param("task 1"), Some(0.5));
+            assert_eq!(tree.sample_1d("task 3", 1.0), 1.0);
+
+            assert!(tree.set_param("task 1", 0.25));
+            assert_eq!(tree.param("task 1"), Some(0.25));
+            assert_eq!(tree.sample_1d("task 3", 1.0), 0.5);
+
+            assert!(!tree.set_param("task 2", 0.25));
+            assert!(!tree.set_param("task 4", 0.25));
+            assert_eq!(tree.param("task 2"), None);
+        }
+
+        #[test]
+        fn introspect_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            tree.add_task(
+                "task 2",
+                ScaleBuilder::new()
+                    .named_source("task 1")
+                    .scale(2.0)
+                    .link(&tree)
+                    .build(),
+            );
+            let anonymous = ScaleBuilder::new()
+                .named_source("task 1")
+                .scale(3.0)
+                .link(&tree)
+                .build();
+            tree.add_task(
+                "task 3",
+                AggregatorBuilder::new()
+                    .add_named_task("task 2")
+                    .add_task(anonymous)
+                    .link(&tree)
+                    .build(),
+            );
+
+            let names: Vec<&str> = tree.names().collect();
+            assert_eq!(names, ["task 1", "task 2", "task 3"]);
+
+            let task = tree.get("task 3").unwrap();
+            assert_eq!(task.kind(), "aggregate");
+            assert_eq!(
+                task.params(),
+                [("operator", "add".to_owned()), ("initial", "0".to_owned())]
+            );
+
+            let inputs = tree.get("task 2").unwrap().inputs();
+            assert_eq!(inputs.len(), 2);
+            assert_eq!(inputs[0].0, "scale");
+            assert_eq!(inputs[0].1.params(), [("value", "2".to_owned())]);
+            assert_eq!(inputs[1].0, "source");
+            assert_eq!(tree.name_of(&inputs[1].1), Some("task 1"));
+            assert_eq!(tree.name_of(&inputs[0].1), None);
+
+            // Named tasks are linked after the tasks given directly
+            assert_eq!(tree.dependencies("task 3"), ["task 1", "task 2"]);
+            assert_eq!(tree.dependents("task 1"), ["task 2", "task 3"]);
+            assert!(tree.depends_on("task 3", "task 1"));
+            assert!(!tree.depends_on("task 1", "task 3"));
+
+            let result = tree.remove("task 1");
+            assert!(matches!(result, Err(x) if x == "Task 'task 1' is used by 'task 2', 'task 3'"));
+            assert!(tree.remove("task 4").is_err());
+            assert!(tree.remove("task 3").is_ok());
+            assert!(tree.get("task 3").is_none());
+            assert!(tree.dependents("task 2").is_empty());
+        }
+
+        #[test]
+        fn graph_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            tree.add_task(
+                "task 2",
+                ScaleBuilder::new()
+                    .named_source("task 1")
+                    .scale(2.0)
+                    .link(&tree)
+                    .build(),
+            );
+            let anonymous = ScaleBuilder::new()
+                .named_source("task 1")
+                .scale(3.0)
+                .link(&tree)
+                .build();
+            tree.add_task(
+                "task 3",
+                AggregatorBuilder::new()
+                    .add_named_task("task 2")
+                    .add_task(anonymous)
+                    .link(&tree)
+                    .build(),
+            );
+
+            let graph = tree.graph();
+            let nodes: Vec<(&str, &str)> = graph
+                .nodes
+                .iter()
+                .map(|x| (x.name.as_str(), x.kind.as_str()))
+                .collect();
+            assert_eq!(
+                nodes,
+                [
+                    ("task 1", "parameter"),
+                    ("task 2", "scale"),
+                    ("task 3", "aggregate"),
+                    ("task 3.source.0", "scale"),
+                ]
+            );
+            assert_eq!(
+                graph.nodes[1].params,
+                [("scale".to_owned(), "2".to_owned())]
+            );
+
+            let edges: Vec<(&str, &str, Option<&str>)> = graph
+                .edges
+                .iter()
+                .map(|x| (x.from.as_str(), x.to.as_str(), x.label.as_deref()))
+                .collect();
+            assert_eq!(
+                edges,
+                [
+                    ("task 1", "task 2", Some("source")),
+                    ("task 1", "task 3.source.0", Some("source")),
+                    ("task 2", "task 3", Some("source.1")),
+                    ("task 3.source.0", "task 3", Some("source.0")),
+                ]
+            );
+            assert!(graph
+                .to_dot()
+                .contains("    \"task 2\" [label=\"task 2\\nscale\\nscale = 2\"];\n"));
+            assert!(graph.to_mermaid().contains("    n0 -->|source| n1\n"));
+        }
+
+        #[test]
+        fn invalidate_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            let scale = ScaleBuilder::new()
+                .named_source("task 1")
+                .scale(2.0)
+                .link(&tree)
+                .build();
+            tree.add_task("task 2", CacheBuilder::new().source(scale).build());
+
+            assert_eq!(tree.sample_1d("task 2", 1.0), 1.0);
+            if let Some(TaskSource::Parameter(x)) = tree.get("task 1") {
+                x.borrow_mut().set(1.0);
+            }
+            assert_eq!(tree.sample_1d("task 2", 1.0), 1.0);
+
+            tree.invalidate();
+            assert_eq!(tree.generation(), 1);
+            assert_eq!(tree.sample_1d("task 2", 1.0), 2.0);
+
+            assert!(tree.set_param("task 1", 0.25));
+            assert_eq!(tree.generation(), 2);
+            assert_eq!(tree.sample_1d("task 2", 1.0), 0.5);
+
+            assert!(!tree.set_param("task 2", 0.25));
+            assert_eq!(tree.generation(), 2);
+        }
+
+        #[test]
+        fn optimize_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("height", Parameter::new(0.5));
+            tree.add_task("base", GradientBuilder::new().build());
+            let identity = ScaleBuilder::new()
+                .named_source("base")
+                .scale(1.0)
+                .link(&tree)
+                .build();
+            tree.add_task(
+                "a",
+                AggregatorBuilder::new()
+                    .add_task(identity)
+                    .add_task(2.0)
+                    .add_task(3.0)
+                    .build(),
+            );
+            let inner = ScaleOffsetBuilder::new()
+                .named_source("base")
+                .scale(2.0)
+                .offset(1.0)
+                .link(&tree)
+                .build();
+            tree.add_task(
+                "b",
+                ScaleOffsetBuilder::new()
+                    .source(inner)
+                    .scale(0.5)
+                    .offset(0.25)
+                    .build(),
+            );
+            tree.add_task(
+                "c",
+                AggregatorBuilder::new()
+                    .operation(Operation::Mul)
+                    .initial(1.0)
+                    .add_task(2.0)
+                    .add_task(3.0)
+                    .build(),
+            );
+            tree.add_task(
+                "d",
+                ScaleOffsetBuilder::new()
+                    .named_source("b")
+                    .named_scale("height")
+                    .named_offset("c")
+                    .link(&tree)
+                    .build(),
+            );
+
+            let names = ["a", "b", "c", "d"];
+            let points = [(0.0, 0.0), (0.25, 0.5), (1.0, 0.75)];
+            let mut before = vec![];
+            for name in names {
+                for (x, y) in points {
+                    before.push(tree.sample_2d(name, x, y));
+                }
+            }
+
+            tree.optimize();
+
+            let mut after = vec![];
+            for name in names {
+                for (x, y) in points {
+                    after.push(tree.sample_2d(name, x, y));
+                }
+            }
+            for (x, y) in before.iter().zip(after.iter()) {
+                assert!((x - y).abs() < 1e-6, "{} != {}", x, y);
+            }
+
+            assert!(matches!(tree.get("c"), Some(TaskSource::Constant(x)) if *x == 6.0));
+
+            let a = tree.get("a").unwrap();
+            assert_eq!(a.params()[1], ("initial", "5".to_owned()));
+            assert_eq!(a.inputs().len(), 1);
+            assert_eq!(tree.dependencies("a"), ["base"]);
+
+            let b = tree.get("b").unwrap().inputs();
+            assert!(matches!(b[0].1, TaskSource::Constant(x) if x == 0.75));
+            assert!(matches!(b[1].1, TaskSource::Constant(x) if x == 1.0));
+            assert_eq!(tree.name_of(&b[2].1), Some("base"));
+
+            let d = tree.get("d").unwrap().inputs();
+            assert!(matches!(d[0].1, TaskSource::Constant(x) if x == 6.0));
+            assert_eq!(tree.dependencies("d"), ["height", "b"]);
+
+            let b = tree.sample_2d("b", 0.25, 0.5);
+            assert!(tree.set_param("height", 0.25));
+            assert_eq!(tree.sample_2d("d", 0.25, 0.5), b * 0.25 + 6.0);
+        }
+
+        #[test]
+        fn optimize_modifiers() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("base", GradientBuilder::new().build());
+            let inverted = InvertBuilder::new()
+                .named_source("base")
+                .link(&tree)
+                .build();
+            tree.add_task("a", ScaleBuilder::new().source(inverted).scale(0.5).build());
+            tree.add_task(
+                "b",
+                ClampBuilder::new()
+                    .source(AbsBuilder::new().source(-2.0).build())
+                    .min(0.0)
+                    .max(1.0)
+                    .build(),
+            );
+
+            let a = tree.sample_1d("a", 0.25);
+            tree.optimize();
+
+            assert_eq!(tree.sample_1d("a", 0.25), a);
+            let inputs = tree.get("a").unwrap().inputs();
+            assert!(matches!(inputs[0].1, TaskSource::Constant(x) if x == -0.5));
+            assert_eq!(tree.name_of(&inputs[1].1), Some("base"));
+            assert!(matches!(tree.get("b"), Some(TaskSource::Constant(x)) if *x == 1.0));
+        }
+
+        #[test]
+        fn optimize_then_replace() {
+            let mut tree = TaskTree::new();
+
+            let inner = ScaleBuilder::new()
+                .source(GradientBuilder::new().build())
+                .scale(2.0)
+                .build();
+            tree.add_task("a", ScaleBuilder::new().source(inner).scale(3.0).build());
+            tree.add_task(
+                "b",
+                ScaleBuilder::new()
+                    .named_source("a")
+                    .scale(5.0)
+                    .link(&tree)
+                    .build(),
+            );
+
+            // "a" folds into a single new scale, "b" must keep sampling it through its name
+            tree.optimize();
+            assert_eq!(tree.dependencies("b"), ["a"]);
+            assert!(tree.replace("a", 100.0).is_ok());
+            assert_eq!(tree.sample_1d("b", 0.25), 500.0);
+        }
+
+        #[test]
+        fn replace_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            tree.add_task(
+                "task 2",
+                ScaleBuilder::new()
+                    .named_source("task 1")
+                    .scale(2.0)
+                    .link(&tree)
+                    .build(),
+            );
+            tree.add_task(
+                "task 3",
+                AggregatorBuilder::new()
+                    .add_named_task("task 2")
+                    .add_task(
+                        ScaleBuilder::new()
+                            .named_source("task 1")
+                            .scale(1.0)
+                            .link(&tree)
+                            .build(),
+                    )
+                    .link(&tree)
+                    .build(),
+            );
+            assert_eq!(tree.sample_1d("task 3", 1.0), 1.5);
+
+            assert!(tree.replace("task 1", 1.0).is_ok());
+            assert_eq!(tree.sample_1d("task 2", 1.0), 2.0);
+            assert_eq!(tree.sample_1d("task 3", 1.0), 3.0);
+
+            // Wrapping the task being replaced
+            let wrapped = ScaleBuilder::new()
+                .named_source("task 2")
+                .scale(0.5)
+                .link(&tree)
+                .build();
+            assert!(tree.replace("task 2", wrapped).is_ok());
+            assert_eq!(tree.sample_1d("task 2", 1.0), 1.0);
+            assert_eq!(tree.sample_1d("task 3", 1.0), 2.0);
+            assert_eq!(tree.dependents("task 2"), ["task 3"]);
+
+            let cycle = ScaleBuilder::new()
+                .named_source("task 3")
+                .link(&tree)
+                .build();
+            let result = tree.replace("task 2", cycle);
+            assert!(matches!(result, Err(x) if x.contains("cycle through 'task 3'")));
+            assert!(tree.replace("task 4", 1.0).is_err());
+        }
+
+        #[test]
+        fn aliases_result() {
+            let mut tree = TaskTree::new();
+
+            let base: TaskSource = Parameter::new(0.5).into();
+            tree.add_task("task 1", base.clone());
+            tree.add_task("task 2", base);
+            for (name, source) in [("task 3", "task 1"), ("task 4", "task 2")] {
+                tree.add_task(
+                    name,
+                    ScaleBuilder::new()
+                        .named_source(source)
+                        .scale(2.0)
+                        .link(&tree)
+                        .build(),
+                );
+            }
+            assert_eq!(tree.aliases("task 2"), ["task 1", "task 2"]);
+            assert_eq!(tree.aliases("task 3"), ["task 3"]);
+            assert_eq!(tree.dependencies("task 4"), ["task 1", "task 2"]);
+            assert_eq!(tree.dependents("task 2"), ["task 3", "task 4"]);
+
+            let graph = tree.graph();
+            assert!(!graph.nodes.iter().any(|x| x.name == "task 2"));
+            assert!(graph.nodes[0]
+                .params
+                .contains(&("aliases".to_owned(), "task 2".to_owned())));
+
+            assert!(tree.replace("task 2", Parameter::new(1.0)).is_ok());
+            assert_eq!(tree.sample_1d("task 1", 1.0), 1.0);
+            assert_eq!(tree.sample_1d("task 3", 1.0), 2.0);
+            assert_eq!(tree.sample_1d("task 4", 1.0), 2.0);
+
+            assert!(tree.remove("task 1").is_ok());
+            assert_eq!(tree.aliases("task 2"), ["task 2"]);
+            assert!(tree.remove("task 2").is_err());
+        }
     }
 
     mod f64 {
         use crate::task::f64::{
-            AggregatorBuilder, BiasBuilder, CacheBuilder, Operation, SelectorBuilder, TaskTree,
+            AbsBuilder, AggregatorBuilder, BiasBuilder, CacheBuilder, ClampBuilder,
+            GradientBuilder, InvertBuilder, Operation, Parameter, ScaleBuilder, ScaleOffsetBuilder,
+            SelectorBuilder, TaskSource, TaskTree,
         };
 
         #[test]
@@ -258,5 +976,428 @@ mod tests {
 
             assert_eq!(tree.sample_1d("task 6", 1.0), 1.0);
         }
+
+        #[test]
+        fn param_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            tree.add_task("task 2", 2.0);
+
+            tree.add_task(
+                "task 3",
+                ScaleBuilder::new()
+                    .named_source("task 1")
+                    .named_scale("task 2")
+                    .link(&tree)
+                    .build(),
+            );
+
+            assert_eq!(tree.param("task 1"), Some(0.5));
+            assert_eq!(tree.sample_1d("task 3", 1.0), 1.0);
+
+            assert!(tree.set_param("task 1", 0.25));
+            assert_eq!(tree.param("task 1"), Some(0.25));
+            assert_eq!(tree.sample_1d("task 3", 1.0), 0.5);
+
+            assert!(!tree.set_param("task 2", 0.25));
+            assert!(!tree.set_param("task 4", 0.25));
+            assert_eq!(tree.param("task 2"), None);
+        }
+
+        #[test]
+        fn introspect_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            tree.add_task(
+                "task 2",
+                ScaleBuilder::new()
+                    .named_source("task 1")
+                    .scale(2.0)
+                    .link(&tree)
+                    .build(),
+            );
+            let anonymous = ScaleBuilder::new()
+                .named_source("task 1")
+                .scale(3.0)
+                .link(&tree)
+                .build();
+            tree.add_task(
+                "task 3",
+                AggregatorBuilder::new()
+                    .add_named_task("task 2")
+                    .add_task(anonymous)
+                    .link(&tree)
+                    .build(),
+            );
+
+            let names: Vec<&str> = tree.names().collect();
+            assert_eq!(names, ["task 1", "task 2", "task 3"]);
+
+            let task = tree.get("task 3").unwrap();
+            assert_eq!(task.kind(), "aggregate");
+            assert_eq!(
+                task.params(),
+                [("operator", "add".to_owned()), ("initial", "0".to_owned())]
+            );
+
+            let inputs = tree.get("task 2").unwrap().inputs();
+            assert_eq!(inputs.len(), 2);
+            assert_eq!(inputs[0].0, "scale");
+            assert_eq!(inputs[0].1.params(), [("value", "2".to_owned())]);
+            assert_eq!(inputs[1].0, "source");
+            assert_eq!(tree.name_of(&inputs[1].1), Some("task 1"));
+            assert_eq!(tree.name_of(&inputs[0].1), None);
+
+            // Named tasks are linked after the tasks given directly
+            assert_eq!(tree.dependencies("task 3"), ["task 1", "task 2"]);
+            assert_eq!(tree.dependents("task 1"), ["task 2", "task 3"]);
+            assert!(tree.depends_on("task 3", "task 1"));
+            assert!(!tree.depends_on("task 1", "task 3"));
+
+            let result = tree.remove("task 1");
+            assert!(matches!(result, Err(x) if x == "Task 'task 1' is used by 'task 2', 'task 3'"));
+            assert!(tree.remove("task 4").is_err());
+            assert!(tree.remove("task 3").is_ok());
+            assert!(tree.get("task 3").is_none());
+            assert!(tree.dependents("task 2").is_empty());
+        }
+
+        #[test]
+        fn graph_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            tree.add_task(
+                "task 2",
+                ScaleBuilder::new()
+                    .named_source("task 1")
+                    .scale(2.0)
+                    .link(&tree)
+                    .build(),
+            );
+            let anonymous = ScaleBuilder::new()
+                .named_source("task 1")
+                .scale(3.0)
+                .link(&tree)
+                .build();
+            tree.add_task(
+                "task 3",
+                AggregatorBuilder::new()
+                    .add_named_task("task 2")
+                    .add_task(anonymous)
+                    .link(&tree)
+                    .build(),
+            );
+
+            let graph = tree.graph();
+            let nodes: Vec<(&str, &str)> = graph
+                .nodes
+                .iter()
+                .map(|x| (x.name.as_str(), x.kind.as_str()))
+                .collect();
+            assert_eq!(
+                nodes,
+                [
+                    ("task 1", "parameter"),
+                    ("task 2", "scale"),
+                    ("task 3", "aggregate"),
+                    ("task 3.source.0", "scale"),
+                ]
+            );
+            assert_eq!(
+                graph.nodes[1].params,
+                [("scale".to_owned(), "2".to_owned())]
+            );
+
+            let edges: Vec<(&str, &str, Option<&str>)> = graph
+                .edges
+                .iter()
+                .map(|x| (x.from.as_str(), x.to.as_str(), x.label.as_deref()))
+                .collect();
+            assert_eq!(
+                edges,
+                [
+                    ("task 1", "task 2", Some("source")),
+                    ("task 1", "task 3.source.0", Some("source")),
+                    ("task 2", "task 3", Some("source.1")),
+                    ("task 3.source.0", "task 3", Some("source.0")),
+                ]
+            );
+            assert!(graph
+                .to_dot()
+                .contains("    \"task 2\" [label=\"task 2\\nscale\\nscale = 2\"];\n"));
+            assert!(graph.to_mermaid().contains("    n0 -->|source| n1\n"));
+        }
+
+        #[test]
+        fn invalidate_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            let scale = ScaleBuilder::new()
+                .named_source("task 1")
+                .scale(2.0)
+                .link(&tree)
+                .build();
+            tree.add_task("task 2", CacheBuilder::new().source(scale).build());
+
+            assert_eq!(tree.sample_1d("task 2", 1.0), 1.0);
+            if let Some(TaskSource::Parameter(x)) = tree.get("task 1") {
+                x.borrow_mut().set(1.0);
+            }
+            assert_eq!(tree.sample_1d("task 2", 1.0), 1.0);
+
+            tree.invalidate();
+            assert_eq!(tree.generation(), 1);
+            assert_eq!(tree.sample_1d("task 2", 1.0), 2.0);
+
+            assert!(tree.set_param("task 1", 0.25));
+            assert_eq!(tree.generation(), 2);
+            assert_eq!(tree.sample_1d("task 2", 1.0), 0.5);
+
+            assert!(!tree.set_param("task 2", 0.25));
+            assert_eq!(tree.generation(), 2);
+        }
+
+        #[test]
+        fn optimize_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("height", Parameter::new(0.5));
+            tree.add_task("base", GradientBuilder::new().build());
+            let identity = ScaleBuilder::new()
+                .named_source("base")
+                .scale(1.0)
+                .link(&tree)
+                .build();
+            tree.add_task(
+                "a",
+                AggregatorBuilder::new()
+                    .add_task(identity)
+                    .add_task(2.0)
+                    .add_task(3.0)
+                    .build(),
+            );
+            let inner = ScaleOffsetBuilder::new()
+                .named_source("base")
+                .scale(2.0)
+                .offset(1.0)
+                .link(&tree)
+                .build();
+            tree.add_task(
+                "b",
+                ScaleOffsetBuilder::new()
+                    .source(inner)
+                    .scale(0.5)
+                    .offset(0.25)
+                    .build(),
+            );
+            tree.add_task(
+                "c",
+                AggregatorBuilder::new()
+                    .operation(Operation::Mul)
+                    .initial(1.0)
+                    .add_task(2.0)
+                    .add_task(3.0)
+                    .build(),
+            );
+            tree.add_task(
+                "d",
+                ScaleOffsetBuilder::new()
+                    .named_source("b")
+                    .named_scale("height")
+                    .named_offset("c")
+                    .link(&tree)
+                    .build(),
+            );
+
+            let names = ["a", "b", "c", "d"];
+            let points = [(0.0, 0.0), (0.25, 0.5), (1.0, 0.75)];
+            let mut before = vec![];
+            for name in names {
+                for (x, y) in points {
+                    before.push(tree.sample_2d(name, x, y));
+                }
+            }
+
+            tree.optimize();
+
+            let mut after = vec![];
+            for name in names {
+                for (x, y) in points {
+                    after.push(tree.sample_2d(name, x, y));
+                }
+            }
+            for (x, y) in before.iter().zip(after.iter()) {
+                assert!((x - y).abs() < 1e-6, "{} != {}", x, y);
+            }
+
+            assert!(matches!(tree.get("c"), Some(TaskSource::Constant(x)) if *x == 6.0));
+
+            let a = tree.get("a").unwrap();
+            assert_eq!(a.params()[1], ("initial", "5".to_owned()));
+            assert_eq!(a.inputs().len(), 1);
+            assert_eq!(tree.dependencies("a"), ["base"]);
+
+            let b = tree.get("b").unwrap().inputs();
+            assert!(matches!(b[0].1, TaskSource::Constant(x) if x == 0.75));
+            assert!(matches!(b[1].1, TaskSource::Constant(x) if x == 1.0));
+            assert_eq!(tree.name_of(&b[2].1), Some("base"));
+
+            let d = tree.get("d").unwrap().inputs();
+            assert!(matches!(d[0].1, TaskSource::Constant(x) if x == 6.0));
+            assert_eq!(tree.dependencies("d"), ["height", "b"]);
+
+            let b = tree.sample_2d("b", 0.25, 0.5);
+            assert!(tree.set_param("height", 0.25));
+            assert_eq!(tree.sample_2d("d", 0.25, 0.5), b * 0.25 + 6.0);
+        }
+
+        #[test]
+        fn optimize_modifiers() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("base", GradientBuilder::new().build());
+            let inverted = InvertBuilder::new()
+                .named_source("base")
+                .link(&tree)
+                .build();
+            tree.add_task("a", ScaleBuilder::new().source(inverted).scale(0.5).build());
+            tree.add_task(
+                "b",
+                ClampBuilder::new()
+                    .source(AbsBuilder::new().source(-2.0).build())
+                    .min(0.0)
+                    .max(1.0)
+                    .build(),
+            );
+
+            let a = tree.sample_1d("a", 0.25);
+            tree.optimize();
+
+            assert_eq!(tree.sample_1d("a", 0.25), a);
+            let inputs = tree.get("a").unwrap().inputs();
+            assert!(matches!(inputs[0].1, TaskSource::Constant(x) if x == -0.5));
+            assert_eq!(tree.name_of(&inputs[1].1), Some("base"));
+            assert!(matches!(tree.get("b"), Some(TaskSource::Constant(x)) if *x == 1.0));
+        }
+
+        #[test]
+        fn optimize_then_replace() {
+            let mut tree = TaskTree::new();
+
+            let inner = ScaleBuilder::new()
+                .source(GradientBuilder::new().build())
+                .scale(2.0)
+                .build();
+            tree.add_task("a", ScaleBuilder::new().source(inner).scale(3.0).build());
+            tree.add_task(
+                "b",
+                ScaleBuilder::new()
+                    .named_source("a")
+                    .scale(5.0)
+                    .link(&tree)
+                    .build(),
+            );
+
+            // "a" folds into a single new scale, "b" must keep sampling it through its name
+            tree.optimize();
+            assert_eq!(tree.dependencies("b"), ["a"]);
+            assert!(tree.replace("a", 100.0).is_ok());
+            assert_eq!(tree.sample_1d("b", 0.25), 500.0);
+        }
+
+        #[test]
+        fn replace_result() {
+            let mut tree = TaskTree::new();
+
+            tree.add_task("task 1", Parameter::new(0.5));
+            tree.add_task(
+                "task 2",
+                ScaleBuilder::new()
+                    .named_source("task 1")
+                    .scale(2.0)
+                    .link(&tree)
+                    .build(),
+            );
+            tree.add_task(
+                "task 3",
+                AggregatorBuilder::new()
+                    .add_named_task("task 2")
+                    .add_task(
+                        ScaleBuilder::new()
+                            .named_source("task 1")
+                            .scale(1.0)
+                            .link(&tree)
+                            .build(),
+                    )
+                    .link(&tree)
+                    .build(),
+            );
+            assert_eq!(tree.sample_1d("task 3", 1.0), 1.5);
+
+            assert!(tree.replace("task 1", 1.0).is_ok());
+            assert_eq!(tree.sample_1d("task 2", 1.0), 2.0);
+            assert_eq!(tree.sample_1d("task 3", 1.0), 3.0);
+
+            // Wrapping the task being replaced
+            let wrapped = ScaleBuilder::new()
+                .named_source("task 2")
+                .scale(0.5)
+                .link(&tree)
+                .build();
+            assert!(tree.replace("task 2", wrapped).is_ok());
+            assert_eq!(tree.sample_1d("task 2", 1.0), 1.0);
+            assert_eq!(tree.sample_1d("task 3", 1.0), 2.0);
+            assert_eq!(tree.dependents("task 2"), ["task 3"]);
+
+            let cycle = ScaleBuilder::new()
+                .named_source("task 3")
+                .link(&tree)
+                .build();
+            let result = tree.replace("task 2", cycle);
+            assert!(matches!(result, Err(x) if x.contains("cycle through 'task 3'")));
+            assert!(tree.replace("task 4", 1.0).is_err());
+        }
+
+        #[test]
+        fn aliases_result() {
+            let mut tree = TaskTree::new();
+
+            let base: TaskSource = Parameter::new(0.5).into();
+            tree.add_task("task 1", base.clone());
+            tree.add_task("task 2", base);
+            for (name, source) in [("task 3", "task 1"), ("task 4", "task 2")] {
+                tree.add_task(
+                    name,
+                    ScaleBuilder::new()
+                        .named_source(source)
+                        .scale(2.0)
+                        .link(&tree)
+                        .build(),
+                );
+            }
+            assert_eq!(tree.aliases("task 2"), ["task 1", "task 2"]);
+            assert_eq!(tree.aliases("task 3"), ["task 3"]);
+            assert_eq!(tree.dependencies("task 4"), ["task 1", "task 2"]);
+            assert_eq!(tree.dependents("task 2"), ["task 3", "task 4"]);
+
+            let graph = tree.graph();
+            assert!(!graph.nodes.iter().any(|x| x.name == "task 2"));
+            assert!(graph.nodes[0]
+                .params
+                .contains(&("aliases".to_owned(), "task 2".to_owned())));
+
+            assert!(tree.replace("task 2", Parameter::new(1.0)).is_ok());
+            assert_eq!(tree.sample_1d("task 1", 1.0), 1.0);
+            assert_eq!(tree.sample_1d("task 3", 1.0), 2.0);
+            assert_eq!(tree.sample_1d("task 4", 1.0), 2.0);
+
+            assert!(tree.remove("task 1").is_ok());
+            assert_eq!(tree.aliases("task 2"), ["task 2"]);
+            assert!(tree.remove("task 2").is_err());
+        }
     }
 }
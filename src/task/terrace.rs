@@ -0,0 +1,199 @@
+mod builder;
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::float::Float;
+use crate::math::lerp;
+use crate::task::{Task, TaskSource};
+pub use builder::TerraceBuilder;
+
+/// Task quantizing its source into terraces between sorted levels.
+///
+/// With smooth transitions the output curves from one level up to the next, flat just above a
+/// level and steep just below the next one. Sharp transitions snap to the level below instead.
+/// Inverting the terraces flips every step, so they are steep above a level and flat below the
+/// next one. Sources outside the levels map to the first or last level, terraces without levels
+/// return their source unchanged.
+#[derive(Clone, Debug)]
+pub struct Terrace<T: Float> {
+    pub(crate) invert: bool,
+    pub(crate) levels: Vec<T>,
+    pub(crate) smooth: bool,
+    pub(crate) source: TaskSource<T>,
+}
+
+impl<T: Float> Terrace<T> {
+    pub fn new(levels: Vec<T>, smooth: bool, invert: bool, source: TaskSource<T>) -> Self {
+        let mut levels = levels;
+        levels.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        Self {
+            invert,
+            levels,
+            smooth,
+            source,
+        }
+    }
+
+    pub(crate) fn remap(&self, x: T) -> T {
+        let levels = &self.levels;
+        let i = levels.partition_point(|&l| l <= x);
+        if levels.is_empty() {
+            return x;
+        } else if i == 0 {
+            return levels[0];
+        } else if i == levels.len() {
+            return levels[i - 1];
+        }
+
+        let (mut l0, mut l1) = (levels[i - 1], levels[i]);
+        let mut t = (x - l0) / (l1 - l0);
+        if self.invert {
+            t = T::ONE - t;
+            core::mem::swap(&mut l0, &mut l1);
+        }
+
+        if self.smooth {
+            lerp(l0, l1, t * t)
+        } else if t < T::ONE {
+            l0
+        } else {
+            l1
+        }
+    }
+
+    fn eval<F: Fn(&mut dyn Task<T>) -> T>(&mut self, sampler: F) -> T {
+        let v = sampler(&mut self.source);
+        self.remap(v)
+    }
+}
+
+impl<T: Float> Task<T> for Terrace<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        self.eval(|t| t.sample_1d(x))
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        self.eval(|t| t.sample_2d(x, y))
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        self.eval(|t| t.sample_3d(x, y, z))
+    }
+}
+
+pub mod f32 {
+    pub type Terrace = super::Terrace<f32>;
+    pub type TerraceBuilder = super::TerraceBuilder<f32>;
+}
+
+pub mod f64 {
+    pub type Terrace = super::Terrace<f64>;
+    pub type TerraceBuilder = super::TerraceBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Terrace = super::Terrace<Fixed>;
+    pub type TerraceBuilder = super::TerraceBuilder<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{Task, TerraceBuilder};
+
+        fn terrace(smooth: bool, invert: bool, source: f32) -> f32 {
+            TerraceBuilder::new()
+                .add_level(1.0)
+                .add_level(-1.0)
+                .add_level(0.0)
+                .smooth(smooth)
+                .invert(invert)
+                .source(source)
+                .build()
+                .sample_1d(0.0)
+        }
+
+        #[test]
+        fn terrace_smooth() {
+            assert_eq!(terrace(true, false, -2.0), -1.0);
+            assert_eq!(terrace(true, false, -0.5), -0.75);
+            assert_eq!(terrace(true, false, 0.0), 0.0);
+            assert_eq!(terrace(true, false, 0.5), 0.25);
+            assert_eq!(terrace(true, false, 2.0), 1.0);
+        }
+
+        #[test]
+        fn terrace_smooth_inverted() {
+            assert_eq!(terrace(true, true, -0.5), -0.25);
+            assert_eq!(terrace(true, true, 0.0), 0.0);
+            assert_eq!(terrace(true, true, 0.5), 0.75);
+            assert_eq!(terrace(true, true, 1.0), 1.0);
+        }
+
+        #[test]
+        fn terrace_sharp() {
+            assert_eq!(terrace(false, false, -0.5), -1.0);
+            assert_eq!(terrace(false, false, 0.0), 0.0);
+            assert_eq!(terrace(false, false, 0.99), 0.0);
+            assert_eq!(terrace(false, true, -0.5), 0.0);
+            assert_eq!(terrace(false, true, 0.5), 1.0);
+        }
+
+        #[test]
+        fn terrace_without_levels() {
+            let mut result = TerraceBuilder::new().source(0.3).build();
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.3);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.3);
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{Task, TerraceBuilder};
+
+        fn terrace(smooth: bool, invert: bool, source: f64) -> f64 {
+            TerraceBuilder::new()
+                .levels(vec![-1.0, 0.0, 1.0])
+                .smooth(smooth)
+                .invert(invert)
+                .source(source)
+                .build()
+                .sample_1d(0.0)
+        }
+
+        #[test]
+        fn terrace_smooth() {
+            assert_eq!(terrace(true, false, -2.0), -1.0);
+            assert_eq!(terrace(true, false, -0.5), -0.75);
+            assert_eq!(terrace(true, false, 0.0), 0.0);
+            assert_eq!(terrace(true, false, 0.5), 0.25);
+            assert_eq!(terrace(true, false, 2.0), 1.0);
+        }
+
+        #[test]
+        fn terrace_smooth_inverted() {
+            assert_eq!(terrace(true, true, -0.5), -0.25);
+            assert_eq!(terrace(true, true, 0.0), 0.0);
+            assert_eq!(terrace(true, true, 0.5), 0.75);
+            assert_eq!(terrace(true, true, 1.0), 1.0);
+        }
+
+        #[test]
+        fn terrace_sharp() {
+            assert_eq!(terrace(false, false, -0.5), -1.0);
+            assert_eq!(terrace(false, false, 0.0), 0.0);
+            assert_eq!(terrace(false, false, 0.99), 0.0);
+            assert_eq!(terrace(false, true, -0.5), 0.0);
+            assert_eq!(terrace(false, true, 0.5), 1.0);
+        }
+
+        #[test]
+        fn terrace_without_levels() {
+            let mut result = TerraceBuilder::new().source(0.3).build();
+            assert_eq!(result.sample_2d(1.0, 1.0), 0.3);
+            assert_eq!(result.sample_3d(1.0, 1.0, 1.0), 0.3);
+        }
+    }
+}
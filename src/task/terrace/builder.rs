@@ -0,0 +1,81 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::Terrace;
+use crate::float::Float;
+use crate::task::{named_to_task, source_or_message, NameOrSource, TaskSource, TaskTree};
+
+pub struct TerraceBuilder<T: Float> {
+    invert: bool,
+    levels: Vec<T>,
+    smooth: bool,
+    source: NameOrSource<T>,
+}
+
+impl<T: Float> Default for TerraceBuilder<T> {
+    fn default() -> Self {
+        Self {
+            invert: false,
+            levels: vec![],
+            smooth: true,
+            source: NameOrSource::Source(T::ZERO.into()),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> TerraceBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a terrace level, levels can be added in any order.
+    pub fn add_level(&mut self, level: T) -> &mut Self {
+        self.levels.push(level);
+        self
+    }
+
+    pub fn build(&self) -> Terrace<T> {
+        Terrace::new(
+            self.levels.clone(),
+            self.smooth,
+            self.invert,
+            source_or_message!(self.source, TerraceBuilder<T>),
+        )
+    }
+
+    pub fn invert(&mut self, invert: bool) -> &mut Self {
+        self.invert = invert;
+        self
+    }
+
+    pub fn levels(&mut self, levels: Vec<T>) -> &mut Self {
+        self.levels = levels;
+        self
+    }
+
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        named_to_task!(self.source, tree);
+
+        self
+    }
+
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
+
+    /// Curves smoothly between levels when `true` (the default), snaps to the level below
+    /// otherwise.
+    pub fn smooth(&mut self, smooth: bool) -> &mut Self {
+        self.smooth = smooth;
+        self
+    }
+
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
+}
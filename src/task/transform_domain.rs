@@ -1,8 +1,14 @@
 mod builder;
 
+use alloc::vec;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+pub use builder::TransformDomainBuilder;
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
 #[cfg_attr(
     feature = "serde",
@@ -10,85 +16,345 @@ use serde::{Deserialize, Serialize};
     serde(rename_all = "snake_case")
 )]
 pub enum DomainOperation {
+    /// Adds the values to the coordinates
     #[default]
     Translate,
+    /// Multiplies the coordinates by the values
     Scale,
+    /// Rotates the coordinates by the values in degrees around the x, y and z axes, in that
+    /// order. 2D sampling lies in the z = 0 plane, so it only needs the z angle.
+    Rotate,
+    /// Multiplies the coordinates by the matrix of the operation, then adds the values
+    Affine,
+    /// Adds the values to the coordinates like `Translate`, but samples them at the
+    /// coordinates transformed by the previous operations instead of the input coordinates
+    Warp,
 }
 
-macro_rules! transform_domain {
-    ($type: ty) => {
-        #[derive(Clone, Debug)]
-        pub struct TransformDomain {
-            pub(crate) source: TaskSource,
-            pub(crate) operation: DomainOperation,
-            pub(crate) value: [TaskSource; 3],
-        }
+/// One operation of a [`TransformDomain`] with its values.
+#[derive(Clone, Debug)]
+pub(crate) struct DomainStep<T: Float> {
+    pub(crate) operation: DomainOperation,
+    pub(crate) value: [TaskSource<T>; 3],
+    /// Rows of the linear part of [`DomainOperation::Affine`]
+    pub(crate) matrix: [[T; 3]; 3],
+}
 
-        impl Default for TransformDomain {
-            fn default() -> Self {
-                Self {
-                    operation: DomainOperation::Translate,
-                    source: 0.0.into(),
-                    value: [0.0.into(), 0.0.into(), 0.0.into()],
-                }
-            }
+impl<T: Float> DomainStep<T> {
+    /// Identity matrix, the default of [`DomainOperation::Affine`].
+    pub(crate) const IDENTITY: [[T; 3]; 3] = [
+        [T::ONE, T::ZERO, T::ZERO],
+        [T::ZERO, T::ONE, T::ZERO],
+        [T::ZERO, T::ZERO, T::ONE],
+    ];
+
+    /// The value every input must have for the operation to leave the coordinates unchanged,
+    /// `None` if the matrix already changes them.
+    pub(crate) fn identity_value(&self) -> Option<T> {
+        use DomainOperation::*;
+        match self.operation {
+            Translate | Rotate | Warp => Some(T::ZERO),
+            Scale => Some(T::ONE),
+            Affine if self.matrix == Self::IDENTITY => Some(T::ZERO),
+            Affine => None,
         }
+    }
 
-        impl Task for TransformDomain {
-            fn sample_1d(&mut self, x: $type) -> $type {
-                let result = self.eval([x, 0.0, 0.0], |t| t.sample_1d(x));
-                self.source.sample_1d(result[0])
+    fn apply(&mut self, input: [T; 3], current: [T; 3], dims: usize) -> [T; 3] {
+        use DomainOperation::*;
+        let at = match self.operation {
+            Warp => current,
+            _ => input,
+        };
+        let [vx, vy, vz] = &mut self.value;
+        let v = [
+            sample(vx, at, dims),
+            sample(vy, at, dims),
+            sample(vz, at, dims),
+        ];
+        let [x, y, z] = current;
+        match self.operation {
+            Translate | Warp => [x + v[0], y + v[1], z + v[2]],
+            Scale => [x * v[0], y * v[1], z * v[2]],
+            Rotate => rotate(current, v),
+            Affine => {
+                let m = &self.matrix;
+                [
+                    m[0][0] * x + m[0][1] * y + m[0][2] * z + v[0],
+                    m[1][0] * x + m[1][1] * y + m[1][2] * z + v[1],
+                    m[2][0] * x + m[2][1] * y + m[2][2] * z + v[2],
+                ]
             }
+        }
+    }
+}
 
-            fn sample_2d(&mut self, x: $type, y: $type) -> $type {
-                let result = self.eval([x, y, 0.0], |t| t.sample_2d(x, y));
-                self.source.sample_2d(result[0], result[1])
-            }
+/// Task transforming the coordinates its source is sampled at.
+///
+/// The operations are applied in order, each to the coordinates returned by the previous one,
+/// so a single task can e.g. scale, rotate and warp the domain.
+#[derive(Clone, Debug)]
+pub struct TransformDomain<T: Float> {
+    pub(crate) source: TaskSource<T>,
+    pub(crate) steps: Vec<DomainStep<T>>,
+}
 
-            fn sample_3d(&mut self, x: $type, y: $type, z: $type) -> $type {
-                let result = self.eval([x, y, z], |t| t.sample_3d(x, y, z));
-                self.source.sample_3d(result[0], result[1], result[2])
-            }
+impl<T: Float> Default for TransformDomain<T> {
+    fn default() -> Self {
+        Self {
+            source: T::ZERO.into(),
+            steps: vec![],
         }
+    }
+}
 
-        impl TransformDomain {
-            fn eval<F: Fn(&mut dyn Task) -> $type>(
-                &mut self,
-                args: [$type; 3],
-                sampler: F,
-            ) -> [$type; 3] {
-                use DomainOperation::*;
-                let v: Vec<$type> = match &self.operation {
-                    Translate => args
-                        .iter()
-                        .zip(self.value.iter_mut())
-                        .map(|(x, y)| *x + sampler(y))
-                        .collect(),
-                    Scale => args
-                        .iter()
-                        .zip(self.value.iter_mut())
-                        .map(|(x, y)| *x * sampler(y))
-                        .collect(),
-                };
-
-                [v[0], v[1], v[2]]
-            }
+/// Samples `task` at the first `dims` coordinates of `p`.
+fn sample<T: Float>(task: &mut TaskSource<T>, [x, y, z]: [T; 3], dims: usize) -> T {
+    match dims {
+        1 => task.sample_1d(x),
+        2 => task.sample_2d(x, y),
+        _ => task.sample_3d(x, y, z),
+    }
+}
+
+/// Rotates `[x, y, z]` around the x, y and z axes by `angles` in degrees, in that order.
+fn rotate<T: Float>([x, y, z]: [T; 3], angles: [T; 3]) -> [T; 3] {
+    let radians = |degrees: T| degrees * T::from_f64(core::f64::consts::PI) / T::from_f64(180.0);
+    let [ax, ay, az] = angles.map(radians);
+    let (sx, cx) = (ax.sin(), ax.cos());
+    let (sy, cy) = (ay.sin(), ay.cos());
+    let (sz, cz) = (az.sin(), az.cos());
+
+    let (y, z) = (y * cx - z * sx, y * sx + z * cx);
+    let (x, z) = (x * cy + z * sy, z * cy - x * sy);
+    let (x, y) = (x * cz - y * sz, x * sz + y * cz);
+    [x, y, z]
+}
+
+impl<T: Float> Task<T> for TransformDomain<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        let result = self.eval([x, T::ZERO, T::ZERO], 1);
+        self.source.sample_1d(result[0])
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        let result = self.eval([x, y, T::ZERO], 2);
+        self.source.sample_2d(result[0], result[1])
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        let result = self.eval([x, y, z], 3);
+        self.source.sample_3d(result[0], result[1], result[2])
+    }
+}
+
+impl<T: Float> TransformDomain<T> {
+    /// Applies every operation to the first `dims` coordinates of `args`.
+    fn eval(&mut self, args: [T; 3], dims: usize) -> [T; 3] {
+        let mut result = args;
+        for step in self.steps.iter_mut() {
+            result = step.apply(args, result, dims);
         }
-    };
+        result
+    }
 }
 
 pub mod f32 {
-    pub use super::builder::f32::TransformDomainBuilder;
     pub use super::DomainOperation;
-    use crate::task::f32::{Task, TaskSource};
-
-    transform_domain!(f32);
+    pub type TransformDomain = super::TransformDomain<f32>;
+    pub type TransformDomainBuilder = super::TransformDomainBuilder<f32>;
 }
 
 pub mod f64 {
-    pub use super::builder::f64::TransformDomainBuilder;
     pub use super::DomainOperation;
-    use crate::task::f64::{Task, TaskSource};
+    pub type TransformDomain = super::TransformDomain<f64>;
+    pub type TransformDomainBuilder = super::TransformDomainBuilder<f64>;
+}
+
+pub mod fixed {
+    pub use super::DomainOperation;
+    use crate::float::Fixed;
+    pub type TransformDomain = super::TransformDomain<Fixed>;
+    pub type TransformDomainBuilder = super::TransformDomainBuilder<Fixed>;
+}
 
-    transform_domain!(f64);
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{
+            DomainOperation, GradientBuilder, Task, TaskSource, TransformDomainBuilder,
+        };
+
+        fn transform(operation: DomainOperation, value: [f32; 3], point: [f32; 3]) -> [f32; 3] {
+            let mut task = TransformDomainBuilder::new()
+                .operation(operation)
+                .matrix([[0.0, -2.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 3.0]])
+                .value_x(value[0])
+                .value_y(value[1])
+                .value_z(value[2])
+                .build();
+            task.eval(point, 3).map(|v| (v * 1e4).round() / 1e4)
+        }
+
+        #[test]
+        fn transform_domain_rotate() {
+            let rotate = |angles, point| transform(DomainOperation::Rotate, angles, point);
+            assert_eq!(rotate([0.0, 0.0, 90.0], [1.0, 2.0, 3.0]), [-2.0, 1.0, 3.0]);
+            assert_eq!(rotate([90.0, 0.0, 0.0], [1.0, 2.0, 3.0]), [1.0, -3.0, 2.0]);
+            assert_eq!(rotate([0.0, 90.0, 0.0], [1.0, 2.0, 3.0]), [3.0, 2.0, -1.0]);
+            assert_eq!(rotate([90.0, 0.0, 90.0], [1.0, 2.0, 3.0]), [3.0, 1.0, 2.0]);
+            assert_eq!(
+                rotate([0.0, 0.0, -180.0], [1.0, 2.0, 0.0]),
+                [-1.0, -2.0, 0.0]
+            );
+        }
+
+        #[test]
+        fn transform_domain_affine() {
+            let affine = |offset, point| transform(DomainOperation::Affine, offset, point);
+            assert_eq!(affine([0.0, 0.0, 0.0], [1.0, 2.0, 3.0]), [-4.0, 1.0, 9.0]);
+            assert_eq!(affine([0.5, -1.0, 2.0], [1.0, 2.0, 3.0]), [-3.5, 0.0, 11.0]);
+
+            let mut task = TransformDomainBuilder::new()
+                .operation(DomainOperation::Affine)
+                .source(0.5)
+                .build();
+            assert_eq!(task.sample_2d(1.0, 2.0), 0.5);
+        }
+
+        fn gradient() -> TaskSource {
+            GradientBuilder::new()
+                .s1([-4.0, -4.0, -4.0])
+                .s2([4.0, 4.0, 4.0])
+                .build()
+                .into()
+        }
+
+        #[test]
+        fn transform_domain_operations() {
+            let mut task = TransformDomainBuilder::new()
+                .operation(DomainOperation::Scale)
+                .value_x(2.0)
+                .value_y(3.0)
+                .value_z(1.0)
+                .add_operation(DomainOperation::Rotate)
+                .value_z(90.0)
+                .add_operation(DomainOperation::Translate)
+                .value_x(0.5)
+                .build();
+            let result = task.eval([1.0, 1.0, 1.0], 3);
+            assert_eq!(result.map(|v| (v * 1e4).round() / 1e4), [-2.5, 2.0, 1.0]);
+
+            // Translations sample their values at the input coordinates, warps at the
+            // coordinates transformed so far
+            let mut source = gradient();
+            let mut translate = TransformDomainBuilder::new()
+                .operation(DomainOperation::Scale)
+                .value_x(2.0)
+                .add_operation(DomainOperation::Translate)
+                .value_x(gradient())
+                .source(gradient())
+                .build();
+            let mut warp = TransformDomainBuilder::new()
+                .operation(DomainOperation::Scale)
+                .value_x(2.0)
+                .add_operation(DomainOperation::Warp)
+                .value_x(gradient())
+                .source(gradient())
+                .build();
+            let offset = source.sample_1d(0.75);
+            assert_eq!(translate.sample_1d(0.75), source.sample_1d(1.5 + offset));
+            let offset = source.sample_1d(1.5);
+            assert_eq!(warp.sample_1d(0.75), source.sample_1d(1.5 + offset));
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{
+            DomainOperation, GradientBuilder, Task, TaskSource, TransformDomainBuilder,
+        };
+
+        fn transform(operation: DomainOperation, value: [f64; 3], point: [f64; 3]) -> [f64; 3] {
+            let mut task = TransformDomainBuilder::new()
+                .operation(operation)
+                .matrix([[0.0, -2.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 3.0]])
+                .value_x(value[0])
+                .value_y(value[1])
+                .value_z(value[2])
+                .build();
+            task.eval(point, 3).map(|v| (v * 1e8).round() / 1e8)
+        }
+
+        #[test]
+        fn transform_domain_rotate() {
+            let rotate = |angles, point| transform(DomainOperation::Rotate, angles, point);
+            assert_eq!(rotate([0.0, 0.0, 90.0], [1.0, 2.0, 3.0]), [-2.0, 1.0, 3.0]);
+            assert_eq!(rotate([90.0, 0.0, 0.0], [1.0, 2.0, 3.0]), [1.0, -3.0, 2.0]);
+            assert_eq!(rotate([0.0, 90.0, 0.0], [1.0, 2.0, 3.0]), [3.0, 2.0, -1.0]);
+            assert_eq!(rotate([90.0, 0.0, 90.0], [1.0, 2.0, 3.0]), [3.0, 1.0, 2.0]);
+            assert_eq!(
+                rotate([0.0, 0.0, -180.0], [1.0, 2.0, 0.0]),
+                [-1.0, -2.0, 0.0]
+            );
+        }
+
+        #[test]
+        fn transform_domain_affine() {
+            let affine = |offset, point| transform(DomainOperation::Affine, offset, point);
+            assert_eq!(affine([0.0, 0.0, 0.0], [1.0, 2.0, 3.0]), [-4.0, 1.0, 9.0]);
+            assert_eq!(affine([0.5, -1.0, 2.0], [1.0, 2.0, 3.0]), [-3.5, 0.0, 11.0]);
+
+            let mut task = TransformDomainBuilder::new()
+                .operation(DomainOperation::Affine)
+                .source(0.5)
+                .build();
+            assert_eq!(task.sample_2d(1.0, 2.0), 0.5);
+        }
+
+        fn gradient() -> TaskSource {
+            GradientBuilder::new()
+                .s1([-4.0, -4.0, -4.0])
+                .s2([4.0, 4.0, 4.0])
+                .build()
+                .into()
+        }
+
+        #[test]
+        fn transform_domain_operations() {
+            let mut task = TransformDomainBuilder::new()
+                .operation(DomainOperation::Scale)
+                .value_x(2.0)
+                .value_y(3.0)
+                .value_z(1.0)
+                .add_operation(DomainOperation::Rotate)
+                .value_z(90.0)
+                .add_operation(DomainOperation::Translate)
+                .value_x(0.5)
+                .build();
+            let result = task.eval([1.0, 1.0, 1.0], 3);
+            assert_eq!(result.map(|v| (v * 1e8).round() / 1e8), [-2.5, 2.0, 1.0]);
+
+            // Translations sample their values at the input coordinates, warps at the
+            // coordinates transformed so far
+            let mut source = gradient();
+            let mut translate = TransformDomainBuilder::new()
+                .operation(DomainOperation::Scale)
+                .value_x(2.0)
+                .add_operation(DomainOperation::Translate)
+                .value_x(gradient())
+                .source(gradient())
+                .build();
+            let mut warp = TransformDomainBuilder::new()
+                .operation(DomainOperation::Scale)
+                .value_x(2.0)
+                .add_operation(DomainOperation::Warp)
+                .value_x(gradient())
+                .source(gradient())
+                .build();
+            let offset = source.sample_1d(0.75);
+            assert_eq!(translate.sample_1d(0.75), source.sample_1d(1.5 + offset));
+            let offset = source.sample_1d(1.5);
+            assert_eq!(warp.sample_1d(0.75), source.sample_1d(1.5 + offset));
+        }
+    }
 }
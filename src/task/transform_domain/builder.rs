@@ -1,114 +1,161 @@
-use super::DomainOperation;
-
-macro_rules! transform_domain_builder {
-    ($type: ty) => {
-        pub struct TransformDomainBuilder {
-            operation: DomainOperation,
-            value_x: NameOrSource,
-            value_y: NameOrSource,
-            value_z: NameOrSource,
-            source: NameOrSource,
-        }
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{DomainOperation, DomainStep, TransformDomain};
+use crate::float::Float;
+use crate::task::{named_to_task, source_or_message, NameOrSource, TaskSource, TaskTree};
+
+struct DomainStepBuilder<T: Float> {
+    operation: DomainOperation,
+    value_x: NameOrSource<T>,
+    value_y: NameOrSource<T>,
+    value_z: NameOrSource<T>,
+    matrix: [[T; 3]; 3],
+}
 
-        impl Default for TransformDomainBuilder {
-            fn default() -> Self {
-                Self {
-                    operation: DomainOperation::Translate,
-                    value_x: NameOrSource::Source(0.0.into()),
-                    value_y: NameOrSource::Source(0.0.into()),
-                    value_z: NameOrSource::Source(0.0.into()),
-                    source: NameOrSource::Source(0.0.into()),
-                }
-            }
+impl<T: Float> DomainStepBuilder<T> {
+    fn new(operation: DomainOperation) -> Self {
+        Self {
+            operation,
+            value_x: NameOrSource::Source(T::ZERO.into()),
+            value_y: NameOrSource::Source(T::ZERO.into()),
+            value_z: NameOrSource::Source(T::ZERO.into()),
+            matrix: DomainStep::IDENTITY,
         }
-
-        impl TransformDomainBuilder {
-            pub fn new() -> Self {
-                Self::default()
-            }
-
-            pub fn build(&self) -> TransformDomain {
-                TransformDomain {
-                    source: source_or_message!(self.source, TransformDomainBuilder),
-                    operation: self.operation,
-                    value: [
-                        source_or_message!(self.value_x, TransformDomainBuilder),
-                        source_or_message!(self.value_y, TransformDomainBuilder),
-                        source_or_message!(self.value_z, TransformDomainBuilder),
-                    ],
-                }
-            }
-
-            pub fn link(&mut self, tree: &TaskTree) -> &mut Self {
-                named_to_task!(self.value_x, tree);
-                named_to_task!(self.value_y, tree);
-                named_to_task!(self.value_z, tree);
-                named_to_task!(self.source, tree);
-                self
-            }
-
-            pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.source = NameOrSource::Named(name.into());
-                self
-            }
-
-            pub fn named_value_x<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.value_z = NameOrSource::Named(name.into());
-                self
-            }
-
-            pub fn named_value_y<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.value_y = NameOrSource::Named(name.into());
-                self
-            }
-
-            pub fn named_value_z<S: Into<String>>(&mut self, name: S) -> &mut Self {
-                self.value_z = NameOrSource::Named(name.into());
-                self
-            }
-
-            pub fn operation(&mut self, operation: DomainOperation) -> &mut Self {
-                self.operation = operation;
-                self
-            }
-
-            pub fn source<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.source = NameOrSource::Source(task.into());
-                self
-            }
-
-            pub fn value_x<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.value_x = NameOrSource::Source(task.into());
-                self
-            }
-
-            pub fn value_y<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.value_y = NameOrSource::Source(task.into());
-                self
-            }
-
-            pub fn value_z<V: Into<TaskSource>>(&mut self, task: V) -> &mut Self {
-                self.value_z = NameOrSource::Source(task.into());
-                self
-            }
+    }
+
+    fn build(&self) -> DomainStep<T> {
+        DomainStep {
+            operation: self.operation,
+            value: [
+                source_or_message!(self.value_x, TransformDomainBuilder<T>),
+                source_or_message!(self.value_y, TransformDomainBuilder<T>),
+                source_or_message!(self.value_z, TransformDomainBuilder<T>),
+            ],
+            matrix: self.matrix,
         }
-    };
+    }
+
+    fn link(&mut self, tree: &TaskTree<T>) {
+        named_to_task!(self.value_x, tree);
+        named_to_task!(self.value_y, tree);
+        named_to_task!(self.value_z, tree);
+    }
+}
+
+/// Builds a [`TransformDomain`] from a list of operations.
+///
+/// It starts with a single operation, the value and matrix setters apply to the operation
+/// added last:
+///
+/// ```
+/// use ferro_noise::task::f64::{DomainOperation, TransformDomainBuilder};
+///
+/// let task = TransformDomainBuilder::new()
+///     .operation(DomainOperation::Scale)
+///     .value_x(2.0)
+///     .value_y(2.0)
+///     .add_operation(DomainOperation::Rotate)
+///     .value_z(30.0)
+///     .source(1.0)
+///     .build();
+/// ```
+pub struct TransformDomainBuilder<T: Float> {
+    steps: Vec<DomainStepBuilder<T>>,
+    source: NameOrSource<T>,
 }
 
-pub mod f32 {
-    use super::DomainOperation;
-    use crate::task::{
-        f32::{NameOrSource, TaskSource, TaskTree, TransformDomain},
-        named_to_task, source_or_message,
-    };
-    transform_domain_builder!(f32);
+impl<T: Float> Default for TransformDomainBuilder<T> {
+    fn default() -> Self {
+        Self {
+            steps: vec![DomainStepBuilder::new(DomainOperation::Translate)],
+            source: NameOrSource::Source(T::ZERO.into()),
+        }
+    }
 }
 
-pub mod f64 {
-    use super::DomainOperation;
-    use crate::task::{
-        f64::{NameOrSource, TaskSource, TaskTree, TransformDomain},
-        named_to_task, source_or_message,
-    };
-    transform_domain_builder!(f64);
+impl<T: Float> TransformDomainBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn last(&mut self) -> &mut DomainStepBuilder<T> {
+        self.steps
+            .last_mut()
+            .expect("TransformDomainBuilder without operations")
+    }
+
+    /// Adds an operation applied after the previous ones, with values of zero.
+    pub fn add_operation(&mut self, operation: DomainOperation) -> &mut Self {
+        self.steps.push(DomainStepBuilder::new(operation));
+        self
+    }
+
+    pub fn build(&self) -> TransformDomain<T> {
+        TransformDomain {
+            source: source_or_message!(self.source, TransformDomainBuilder<T>),
+            steps: self.steps.iter().map(|x| x.build()).collect(),
+        }
+    }
+
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        for step in self.steps.iter_mut() {
+            step.link(tree);
+        }
+        named_to_task!(self.source, tree);
+        self
+    }
+
+    /// Sets the rows of the matrix used by [`DomainOperation::Affine`], the identity by default.
+    pub fn matrix(&mut self, matrix: [[T; 3]; 3]) -> &mut Self {
+        self.last().matrix = matrix;
+        self
+    }
+
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_value_x<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.last().value_x = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_value_y<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.last().value_y = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_value_z<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.last().value_z = NameOrSource::Named(name.into());
+        self
+    }
+
+    /// Sets the operation added last.
+    pub fn operation(&mut self, operation: DomainOperation) -> &mut Self {
+        self.last().operation = operation;
+        self
+    }
+
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
+
+    pub fn value_x<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.last().value_x = NameOrSource::Source(task.into());
+        self
+    }
+
+    pub fn value_y<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.last().value_y = NameOrSource::Source(task.into());
+        self
+    }
+
+    pub fn value_z<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.last().value_z = NameOrSource::Source(task.into());
+        self
+    }
 }
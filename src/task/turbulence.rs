@@ -0,0 +1,176 @@
+mod builder;
+
+use crate::float::Float;
+use crate::task::{Task, TaskSource};
+pub use builder::TurbulenceBuilder;
+
+/// Task warping the domain of its source.
+///
+/// The coordinates are displaced by the outputs of `dx`, `dy` and `dz` times `power` before
+/// sampling the source, so the displacement tasks should be noise with different seeds. Each
+/// further iteration samples the displacement tasks again at the displaced coordinates, warping
+/// the warp. Without iterations the source is sampled as is.
+#[derive(Clone, Debug)]
+pub struct Turbulence<T: Float> {
+    pub(crate) dx: TaskSource<T>,
+    pub(crate) dy: TaskSource<T>,
+    pub(crate) dz: TaskSource<T>,
+    pub(crate) iterations: usize,
+    pub(crate) power: TaskSource<T>,
+    pub(crate) source: TaskSource<T>,
+}
+
+impl<T: Float> Task<T> for Turbulence<T> {
+    fn sample_1d(&mut self, x: T) -> T {
+        let power = self.power.sample_1d(x);
+        let mut ox = T::ZERO;
+        for _ in 0..self.iterations {
+            ox = self.dx.sample_1d(x + ox) * power;
+        }
+        self.source.sample_1d(x + ox)
+    }
+
+    fn sample_2d(&mut self, x: T, y: T) -> T {
+        let power = self.power.sample_2d(x, y);
+        let (mut ox, mut oy) = (T::ZERO, T::ZERO);
+        for _ in 0..self.iterations {
+            let (qx, qy) = (x + ox, y + oy);
+            ox = self.dx.sample_2d(qx, qy) * power;
+            oy = self.dy.sample_2d(qx, qy) * power;
+        }
+        self.source.sample_2d(x + ox, y + oy)
+    }
+
+    fn sample_3d(&mut self, x: T, y: T, z: T) -> T {
+        let power = self.power.sample_3d(x, y, z);
+        let (mut ox, mut oy, mut oz) = (T::ZERO, T::ZERO, T::ZERO);
+        for _ in 0..self.iterations {
+            let (qx, qy, qz) = (x + ox, y + oy, z + oz);
+            ox = self.dx.sample_3d(qx, qy, qz) * power;
+            oy = self.dy.sample_3d(qx, qy, qz) * power;
+            oz = self.dz.sample_3d(qx, qy, qz) * power;
+        }
+        self.source.sample_3d(x + ox, y + oy, z + oz)
+    }
+}
+
+pub mod f32 {
+    pub type Turbulence = super::Turbulence<f32>;
+    pub type TurbulenceBuilder = super::TurbulenceBuilder<f32>;
+}
+
+pub mod f64 {
+    pub type Turbulence = super::Turbulence<f64>;
+    pub type TurbulenceBuilder = super::TurbulenceBuilder<f64>;
+}
+
+pub mod fixed {
+    use crate::float::Fixed;
+    pub type Turbulence = super::Turbulence<Fixed>;
+    pub type TurbulenceBuilder = super::TurbulenceBuilder<Fixed>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod f32 {
+        use crate::task::f32::{GradientBuilder, Task, TaskSource, TurbulenceBuilder};
+
+        fn gradient() -> TaskSource {
+            GradientBuilder::new()
+                .s1([-4.0, -4.0, -4.0])
+                .s2([4.0, 4.0, 4.0])
+                .build()
+                .into()
+        }
+
+        #[test]
+        fn turbulence_displaces_coordinates() {
+            let mut source = gradient();
+            let mut result = TurbulenceBuilder::new()
+                .dx(0.25)
+                .dy(-0.5)
+                .dz(1.0)
+                .power(2.0)
+                .source(gradient())
+                .build();
+            assert_eq!(result.sample_1d(0.1), source.sample_1d(0.6));
+            assert_eq!(result.sample_2d(0.1, 0.2), source.sample_2d(0.6, -0.8));
+            assert_eq!(
+                result.sample_3d(0.1, 0.2, 0.3),
+                source.sample_3d(0.6, -0.8, 2.3)
+            );
+        }
+
+        #[test]
+        fn turbulence_iterations() {
+            let mut warp = gradient();
+            let mut source = gradient();
+            let turbulence = |iterations| {
+                TurbulenceBuilder::new()
+                    .dx(gradient())
+                    .power(0.5)
+                    .iterations(iterations)
+                    .source(gradient())
+                    .build()
+                    .sample_1d(1.0)
+            };
+
+            assert_eq!(turbulence(0), source.sample_1d(1.0));
+            let once = warp.sample_1d(1.0) * 0.5;
+            assert_eq!(turbulence(1), source.sample_1d(1.0 + once));
+            let twice = warp.sample_1d(1.0 + once) * 0.5;
+            assert_eq!(turbulence(2), source.sample_1d(1.0 + twice));
+        }
+    }
+
+    mod f64 {
+        use crate::task::f64::{GradientBuilder, Task, TaskSource, TurbulenceBuilder};
+
+        fn gradient() -> TaskSource {
+            GradientBuilder::new()
+                .s1([-4.0, -4.0, -4.0])
+                .s2([4.0, 4.0, 4.0])
+                .build()
+                .into()
+        }
+
+        #[test]
+        fn turbulence_displaces_coordinates() {
+            let mut source = gradient();
+            let mut result = TurbulenceBuilder::new()
+                .dx(0.25)
+                .dy(-0.5)
+                .dz(1.0)
+                .power(2.0)
+                .source(gradient())
+                .build();
+            assert_eq!(result.sample_1d(0.1), source.sample_1d(0.6));
+            assert_eq!(result.sample_2d(0.1, 0.2), source.sample_2d(0.6, -0.8));
+            assert_eq!(
+                result.sample_3d(0.1, 0.2, 0.3),
+                source.sample_3d(0.6, -0.8, 2.3)
+            );
+        }
+
+        #[test]
+        fn turbulence_iterations() {
+            let mut warp = gradient();
+            let mut source = gradient();
+            let turbulence = |iterations| {
+                TurbulenceBuilder::new()
+                    .dx(gradient())
+                    .power(0.5)
+                    .iterations(iterations)
+                    .source(gradient())
+                    .build()
+                    .sample_1d(1.0)
+            };
+
+            assert_eq!(turbulence(0), source.sample_1d(1.0));
+            let once = warp.sample_1d(1.0) * 0.5;
+            assert_eq!(turbulence(1), source.sample_1d(1.0 + once));
+            let twice = warp.sample_1d(1.0 + once) * 0.5;
+            assert_eq!(turbulence(2), source.sample_1d(1.0 + twice));
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::string::String;
+
+use super::Turbulence;
+use crate::float::Float;
+use crate::task::{named_to_task, source_or_message, NameOrSource, TaskSource, TaskTree};
+
+pub struct TurbulenceBuilder<T: Float> {
+    dx: NameOrSource<T>,
+    dy: NameOrSource<T>,
+    dz: NameOrSource<T>,
+    iterations: usize,
+    power: NameOrSource<T>,
+    source: NameOrSource<T>,
+}
+
+impl<T: Float> Default for TurbulenceBuilder<T> {
+    fn default() -> Self {
+        Self {
+            dx: NameOrSource::Source(T::ZERO.into()),
+            dy: NameOrSource::Source(T::ZERO.into()),
+            dz: NameOrSource::Source(T::ZERO.into()),
+            iterations: 1,
+            power: NameOrSource::Source(T::ONE.into()),
+            source: NameOrSource::Source(T::ZERO.into()),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> TurbulenceBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build(&self) -> Turbulence<T> {
+        Turbulence {
+            dx: source_or_message!(self.dx, TurbulenceBuilder<T>),
+            dy: source_or_message!(self.dy, TurbulenceBuilder<T>),
+            dz: source_or_message!(self.dz, TurbulenceBuilder<T>),
+            iterations: self.iterations,
+            power: source_or_message!(self.power, TurbulenceBuilder<T>),
+            source: source_or_message!(self.source, TurbulenceBuilder<T>),
+        }
+    }
+
+    /// Task displacing the x coordinate.
+    pub fn dx<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.dx = NameOrSource::Source(task.into());
+        self
+    }
+
+    /// Task displacing the y coordinate.
+    pub fn dy<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.dy = NameOrSource::Source(task.into());
+        self
+    }
+
+    /// Task displacing the z coordinate.
+    pub fn dz<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.dz = NameOrSource::Source(task.into());
+        self
+    }
+
+    /// Number of times the displacement is applied, 1 by default.
+    pub fn iterations(&mut self, iterations: usize) -> &mut Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Link named tasks to their task tree values
+    pub fn link(&mut self, tree: &TaskTree<T>) -> &mut Self {
+        named_to_task!(self.dx, tree);
+        named_to_task!(self.dy, tree);
+        named_to_task!(self.dz, tree);
+        named_to_task!(self.power, tree);
+        named_to_task!(self.source, tree);
+
+        self
+    }
+
+    pub fn named_dx<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.dx = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_dy<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.dy = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_dz<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.dz = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_power<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.power = NameOrSource::Named(name.into());
+        self
+    }
+
+    pub fn named_source<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.source = NameOrSource::Named(name.into());
+        self
+    }
+
+    /// Factor the displacement tasks are scaled by, 1 by default.
+    pub fn power<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.power = NameOrSource::Source(task.into());
+        self
+    }
+
+    pub fn source<V: Into<TaskSource<T>>>(&mut self, task: V) -> &mut Self {
+        self.source = NameOrSource::Source(task.into());
+        self
+    }
+}